@@ -35,21 +35,150 @@ const TABLE: [u32; 256] = [
     0xAFB010B1, 0xAB710D06, 0xA6322BDF, 0xA2F33668, 0xBCB4666D, 0xB8757BDA, 0xB5365D03, 0xB1F740B4,
 ];
 
+/// MPEG2-TSのCRC32（CRC-32/MPEG-2）における生成多項式。
+///
+/// 入出力のビット反転は行わず、最終XORも行わない。
+pub const CRC32_MPEG_POLY: u32 = 0x04C11DB7;
+
 /// MPEG2-TSのCRC32における初期値。
 pub const INIT: u32 = 0xFFFFFFFF;
 
+/// CRC32が正しいセクションについて、CRC_32フィールドまで含めて[`digest32`]で計算した結果。
+///
+/// CRC-32/MPEG-2は最終XORを行わないため、剰余は`0`となる。
+pub const CRC32_RESIDUE: u32 = 0;
+
+/// 任意の生成多項式によるCRC32の計算表。
+///
+/// 入出力のビット反転は行わず、最終XORも行わない。すなわちCRC-32/MPEG-2と同じ方式で、
+/// 生成多項式のみが異なるCRC32を計算する。
+#[derive(Clone, PartialEq, Eq)]
+pub struct Crc32Table([u32; 256]);
+
+/// MPEG2-TSのCRC32における計算表。
+pub const CRC32_MPEG2: Crc32Table = Crc32Table(TABLE);
+
+impl Crc32Table {
+    /// 生成多項式`poly`から計算表を生成する。
+    pub const fn new(poly: u32) -> Crc32Table {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = (i as u32) << 24;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 0x80000000 != 0 {
+                    (crc << 1) ^ poly
+                } else {
+                    crc << 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        Crc32Table(table)
+    }
+
+    /// `init`を初期値として`data`のCRC32を計算する。
+    pub fn digest(&self, init: u32, data: &[u8]) -> u32 {
+        // TODO: unroll
+        data.iter().fold(init, |crc, &v| {
+            (crc << 8) ^ self.0[((crc >> 24) ^ v as u32) as usize]
+        })
+    }
+
+    /// [`INIT`]を初期値として`data`のCRC32を計算する。
+    #[inline]
+    pub fn calc(&self, data: &[u8]) -> u32 {
+        self.digest(INIT, data)
+    }
+
+    /// `data`のCRC32の正しさを確認する。
+    ///
+    /// `data`は末尾にCRC_32フィールドを含むものとし、
+    /// 全体のCRC32が[`CRC32_RESIDUE`]と一致するかどうかで正しさを確認する。
+    #[inline]
+    pub fn check(&self, data: &[u8]) -> bool {
+        self.calc(data) == CRC32_RESIDUE
+    }
+}
+
+impl std::fmt::Debug for Crc32Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("Crc32Table")
+            .field(&crate::utils::UpperHex(self.0[1]))
+            .finish()
+    }
+}
+
 /// MPEG2-TSのCRC32を計算し正しさを確認する。
+#[inline]
 pub fn digest32(init: u32, data: &[u8]) -> u32 {
-    // TODO: unroll
-    data.iter().fold(init, |crc, &v| {
-        (crc << 8) ^ TABLE[((crc >> 24) ^ v as u32) as usize]
-    })
+    CRC32_MPEG2.digest(init, data)
 }
 
 /// MPEG2-TSのCRC32を計算し正しさを確認する。
 #[inline]
 pub fn calc32(data: &[u8]) -> bool {
-    digest32(INIT, data) == 0
+    digest32(INIT, data) == CRC32_RESIDUE
+}
+
+/// MPEG2-TSのCRC32を計算し正しさを確認する。
+///
+/// `data`は末尾にCRC_32フィールドを含むものとし、
+/// 全体のCRC32が[`CRC32_RESIDUE`]と一致するかどうかで正しさを確認する。
+/// [`calc32`]と同じ処理である。
+#[inline]
+pub fn verify32(data: &[u8]) -> bool {
+    calc32(data)
+}
+
+/// PESのCRC16（CRC-16/CCITT）における生成多項式。
+///
+/// 入出力のビット反転は行わず、最終XORも行わない。
+pub const CRC16_CCITT_POLY: u16 = 0x1021;
+
+/// PESのCRC16における初期値。
+pub const INIT16: u16 = 0xFFFF;
+
+/// CRC16が正しいデータについて、CRCフィールドまで含めて[`digest16`]で計算した結果。
+pub const CRC16_RESIDUE: u16 = 0;
+
+const TABLE16: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ CRC16_CCITT_POLY
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// PESのCRC16を計算する。
+pub fn digest16(init: u16, data: &[u8]) -> u16 {
+    data.iter().fold(init, |crc, &v| {
+        (crc << 8) ^ TABLE16[((crc >> 8) ^ v as u16) as usize]
+    })
+}
+
+/// PESのCRC16を計算し正しさを確認する。
+///
+/// `data`は末尾にCRCフィールドを含むものとし、
+/// 全体のCRC16が[`CRC16_RESIDUE`]と一致するかどうかで正しさを確認する。
+#[inline]
+pub fn calc16(data: &[u8]) -> bool {
+    digest16(INIT16, data) == CRC16_RESIDUE
 }
 
 #[cfg(test)]
@@ -72,5 +201,64 @@ mod tests {
         assert!(calc32(
             b"The quick brown fox jumps over the lazy dog\xBA\x62\x11\x9E"
         ));
+        assert!(!verify32(
+            b"The quick brown fox jumps over the lazy dog\xBA\x62\x11\x9F"
+        ));
+    }
+
+    #[test]
+    fn test_crc32_params() {
+        assert_eq!(TABLE[1], CRC32_MPEG_POLY);
+        assert_eq!(
+            digest32(
+                INIT,
+                b"The quick brown fox jumps over the lazy dog\xBA\x62\x11\x9E"
+            ),
+            CRC32_RESIDUE,
+        );
+    }
+
+    #[test]
+    fn test_crc32_table() {
+        assert_eq!(Crc32Table::new(CRC32_MPEG_POLY), CRC32_MPEG2);
+
+        // 様々な長さ・内容のデータで関数版と計算表版の結果が一致することを確認
+        let data: Vec<u8> = (0..=255u32).map(|i| (i * 167 + 13) as u8).collect();
+        for len in 0..data.len() {
+            let data = &data[..len];
+            assert_eq!(CRC32_MPEG2.calc(data), digest32(INIT, data));
+
+            let mut with_crc = data.to_vec();
+            with_crc.extend_from_slice(&digest32(INIT, data).to_be_bytes());
+            assert!(CRC32_MPEG2.check(&with_crc));
+            assert_eq!(CRC32_MPEG2.check(&with_crc), calc32(&with_crc));
+        }
+
+        // CRC-32/BZIP2と生成多項式は同じだが、初期値・最終XORが異なる
+        let table = Crc32Table::new(CRC32_MPEG_POLY);
+        assert_eq!(!table.calc(b"123456789"), 0xFC891918);
+
+        // CRC-32C（Castagnoli）の生成多項式による非反転版
+        let table = Crc32Table::new(0x1EDC6F41);
+        assert_eq!(table.0[1], 0x1EDC6F41);
+        let crc = table.calc(b"123456789");
+        let mut data = b"123456789".to_vec();
+        data.extend_from_slice(&crc.to_be_bytes());
+        assert!(table.check(&data));
+        assert!(!CRC32_MPEG2.check(&data));
+    }
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(TABLE16[1], CRC16_CCITT_POLY);
+        assert_eq!(digest16(INIT16, &[]), INIT16);
+        assert_eq!(digest16(INIT16, b"123456789"), 0x29B1);
+
+        assert_eq!(digest16(INIT16, b"1234"), 0x5349);
+        assert_eq!(digest16(0x5349, b"56789"), 0x29B1);
+
+        assert!(calc16(b"123456789\x29\xB1"));
+        assert!(!calc16(b"123456789\x29\xB2"));
+        assert!(!calc16(b"123456780\x29\xB1"));
     }
 }
@@ -1,5 +1,6 @@
 //! ARIB STD-B21で規定されるデータモジュール。
 
+use crate::dsmcc::message::DownloadDataBlock;
 use crate::eight::str::AribStr;
 use crate::psi::table::{NetworkId, ServiceId, TransportStreamId};
 use crate::utils::{BytesExt, SliceExt};
@@ -275,3 +276,28 @@ impl<'a> CdtLogo<'a> {
         })
     }
 }
+
+/// DDBメッセージで伝送される、データモジュールの1ブロック。
+///
+/// ARIB STD-B24第三編 表9-4による。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataModule<'a> {
+    /// ダウンロード識別。
+    pub download_id: u32,
+    /// モジュール識別。
+    pub module_id: u16,
+    /// モジュールバージョン。
+    pub module_version: u8,
+    /// ブロック番号。
+    pub block_number: u16,
+    /// ブロックデータ。
+    pub block_data: &'a [u8],
+}
+
+impl<'a> DataModule<'a> {
+    /// DDBメッセージを格納する`data`から`DataModule`を読み取る。
+    #[inline]
+    pub fn read(data: &'a [u8]) -> Option<DataModule<'a>> {
+        DownloadDataBlock::read(data).map(|ddb| ddb.data_module())
+    }
+}
@@ -150,6 +150,8 @@
 //! # }
 //! ```
 
+use std::collections::BTreeSet;
+
 use crate::packet::Packet;
 use crate::pes::{PesError, PesPacket, PesPacketLength};
 use crate::pid::{Pid, PidTable};
@@ -356,6 +358,36 @@ impl<T: Filter + ?Sized> Filter for &mut T {
     }
 }
 
+/// [`Demuxer`]で処理する前にパケットをPIDで選別する。
+///
+/// [`Filter`]の呼び出しやパケットの分離よりも前に呼ばれるため、
+/// 不要なPIDのパケットを早期に除外して処理負荷を軽減することができる。
+pub trait PacketFilter: Send + 'static {
+    /// `pid`のパケットを処理する場合は`true`を返す。
+    fn accept(&self, pid: Pid) -> bool;
+}
+
+impl<F> PacketFilter for F
+where
+    F: Fn(Pid) -> bool + Send + 'static,
+{
+    #[inline]
+    fn accept(&self, pid: Pid) -> bool {
+        (self)(pid)
+    }
+}
+
+/// 指定されたPIDのパケットのみを処理する[`PacketFilter`]。
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PidSetFilter(pub BTreeSet<Pid>);
+
+impl PacketFilter for PidSetFilter {
+    #[inline]
+    fn accept(&self, pid: Pid) -> bool {
+        self.0.contains(&pid)
+    }
+}
+
 /// TSパケットを分離する。
 ///
 /// [`Filter`]を実装した型を渡して`Demuxer`を生成し、
@@ -366,6 +398,39 @@ pub struct Demuxer<T: Filter> {
     filter: T,
     cc: PidTable<u8>,
     table: Table<T::Tag>,
+    packet_filter: Option<Box<dyn PacketFilter>>,
+    #[cfg(feature = "stats")]
+    stats: Option<DemuxStats>,
+}
+
+/// [`Demuxer`]で処理したパケットの統計情報。
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemuxStats {
+    /// 処理したパケットの総数。
+    pub total_packets: u64,
+    /// PIDごとの統計情報。
+    pub per_pid: PidTable<PidStats>,
+}
+
+#[cfg(feature = "stats")]
+impl Default for DemuxStats {
+    fn default() -> DemuxStats {
+        DemuxStats {
+            total_packets: 0,
+            per_pid: PidTable::from_fn(|_| PidStats::default()),
+        }
+    }
+}
+
+/// [`DemuxStats`]における、PIDごとの統計情報。
+#[cfg(feature = "stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PidStats {
+    /// 処理したパケットの数。
+    pub count: u32,
+    /// 連続性指標が不正だったパケットの数。
+    pub cc_errors: u32,
 }
 
 impl<T: Filter> Demuxer<T> {
@@ -375,7 +440,50 @@ impl<T: Filter> Demuxer<T> {
         let mut table = Table::new();
 
         filter.on_setup(&mut table);
-        Demuxer { filter, cc, table }
+        Demuxer {
+            filter,
+            cc,
+            table,
+            packet_filter: None,
+            #[cfg(feature = "stats")]
+            stats: None,
+        }
+    }
+
+    /// パケットをPIDで選別する[`PacketFilter`]を設定する。
+    ///
+    /// `filter`が`false`を返したPIDのパケットは、連続性指標の検証も含め一切処理されない。
+    pub fn with_filter<F: PacketFilter>(mut self, filter: F) -> Demuxer<T> {
+        self.packet_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// パケットの統計情報を収集する`Demuxer`を生成する。
+    ///
+    /// 統計情報は[`Demuxer::stats`]で取得できる。
+    #[cfg(feature = "stats")]
+    pub fn with_stats(filter: T) -> Demuxer<T> {
+        let mut demuxer = Demuxer::new(filter);
+        demuxer.stats = Some(DemuxStats::default());
+        demuxer
+    }
+
+    /// 収集したパケットの統計情報を返す。
+    ///
+    /// [`Demuxer::with_stats`]で生成されていない場合は`None`を返す。
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> Option<&DemuxStats> {
+        self.stats.as_ref()
+    }
+
+    /// 処理したパケットの総数を返す。
+    ///
+    /// [`Demuxer::with_stats`]で生成されていない場合は`None`を返す。
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn packet_count(&self) -> Option<u64> {
+        self.stats.as_ref().map(|stats| stats.total_packets)
     }
 
     /// `Demuxer`で処理しているパケットの状態をリセットする。
@@ -468,7 +576,22 @@ impl<T: Filter> Demuxer<T> {
         }
 
         let pid = packet.pid();
+        if let Some(packet_filter) = &self.packet_filter {
+            if !packet_filter.accept(pid) {
+                return;
+            }
+        }
+
         let cc_ok = packet.validate_cc(&mut self.cc[pid]);
+        #[cfg(feature = "stats")]
+        if let Some(stats) = &mut self.stats {
+            let pid_stats = &mut stats.per_pid[pid];
+            stats.total_packets += 1;
+            pid_stats.count = pid_stats.count.saturating_add(1);
+            if !cc_ok {
+                pid_stats.cc_errors = pid_stats.cc_errors.saturating_add(1);
+            }
+        }
         if !cc_ok {
             self.filter.on_discontinued(packet);
         }
@@ -688,3 +811,89 @@ impl PartialPsiSection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(pid: u16, cc: u8) -> Packet {
+        let mut packet = Packet([0xFF; 188]);
+        packet.0[0] = 0x47;
+        packet.0[1] = (pid >> 8) as u8;
+        packet.0[2] = pid as u8;
+        packet.0[3] = 0x10 | cc;
+        packet
+    }
+
+    #[test]
+    fn test_packet_filter() {
+        struct Counter(Vec<Pid>);
+        impl Filter for Counter {
+            type Tag = ();
+            fn on_setup(&mut self, _: &mut Table<()>) {}
+            fn on_discontinued(&mut self, packet: &Packet) {
+                self.0.push(packet.pid());
+            }
+            fn on_pes_packet(&mut self, _: &mut Context<()>, _: &PesPacket) {}
+            fn on_psi_section(&mut self, _: &mut Context<()>, _: &PsiSection) {}
+        }
+
+        let pids = [Pid::new(0x100)].into_iter().collect();
+        let mut demuxer = Demuxer::new(Counter(Vec::new())).with_filter(PidSetFilter(pids));
+        demuxer.feed(&packet(0x100, 0));
+        demuxer.feed(&packet(0x101, 0));
+        demuxer.feed(&packet(0x100, 2));
+        demuxer.feed(&packet(0x101, 5));
+        assert_eq!(demuxer.filter().0, [Pid::new(0x100)]);
+
+        let mut demuxer =
+            Demuxer::new(Counter(Vec::new())).with_filter(|pid: Pid| pid != Pid::new(0x100));
+        demuxer.feed(&packet(0x100, 0));
+        demuxer.feed(&packet(0x100, 2));
+        demuxer.feed(&packet(0x101, 0));
+        demuxer.feed(&packet(0x101, 5));
+        assert_eq!(demuxer.filter().0, [Pid::new(0x101)]);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_demux_stats() {
+        struct Nop;
+        impl Filter for Nop {
+            type Tag = ();
+            fn on_setup(&mut self, _: &mut Table<()>) {}
+            fn on_pes_packet(&mut self, _: &mut Context<()>, _: &PesPacket) {}
+            fn on_psi_section(&mut self, _: &mut Context<()>, _: &PsiSection) {}
+        }
+
+        let mut demuxer = Demuxer::new(Nop);
+        demuxer.feed(&packet(0x100, 0));
+        assert!(demuxer.stats().is_none());
+        assert_eq!(demuxer.packet_count(), None);
+
+        let mut demuxer = Demuxer::with_stats(Nop);
+        demuxer.feed(&packet(0x100, 0));
+        demuxer.feed(&packet(0x100, 1));
+        demuxer.feed(&packet(0x100, 3));
+        demuxer.feed(&packet(0x101, 5));
+
+        let stats = demuxer.stats().unwrap();
+        assert_eq!(stats.total_packets, 4);
+        assert_eq!(demuxer.packet_count(), Some(4));
+        assert_eq!(
+            stats.per_pid[Pid::new(0x100)],
+            PidStats {
+                count: 3,
+                cc_errors: 1,
+            }
+        );
+        assert_eq!(
+            stats.per_pid[Pid::new(0x101)],
+            PidStats {
+                count: 1,
+                cc_errors: 0,
+            }
+        );
+        assert_eq!(stats.per_pid[Pid::new(0x102)], PidStats::default());
+    }
+}
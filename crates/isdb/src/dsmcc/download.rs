@@ -1,5 +1,9 @@
 //! DSM-CCによるダウンロードの支援。
 
+use fxhash::FxHashMap;
+
+use crate::data_module::DataModule;
+
 use super::message::{DiiModule, DownloadDataBlock, DownloadInfoIndication};
 
 /// [`DownloadInfoIndication`]と[`DownloadDataBlock`]を組み合わせてデータをダウンロードする。
@@ -11,6 +15,7 @@ pub struct DownloadData {
     module_version: u8,
     block_size: u16,
     data: Vec<u8>,
+    received: Vec<bool>,
     downloaded: u16,
 }
 
@@ -24,6 +29,7 @@ impl DownloadData {
             module_version: module.module_version,
             block_size: info.block_size,
             data: vec![0; module.module_size as usize],
+            received: vec![false; n_blocks(module.module_size as usize, info.block_size) as usize],
             downloaded: 0,
         }
     }
@@ -47,7 +53,7 @@ impl DownloadData {
 
     #[inline]
     fn n_blocks(&self) -> u16 {
-        ((self.data.len() as u32 - 1) / (self.block_size as u32) + 1) as u16
+        self.received.len() as u16
     }
 
     /// 全ブロックのダウンロードが完了していればそのデータを返す。
@@ -59,28 +65,260 @@ impl DownloadData {
     /// `block`をダウンロード完了データに加える。
     ///
     /// 全ブロックのダウンロードが完了した場合、そのデータを返す。
+    /// データカルーセルでは同じブロックが繰り返し送出されるため、
+    /// ダウンロード済みのブロックは無視される。
+    #[inline]
     pub fn store(&mut self, block: &DownloadDataBlock) -> Option<&[u8]> {
-        if block.block_number >= self.n_blocks()
-            || self.download_id != block.header.download_id
-            || self.module_version != block.module_version
+        self.store_module(&block.data_module())
+    }
+
+    /// `module`をダウンロード完了データに加える。
+    ///
+    /// 動作は[`DownloadData::store`]と同じ。
+    pub fn store_module(&mut self, module: &DataModule) -> Option<&[u8]> {
+        if module.block_number >= self.n_blocks()
+            || self.download_id != module.download_id
+            || self.module_version != module.module_version
         {
             return None;
         }
+        if self.received[module.block_number as usize] {
+            return self.completed();
+        }
 
-        let offset = block.block_number as usize * self.block_size as usize;
-        let size = if block.block_number < self.n_blocks() - 1 {
+        let offset = module.block_number as usize * self.block_size as usize;
+        let size = if module.block_number < self.n_blocks() - 1 {
             self.block_size as usize
         } else {
             self.data.len() - offset
         };
-        if block.block_data.len() < size {
+        if module.block_data.len() < size {
             log::debug!("DownloadData: download block is too small");
             return None;
         }
 
-        self.data[offset..offset + size].copy_from_slice(&block.block_data[..size]);
+        self.data[offset..offset + size].copy_from_slice(&module.block_data[..size]);
+        self.received[module.block_number as usize] = true;
         self.downloaded += 1;
 
         self.completed()
     }
 }
+
+/// 複数のモジュールについて[`DownloadData`]をモジュール識別ごとに管理し、モジュールを再構成する。
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModuleReassembler {
+    modules: FxHashMap<u16, DownloadData>,
+}
+
+impl ModuleReassembler {
+    /// 空の`ModuleReassembler`を生成する。
+    #[inline]
+    pub fn new() -> ModuleReassembler {
+        ModuleReassembler::default()
+    }
+
+    /// `module`をダウンロード対象に加える。
+    ///
+    /// 既にダウンロード中のモジュールについては、
+    /// [`DownloadData::needs_restart`]が`true`を返す場合にのみダウンロードをやり直す。
+    pub fn add_module(&mut self, info: &DownloadInfoIndication, module: &DiiModule) {
+        match self.modules.entry(module.module_id) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                if entry.get().needs_restart(info, module) {
+                    entry.insert(DownloadData::new(info, module));
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(DownloadData::new(info, module));
+            }
+        }
+    }
+
+    /// `info`に含まれる全てのモジュールをダウンロード対象に加える。
+    pub fn add_modules(&mut self, info: &DownloadInfoIndication) {
+        for module in &*info.modules {
+            self.add_module(info, module);
+        }
+    }
+
+    /// `block`を対応するモジュールに加える。
+    ///
+    /// モジュールの全ブロックのダウンロードが完了した場合、そのデータを返す。
+    /// ダウンロード対象でないモジュールのブロックは無視される。
+    #[inline]
+    pub fn store(&mut self, block: &DownloadDataBlock) -> Option<&[u8]> {
+        self.store_module(&block.data_module())
+    }
+
+    /// `module`を対応するモジュールに加える。
+    ///
+    /// 動作は[`ModuleReassembler::store`]と同じ。
+    pub fn store_module(&mut self, module: &DataModule) -> Option<&[u8]> {
+        self.modules
+            .get_mut(&module.module_id)?
+            .store_module(module)
+    }
+
+    /// `module_id`で示されるモジュールをダウンロード対象から外す。
+    #[inline]
+    pub fn remove(&mut self, module_id: u16) {
+        self.modules.remove(&module_id);
+    }
+
+    /// 全てのモジュールをダウンロード対象から外す。
+    #[inline]
+    pub fn clear(&mut self) {
+        self.modules.clear();
+    }
+}
+
+/// モジュールの大きさとブロックの大きさからブロック数を計算する。
+fn n_blocks(module_size: usize, block_size: u16) -> u16 {
+    if block_size == 0 {
+        return 0;
+    }
+    ((module_size + block_size as usize - 1) / block_size as usize) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsmcc::desc::DiiDescriptorBlock;
+    use crate::dsmcc::message::DsmccMessageHeader;
+
+    fn ddb(block_number: u16, block_data: &[u8]) -> Vec<u8> {
+        let mut data = vec![
+            0x11, 0x03, 0x10, 0x03, // protocol_discriminator, dsmcc_type, message_id
+            0x00, 0x00, 0x00, 0x01, // download_id
+            0xFF, 0x00, 0x00, 0x00, // reserved, adaptation_length, message_length
+            0x00, 0x02, 0x05, 0xFF, // module_id, module_version, reserved
+        ];
+        data.extend_from_slice(&block_number.to_be_bytes());
+        data.extend_from_slice(block_data);
+        data
+    }
+
+    /// ダウンロード識別1、ブロック長4のDII。
+    fn dii(modules: Vec<DiiModule<'static>>) -> DownloadInfoIndication<'static> {
+        DownloadInfoIndication {
+            header: DsmccMessageHeader {
+                protocol_discriminator: 0x11,
+                dsmcc_type: 0x03,
+                message_id: 0x1002,
+                transaction_id: 0,
+                dsmcc_adaptation_header: None,
+            },
+            download_id: 1,
+            block_size: 4,
+            window_size: 0,
+            ack_period: 0,
+            t_c_download_window: 0,
+            t_c_download_scenario: 0,
+            compatibility_descriptor: Vec::new(),
+            modules,
+            private_data: &[],
+        }
+    }
+
+    /// モジュール識別2、バージョン5のモジュール情報。
+    fn dii_module(module_size: u32) -> DiiModule<'static> {
+        DiiModule {
+            module_id: 2,
+            module_size,
+            module_version: 5,
+            module_info: DiiDescriptorBlock::new(&[]),
+        }
+    }
+
+    #[test]
+    fn test_download_data() {
+        let info = dii(Vec::new());
+        let module = dii_module(10);
+
+        let mut dd = DownloadData::new(&info, &module);
+        assert!(!dd.needs_restart(&info, &module));
+        assert_eq!(dd.completed(), None);
+
+        let block0 = ddb(0, &[0, 1, 2, 3]);
+        let block1 = ddb(1, &[4, 5, 6, 7]);
+        let block2 = ddb(2, &[8, 9]);
+        let block0 = DownloadDataBlock::read(&block0).unwrap();
+        let block1 = DownloadDataBlock::read(&block1).unwrap();
+        let block2 = DownloadDataBlock::read(&block2).unwrap();
+
+        assert_eq!(dd.store(&block0), None);
+        // 同じブロックが繰り返し送られても完了扱いにならない
+        assert_eq!(dd.store(&block0), None);
+        assert_eq!(dd.store(&block0), None);
+        assert_eq!(dd.store(&block2), None);
+        assert_eq!(dd.store(&block1), Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]));
+        assert_eq!(dd.store(&block1), Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]));
+    }
+
+    #[test]
+    fn test_module_reassembler() {
+        let info = dii(vec![dii_module(6)]);
+
+        let block0 = ddb(0, &[0, 1, 2, 3]);
+        let block1 = ddb(1, &[4, 5]);
+        let block0 = DownloadDataBlock::read(&block0).unwrap();
+        let block1 = DownloadDataBlock::read(&block1).unwrap();
+
+        let mut reassembler = ModuleReassembler::new();
+        // ダウンロード対象でないモジュールは無視される
+        assert_eq!(reassembler.store(&block0), None);
+
+        reassembler.add_modules(&info);
+        assert_eq!(reassembler.store(&block0), None);
+        // 同じDIIを再度受け取ってもダウンロードはやり直されない
+        reassembler.add_modules(&info);
+        assert_eq!(reassembler.store(&block1), Some(&[0, 1, 2, 3, 4, 5][..]));
+
+        reassembler.remove(2);
+        assert_eq!(reassembler.store(&block1), None);
+    }
+
+    #[test]
+    fn test_data_module() {
+        let block0 = ddb(0, &[0, 1, 2, 3]);
+        let block1 = ddb(1, &[4, 5]);
+        let module0 = DataModule::read(&block0).unwrap();
+        let module1 = DataModule::read(&block1).unwrap();
+        assert_eq!(
+            module0,
+            DataModule {
+                download_id: 1,
+                module_id: 2,
+                module_version: 5,
+                block_number: 0,
+                block_data: &[0, 1, 2, 3],
+            },
+        );
+        assert_eq!(
+            module1,
+            DownloadDataBlock::read(&block1).unwrap().data_module()
+        );
+        assert_eq!(DataModule::read(&block0[..17]), None);
+
+        let info = dii(vec![dii_module(6)]);
+
+        let mut reassembler = ModuleReassembler::new();
+        reassembler.add_modules(&info);
+        assert_eq!(reassembler.store_module(&module1), None);
+        assert_eq!(
+            reassembler.store_module(&module0),
+            Some(&[0, 1, 2, 3, 4, 5][..])
+        );
+
+        // ダウンロード識別が異なるブロックは無視される
+        let mut dd = DownloadData::new(&info, &info.modules[0]);
+        let other = DataModule {
+            download_id: 2,
+            ..module0
+        };
+        assert_eq!(dd.store_module(&other), None);
+        assert_eq!(dd.store_module(&module1), None);
+        assert_eq!(dd.store_module(&module0), Some(&[0, 1, 2, 3, 4, 5][..]));
+    }
+}
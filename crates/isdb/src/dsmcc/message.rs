@@ -1,5 +1,6 @@
 //! DIIメッセージおよびDDBメッセージ。
 
+use crate::data_module::DataModule;
 use crate::psi::desc::CompatibilityDescriptor;
 use crate::utils::{BytesExt, SliceExt};
 
@@ -245,4 +246,16 @@ impl<'a> DownloadDataBlock<'a> {
             block_data,
         })
     }
+
+    /// このDDBメッセージで伝送されるデータモジュールのブロックを返す。
+    #[inline]
+    pub fn data_module(&self) -> DataModule<'a> {
+        DataModule {
+            download_id: self.header.download_id,
+            module_id: self.module_id,
+            module_version: self.module_version,
+            block_number: self.block_number,
+            block_data: self.block_data,
+        }
+    }
 }
@@ -1,6 +1,6 @@
 //! ARIB STD-B24で規定されるセクションの定義。
 
-use crate::psi::PsiSection;
+use crate::psi::{PsiSection, PsiTable};
 
 use super::message::{DownloadDataBlock, DownloadInfoIndication};
 
@@ -36,3 +36,10 @@ impl<'a> DsmccSection<'a> {
         }
     }
 }
+
+impl<'a> PsiTable<'a> for DsmccSection<'a> {
+    #[inline]
+    fn read(psi: &PsiSection<'a>) -> Option<DsmccSection<'a>> {
+        DsmccSection::read(psi)
+    }
+}
@@ -682,6 +682,50 @@ pub enum AribChar {
     SkipCharacterSet,
 }
 
+impl AribChar {
+    /// 制御文字であるかどうかを返す。
+    ///
+    /// 図形文字（一般的な図形文字、モザイク図形文字、DRCS）とスペース以外は制御文字とみなす。
+    #[inline]
+    pub fn is_control(&self) -> bool {
+        !matches!(
+            self,
+            AribChar::Generic(_) | AribChar::Mosaic(_) | AribChar::Drcs(_) | AribChar::Space
+        )
+    }
+
+    /// 文字サイズが標準であるものとして、この文字を`char`に変換する。
+    ///
+    /// スペースは全角スペースに変換される。
+    /// 文字に変換できない文字符号やモザイク図形文字、DRCS、制御文字は
+    /// [`U+FFFD REPLACEMENT CHARACTER`][`char::REPLACEMENT_CHARACTER`]に変換される。
+    pub fn to_char(&self) -> char {
+        match self {
+            AribChar::Generic(c) => c
+                .to_char(CharSize::default())
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            AribChar::Space => '\u{3000}',
+            _ => char::REPLACEMENT_CHARACTER,
+        }
+    }
+
+    /// 文字サイズが`char_size`である時に、この文字が占める升目の数を返す。
+    ///
+    /// 半角（小型・中型・超小型）の文字は`1`、全角の文字は`2`を返し、
+    /// 横倍角や全倍といった拡大は考慮しない。
+    /// 制御文字は幅を持たないため`0`を返す。
+    #[inline]
+    pub fn cell_width(&self, char_size: CharSize) -> u8 {
+        if self.is_control() {
+            0
+        } else if char_size.is_small() {
+            1
+        } else {
+            2
+        }
+    }
+}
+
 /// 図形文字と図形化に関わる制御文字からなる、8単位符号の文字型。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GraphicChar {
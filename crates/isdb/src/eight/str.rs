@@ -98,6 +98,17 @@ impl AribStr {
         buf
     }
 
+    /// 既定の[`decode::Options`]に従い8単位符号をUTF-8の文字列に変換する。
+    ///
+    /// 文字に変換できない文字符号は[`U+FFFD REPLACEMENT
+    /// CHARACTER`][`char::REPLACEMENT_CHARACTER`]に変換され、読み飛ばされることはない。
+    pub fn to_utf8_lossy(&self) -> Cow<'_, str> {
+        if self.is_empty() {
+            return Cow::Borrowed("");
+        }
+        Cow::Owned(self.to_string(decode::Options::DEFAULT))
+    }
+
     /// `opts`に従い文字符号を安全に表示するための、
     /// [`Display`]及び[`Debug`]を実装したオブジェクトを返す。
     ///
@@ -108,6 +119,119 @@ impl AribStr {
         Display { inner: self, opts }
     }
 
+    /// 制御符号を`[NEWLINE]`や`[COLOR:MAGENTA]`のような説明に置き換えた、デバッグ用の文字列に変換する。
+    ///
+    /// 出力形式は開発時の確認用であり、安定していない。
+    #[cfg(feature = "debug-output")]
+    pub fn to_debug_string(&self, opts: decode::Options) -> String {
+        fn color_name(index: u8) -> Option<&'static str> {
+            // ARIB STD-B24 第一編 第2部 表7-14の既定パレット
+            Some(match index {
+                0 => "BLACK",
+                1 => "RED",
+                2 => "GREEN",
+                3 => "YELLOW",
+                4 => "BLUE",
+                5 => "MAGENTA",
+                6 => "CYAN",
+                7 => "WHITE",
+                8 => "TRANSPARENT",
+                _ => return None,
+            })
+        }
+
+        fn size_name(size: CharSize) -> &'static str {
+            match size {
+                CharSize::Small => "SMALL",
+                CharSize::Medium => "MEDIUM",
+                CharSize::Normal => "NORMAL",
+                CharSize::Micro => "MICRO",
+                CharSize::HeightW => "DOUBLE_HEIGHT",
+                CharSize::WidthW => "DOUBLE_WIDTH",
+                CharSize::SizeW => "DOUBLE_SIZE",
+                CharSize::Special1 => "SPECIAL_1",
+                CharSize::Special2 => "SPECIAL_2",
+            }
+        }
+
+        let mut buf = String::with_capacity(self.len());
+        let mut char_size = CharSize::default();
+        for c in self.decode(opts) {
+            let _ = match c {
+                AribChar::Generic(c) => {
+                    buf.push(c.to_char(char_size).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    Ok(())
+                }
+                AribChar::Space => {
+                    buf.push_str(if char_size.is_small() { " " } else { "　" });
+                    Ok(())
+                }
+                AribChar::ActivePositionReturn => {
+                    buf.push_str("[NEWLINE]");
+                    Ok(())
+                }
+                AribChar::ClearScreen => {
+                    buf.push_str("[CLEAR]");
+                    Ok(())
+                }
+                AribChar::CharSize(size) => {
+                    char_size = size;
+                    write!(buf, "[SIZE:{}]", size_name(size))
+                }
+                AribChar::ColorForeground(index) => match color_name(index) {
+                    Some(name) => write!(buf, "[COLOR:{}]", name),
+                    None => write!(buf, "[COLOR:{}]", index),
+                },
+                AribChar::ColorBackground(index) => match color_name(index) {
+                    Some(name) => write!(buf, "[BGCOLOR:{}]", name),
+                    None => write!(buf, "[BGCOLOR:{}]", index),
+                },
+                c => write!(buf, "[{:?}]", c),
+            };
+        }
+
+        buf
+    }
+
+    /// [`decode::Options`]に従い8単位符号を解釈し、文字列中の文字数を返す。
+    ///
+    /// 図形文字（一般的な図形文字、モザイク図形文字、DRCS）とスペースを1文字として数え、
+    /// 制御文字は数えない。
+    /// 1文字あたりのバイト数は文字集合によって異なるため、[`len`][AribStr::len]とは一致しない。
+    pub fn char_count(&self, opts: decode::Options) -> usize {
+        let mut decoder = Decoder::new(self.as_bytes(), opts);
+        let mut count = 0;
+        while let Some(c) = decoder.next_graphic() {
+            match c {
+                GraphicChar::Generic(_)
+                | GraphicChar::Mosaic(_)
+                | GraphicChar::Drcs(_)
+                | GraphicChar::Space => count += 1,
+                GraphicChar::ActivePositionReturn | GraphicChar::CharSize(_) => {}
+            }
+        }
+
+        count
+    }
+
+    /// 文字列中にルビ（小型文字）が含まれるかどうかを返す。
+    ///
+    /// ARIB STD-B24の字幕ではルビを小型文字（SSZ）で送出するため、
+    /// 小型文字が指定されている図形文字があればルビがあるものとみなす。
+    pub fn has_ruby_text(&self, opts: decode::Options) -> bool {
+        let mut decoder = Decoder::new(self.as_bytes(), opts);
+        let mut char_size = CharSize::default();
+        while let Some(c) = decoder.next_graphic() {
+            match c {
+                GraphicChar::CharSize(size) => char_size = size,
+                GraphicChar::Generic(_) if char_size == CharSize::Small => return true,
+                _ => {}
+            }
+        }
+
+        false
+    }
+
     /// 文字列中の各[`AribChar`]を返すイテレーターを生成する。
     #[inline]
     pub fn decode(&self, opts: decode::Options) -> AribChars {
@@ -115,6 +239,14 @@ impl AribStr {
             decoder: Decoder::new(self.as_bytes(), opts),
         }
     }
+
+    /// 既定の[`decode::Options`]に従い、文字列中の各[`AribChar`]を返すイテレーターを生成する。
+    ///
+    /// 制御文字も含めて返されるため、図形文字のみを扱う場合は[`AribChar::is_control`]で除外する。
+    #[inline]
+    pub fn chars(&self) -> AribChars<'_> {
+        self.decode(decode::Options::DEFAULT)
+    }
 }
 
 impl Default for &AribStr {
@@ -486,3 +618,231 @@ impl<'a> fmt::Debug for Display<'a> {
         debug.finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_utf8_lossy() {
+        assert_eq!(AribStr::from_bytes(b"").to_utf8_lossy(), "");
+        // ＮＨＫ
+        assert_eq!(
+            AribStr::from_bytes(b"\x23\x4E\x23\x48\x23\x4B").to_utf8_lossy(),
+            "ＮＨＫ",
+        );
+        // 85区の追加漢字
+        assert_eq!(AribStr::from_bytes(b"\x75\x2F").to_utf8_lossy(), "𠮷");
+        // 未割り当ての文字符号
+        assert_eq!(AribStr::from_bytes(b"\x76\x7E").to_utf8_lossy(), "\u{FFFD}");
+    }
+
+    #[test]
+    fn test_char_count() {
+        let count = |bytes: &[u8]| AribStr::from_bytes(bytes).char_count(decode::Options::DEFAULT);
+
+        assert_eq!(count(b""), 0);
+        // ＮＨＫ
+        assert_eq!(count(b"\x23\x4E\x23\x48\x23\x4B"), 3);
+        // 空白は数え、APRやMSZは数えない
+        assert_eq!(count(b"\x23\x4E\x20\x0D\x89\x23\x48"), 3);
+
+        let chars: Vec<_> = AribStr::from_bytes(b"\x23\x4E\x20\x0D")
+            .decode(decode::Options::DEFAULT)
+            .collect();
+        assert_eq!(
+            chars.iter().map(AribChar::is_control).collect::<Vec<_>>(),
+            [false, false, true],
+        );
+
+        let chars: Vec<_> = AribStr::from_bytes(b"\x23\x4E\x20\x0D").chars().collect();
+        assert_eq!(
+            chars
+                .iter()
+                .map(|c| c.cell_width(CharSize::Normal))
+                .collect::<Vec<_>>(),
+            [2, 2, 0],
+        );
+        assert_eq!(chars[0].cell_width(CharSize::Medium), 1);
+        assert_eq!(chars[0].cell_width(CharSize::Small), 1);
+        // 拡大は考慮しない
+        assert_eq!(chars[1].cell_width(CharSize::SizeW), 2);
+
+        assert_eq!(
+            chars.iter().map(AribChar::to_char).collect::<String>(),
+            "Ｎ\u{3000}\u{FFFD}",
+        );
+    }
+
+    #[test]
+    fn test_has_ruby_text() {
+        let has_ruby_text =
+            |bytes: &[u8]| AribStr::from_bytes(bytes).has_ruby_text(decode::Options::CAPTION);
+
+        assert!(!has_ruby_text(b""));
+        // ＮＨＫ
+        assert!(!has_ruby_text(b"\x23\x4E\x23\x48\x23\x4B"));
+        // 中型文字はルビではない
+        assert!(!has_ruby_text(b"\x89\x23\x4E\x23\x48"));
+        // 小型文字のＨ
+        assert!(has_ruby_text(b"\x23\x4E\x88\x23\x48\x8A\x23\x4B"));
+        // 小型文字の指定があっても図形文字がなければルビではない
+        assert!(!has_ruby_text(b"\x23\x4E\x88"));
+        assert!(!has_ruby_text(b"\x88\x8A\x23\x4E"));
+    }
+
+    #[test]
+    fn test_to_utf8_lossy_extra_kanji() {
+        // 85区～86区の追加漢字
+        const TABLE: &[(u8, u8, char)] = &[
+            (0x75, 0x21, '㐂'),
+            (0x75, 0x22, '𠅘'),
+            (0x75, 0x23, '份'),
+            (0x75, 0x24, '仿'),
+            (0x75, 0x25, '侚'),
+            (0x75, 0x26, '俉'),
+            (0x75, 0x27, '傜'),
+            (0x75, 0x28, '儞'),
+            (0x75, 0x29, '冼'),
+            (0x75, 0x2A, '㔟'),
+            (0x75, 0x2B, '匇'),
+            (0x75, 0x2C, '卡'),
+            (0x75, 0x2D, '卬'),
+            (0x75, 0x2E, '詹'),
+            (0x75, 0x2F, '𠮷'),
+            (0x75, 0x30, '呍'),
+            (0x75, 0x31, '咖'),
+            (0x75, 0x32, '咜'),
+            (0x75, 0x33, '咩'),
+            (0x75, 0x34, '唎'),
+            (0x75, 0x35, '啊'),
+            (0x75, 0x36, '噲'),
+            (0x75, 0x37, '囤'),
+            (0x75, 0x38, '圳'),
+            (0x75, 0x39, '圴'),
+            (0x75, 0x3A, '塚'),
+            (0x75, 0x3B, '墀'),
+            (0x75, 0x3C, '姤'),
+            (0x75, 0x3D, '娣'),
+            (0x75, 0x3E, '婕'),
+            (0x75, 0x3F, '寬'),
+            (0x75, 0x40, '﨑'),
+            (0x75, 0x41, '㟢'),
+            (0x75, 0x42, '庬'),
+            (0x75, 0x43, '弴'),
+            (0x75, 0x44, '彅'),
+            (0x75, 0x45, '德'),
+            (0x75, 0x46, '怗'),
+            (0x75, 0x47, '恵'),
+            (0x75, 0x48, '愰'),
+            (0x75, 0x49, '昤'),
+            (0x75, 0x4A, '曈'),
+            (0x75, 0x4B, '曙'),
+            (0x75, 0x4C, '曺'),
+            (0x75, 0x4D, '曻'),
+            (0x75, 0x4E, '桒'),
+            (0x75, 0x4F, '鿄'),
+            (0x75, 0x50, '椑'),
+            (0x75, 0x51, '椻'),
+            (0x75, 0x52, '橅'),
+            (0x75, 0x53, '檑'),
+            (0x75, 0x54, '櫛'),
+            (0x75, 0x55, '𣏌'),
+            (0x75, 0x56, '𣏾'),
+            (0x75, 0x57, '𣗄'),
+            (0x75, 0x58, '毱'),
+            (0x75, 0x59, '泠'),
+            (0x75, 0x5A, '洮'),
+            (0x75, 0x5B, '海'),
+            (0x75, 0x5C, '涿'),
+            (0x75, 0x5D, '淊'),
+            (0x75, 0x5E, '淸'),
+            (0x75, 0x5F, '渚'),
+            (0x75, 0x60, '潞'),
+            (0x75, 0x61, '濹'),
+            (0x75, 0x62, '灤'),
+            (0x75, 0x63, '𤋮'),
+            (0x75, 0x64, '𤋮'),
+            (0x75, 0x65, '煇'),
+            (0x75, 0x66, '燁'),
+            (0x75, 0x67, '爀'),
+            (0x75, 0x68, '玟'),
+            (0x75, 0x69, '玨'),
+            (0x75, 0x6A, '珉'),
+            (0x75, 0x6B, '珖'),
+            (0x75, 0x6C, '琛'),
+            (0x75, 0x6D, '琡'),
+            (0x75, 0x6E, '琢'),
+            (0x75, 0x6F, '琦'),
+            (0x75, 0x70, '琪'),
+            (0x75, 0x71, '琬'),
+            (0x75, 0x72, '琹'),
+            (0x75, 0x73, '瑋'),
+            (0x75, 0x74, '㻚'),
+            (0x75, 0x75, '畵'),
+            (0x75, 0x76, '疁'),
+            (0x75, 0x77, '睲'),
+            (0x75, 0x78, '䂓'),
+            (0x75, 0x79, '磈'),
+            (0x75, 0x7A, '磠'),
+            (0x75, 0x7B, '祇'),
+            (0x75, 0x7C, '禮'),
+            (0x75, 0x7D, '鿆'),
+            (0x75, 0x7E, '䄃'),
+            (0x76, 0x21, '鿅'),
+            (0x76, 0x22, '秚'),
+            (0x76, 0x23, '稞'),
+            (0x76, 0x24, '筿'),
+            (0x76, 0x25, '簱'),
+            (0x76, 0x26, '䉤'),
+            (0x76, 0x27, '綋'),
+            (0x76, 0x28, '羡'),
+            (0x76, 0x29, '脘'),
+            (0x76, 0x2A, '脺'),
+            (0x76, 0x2B, '舘'),
+            (0x76, 0x2C, '芮'),
+            (0x76, 0x2D, '葛'),
+            (0x76, 0x2E, '蓜'),
+            (0x76, 0x2F, '蓬'),
+            (0x76, 0x30, '蕙'),
+            (0x76, 0x31, '藎'),
+            (0x76, 0x32, '蝕'),
+            (0x76, 0x33, '蟬'),
+            (0x76, 0x34, '蠋'),
+            (0x76, 0x35, '裵'),
+            (0x76, 0x36, '角'),
+            (0x76, 0x37, '諶'),
+            (0x76, 0x38, '跎'),
+            (0x76, 0x39, '辻'),
+            (0x76, 0x3A, '迶'),
+            (0x76, 0x3B, '郝'),
+            (0x76, 0x3C, '鄧'),
+            (0x76, 0x3D, '鄭'),
+            (0x76, 0x3E, '醲'),
+            (0x76, 0x3F, '鈳'),
+            (0x76, 0x40, '銈'),
+            (0x76, 0x41, '錡'),
+            (0x76, 0x42, '鍈'),
+            (0x76, 0x43, '閒'),
+            (0x76, 0x44, '雞'),
+            (0x76, 0x45, '餃'),
+            (0x76, 0x46, '饀'),
+            (0x76, 0x47, '髙'),
+            (0x76, 0x48, '鯖'),
+            (0x76, 0x49, '鷗'),
+            (0x76, 0x4A, '麴'),
+            (0x76, 0x4B, '麵'),
+        ];
+
+        for &(c1, c2, expected) in TABLE {
+            let bytes = [c1, c2];
+            let actual = AribStr::from_bytes(&bytes).to_utf8_lossy();
+            assert_eq!(actual, expected.to_string(), "{:02X} {:02X}", c1, c2);
+        }
+
+        // 86区44点以降は未割り当て
+        for c2 in 0x4C..=0x7E {
+            assert_eq!(AribStr::from_bytes(&[0x76, c2]).to_utf8_lossy(), "\u{FFFD}");
+        }
+    }
+}
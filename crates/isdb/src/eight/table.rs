@@ -46,8 +46,8 @@ pub fn decode_kanji(c1: GraphicCode, c2: GraphicCode, char_size: CharSize) -> Op
     match (row, cell) {
         // 1区～84区6点まではJIS互換漢字1面と同じ
         (1..=83, _) | (84, 1..=6) => decode_jis_kanji_plane1(c1, c2, char_size),
-        // 90区～94区は追加記号と同じ
-        (90..=94, _) => decode_extra_symbols(c1, c2, char_size),
+        // 85区～86区の追加漢字、90区～94区の追加記号は追加記号集合と同じ
+        (85..=86, _) | (90..=94, _) => decode_extra_symbols(c1, c2, char_size),
         _ => None,
     }
 }
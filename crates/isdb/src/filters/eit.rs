@@ -0,0 +1,214 @@
+//! 複数のセクションに分かれたEITをまとめるフィルター。
+
+use fxhash::FxHashMap;
+
+use crate::psi::table::{Eit, EitCommon, EitEvent};
+use crate::psi::{PsiSection, PsiSectionSyntax, PsiTable};
+
+/// 受信済みのEITセクション。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EitSection {
+    segment_last_section_number: u8,
+    data: Vec<u8>,
+    crc32: u32,
+}
+
+/// テーブル識別とサービス識別ごとのサブテーブル。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subtable {
+    version_number: u8,
+    sections: Vec<Option<EitSection>>,
+}
+
+impl Subtable {
+    /// 送出されるべき全てのセクションを受信済みかどうかを返す。
+    ///
+    /// EITのセクションは8セクションごとのセグメントに分かれており、
+    /// 各セグメントでは`segment_last_section_number`より後のセクションは送出されない。
+    fn is_complete(&self) -> bool {
+        self.sections.chunks(8).all(|segment| {
+            let Some(section) = segment.iter().flatten().next() else {
+                return false;
+            };
+            let last = (section.segment_last_section_number % 8) as usize;
+            segment[..=last.min(segment.len() - 1)]
+                .iter()
+                .all(Option::is_some)
+        })
+    }
+}
+
+/// 複数のセクションに分かれたEITを受信し、サブテーブルごとにイベントをまとめる。
+///
+/// サブテーブルはテーブル識別とサービス識別によって区別される。
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EitAccumulator {
+    subtables: FxHashMap<(u8, u16), Subtable>,
+}
+
+impl EitAccumulator {
+    /// 空の`EitAccumulator`を生成する。
+    #[inline]
+    pub fn new() -> EitAccumulator {
+        EitAccumulator::default()
+    }
+
+    /// EITのセクションである`psi`を受け取る。
+    ///
+    /// `psi`によってサブテーブルの全セクションが揃った場合、
+    /// 全セクションのイベントをセクション番号順に並べて返す。
+    /// 揃ったサブテーブルと同じバージョンのセクションを再度受け取っても`None`を返し、
+    /// バージョンが更新された場合は改めてセクションを受信し直す。
+    ///
+    /// `psi`がEITでない場合も`None`を返す。
+    pub fn feed(&mut self, psi: &PsiSection) -> Option<Vec<EitEvent<'_>>> {
+        let syntax = psi.syntax.as_ref()?;
+        let eit = match Eit::read(psi)? {
+            Eit::ActualPf(eit)
+            | Eit::OtherPf(eit)
+            | Eit::ActualSchedule(eit)
+            | Eit::OtherSchedule(eit) => eit,
+        };
+
+        let len = syntax.last_section_number as usize + 1;
+        let idx = syntax.section_number as usize;
+        if idx >= len {
+            return None;
+        }
+
+        let key = (psi.table_id, syntax.table_id_extension);
+        let subtable = self.subtables.entry(key).or_insert_with(|| Subtable {
+            // バージョン番号は5ビットであるため0x20以上は無効値
+            version_number: 0xFF,
+            sections: Vec::new(),
+        });
+        if subtable.version_number != syntax.version_number || subtable.sections.len() != len {
+            subtable.version_number = syntax.version_number;
+            subtable.sections.clear();
+            subtable.sections.resize(len, None);
+        }
+
+        if subtable.sections[idx].is_some() {
+            return None;
+        }
+        subtable.sections[idx] = Some(EitSection {
+            segment_last_section_number: eit.segment_last_section_number,
+            data: psi.data.to_vec(),
+            crc32: psi.crc32,
+        });
+        if !subtable.is_complete() {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        for (section_number, section) in subtable.sections.iter().enumerate() {
+            let Some(section) = section else {
+                continue;
+            };
+
+            let psi = PsiSection {
+                table_id: key.0,
+                syntax: Some(PsiSectionSyntax {
+                    table_id_extension: key.1,
+                    version_number: subtable.version_number,
+                    current_next_indicator: true,
+                    section_number: section_number as u8,
+                    last_section_number: (len - 1) as u8,
+                }),
+                data: &section.data,
+                crc32: section.crc32,
+            };
+            // 受信時に読み取れているため失敗しない
+            events.extend(EitCommon::read(&psi)?.events);
+        }
+        Some(events)
+    }
+
+    /// テーブル識別が`table_id`、サービス識別が`service_id`のサブテーブルを破棄する。
+    #[inline]
+    pub fn remove(&mut self, table_id: u8, service_id: u16) {
+        self.subtables.remove(&(table_id, service_id));
+    }
+
+    /// 全てのサブテーブルを破棄する。
+    #[inline]
+    pub fn clear(&mut self) {
+        self.subtables.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section_data(segment_last_section_number: u8, event_ids: &[u16]) -> Vec<u8> {
+        let mut data = vec![
+            0x7F,
+            0xE1, // transport_stream_id
+            0x00,
+            0x04, // original_network_id
+            segment_last_section_number,
+            0x50, // last_table_id
+        ];
+        for event_id in event_ids {
+            data.extend_from_slice(&event_id.to_be_bytes());
+            data.extend_from_slice(&[0xE7, 0x9C, 0x12, 0x00, 0x00]); // start_time
+            data.extend_from_slice(&[0x00, 0x30, 0x00]); // duration
+            data.extend_from_slice(&[0x80, 0x00]); // running_status, descriptors_loop_length
+        }
+        data
+    }
+
+    fn section(
+        version_number: u8,
+        section_number: u8,
+        last_section_number: u8,
+        data: &[u8],
+    ) -> PsiSection<'_> {
+        PsiSection {
+            table_id: 0x50,
+            syntax: Some(PsiSectionSyntax {
+                table_id_extension: 0x0400,
+                version_number,
+                current_next_indicator: true,
+                section_number,
+                last_section_number,
+            }),
+            data,
+            crc32: 0,
+        }
+    }
+
+    fn event_ids(events: Option<Vec<EitEvent>>) -> Option<Vec<u16>> {
+        events.map(|events| events.iter().map(|e| e.event_id.get()).collect())
+    }
+
+    #[test]
+    fn test_eit_accumulator() {
+        // セグメント0はセクション0～1、セグメント1はセクション8のみ
+        let data0 = section_data(1, &[1, 2]);
+        let data1 = section_data(1, &[3]);
+        let data8 = section_data(8, &[4]);
+
+        let mut acc = EitAccumulator::new();
+        assert_eq!(event_ids(acc.feed(&section(0, 0, 8, &data0))), None);
+        assert_eq!(event_ids(acc.feed(&section(0, 8, 8, &data8))), None);
+        assert_eq!(
+            event_ids(acc.feed(&section(0, 1, 8, &data1))),
+            Some(vec![1, 2, 3, 4]),
+        );
+        // 同じバージョンでは再度返さない
+        assert_eq!(event_ids(acc.feed(&section(0, 1, 8, &data1))), None);
+
+        // バージョンが更新されたら受信し直す
+        assert_eq!(event_ids(acc.feed(&section(1, 8, 8, &data8))), None);
+        assert_eq!(event_ids(acc.feed(&section(1, 0, 8, &data0))), None);
+        assert_eq!(
+            event_ids(acc.feed(&section(1, 1, 8, &data1))),
+            Some(vec![1, 2, 3, 4]),
+        );
+
+        acc.remove(0x50, 0x0400);
+        assert_eq!(event_ids(acc.feed(&section(1, 0, 8, &data0))), None);
+    }
+}
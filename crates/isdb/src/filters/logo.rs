@@ -40,7 +40,7 @@ pub struct LogoDownloadFilter<F> {
 
     services: FxHashMap<ServiceId, Service>,
     versions: FxHashMap<u32, u16>,
-    logo_downloads: FxHashMap<u16, dsmcc::download::DownloadData>,
+    logo_downloads: dsmcc::download::ModuleReassembler,
 
     callback: F,
 }
@@ -76,7 +76,7 @@ impl<F: FnMut(&LogoData)> LogoDownloadFilter<F> {
 
             services: FxHashMap::default(),
             versions: FxHashMap::default(),
-            logo_downloads: FxHashMap::default(),
+            logo_downloads: dsmcc::download::ModuleReassembler::new(),
 
             callback: f,
         }
@@ -218,23 +218,11 @@ impl<F: FnMut(&LogoData)> LogoDownloadFilter<F> {
                     // log::trace!("DII Logo Data [PID {:04x}] : Download ID {:08x} / Module ID {:04x} / Module size {}",
                     //             pid, dii.download_id, module.module_id, module.module_size);
 
-                    match self.logo_downloads.entry(module.module_id) {
-                        std::collections::hash_map::Entry::Occupied(mut entry) => {
-                            if entry.get().needs_restart(&dii, module) {
-                                entry.insert(dsmcc::download::DownloadData::new(&dii, module));
-                            }
-                        }
-                        std::collections::hash_map::Entry::Vacant(entry) => {
-                            entry.insert(dsmcc::download::DownloadData::new(&dii, module));
-                        }
-                    }
+                    self.logo_downloads.add_module(&dii, module);
                 }
             }
             Some(dsmcc::table::DsmccSection::Ddb(ddb)) => {
-                let Some(dd) = self.logo_downloads.get_mut(&ddb.module_id) else {
-                    return;
-                };
-                let Some(data) = dd.store(&ddb) else {
+                let Some(data) = self.logo_downloads.store(&ddb) else {
                     // ダウンロード進行中
                     return;
                 };
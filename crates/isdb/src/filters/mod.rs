@@ -1,4 +1,5 @@
 //! TSを扱うのに便利なフィルター。
 
+pub mod eit;
 pub mod logo;
 pub mod sorter;
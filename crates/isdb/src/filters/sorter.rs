@@ -5,11 +5,13 @@ use smallvec::SmallVec;
 
 use crate::demux;
 use crate::eight::char::TimeControlMode;
+use crate::eight::decode;
 use crate::lang;
+use crate::packet::TransportScramblingControl;
 use crate::pes;
-use crate::pid::Pid;
+use crate::pid::{Pid, PidTable};
 use crate::psi;
-use crate::psi::table::{EventId, ServiceId};
+use crate::psi::table::{EventId, RunningStatus, ServiceId};
 use crate::time;
 use crate::AribStr;
 use crate::AribString;
@@ -31,12 +33,34 @@ pub trait Shooter {
     /// `service`のPMTが更新された際に呼ばれる。
     fn on_pmt_updated(&mut self, services: &ServiceMap, service: &Service);
 
+    /// CATが更新された際に呼ばれる。
+    ///
+    /// EMMのPIDは`cat.descriptors`内の限定受信方式記述子から得ることができる。
+    fn on_cat_updated(&mut self, services: &ServiceMap, cat: &psi::table::Cat) {
+        let _ = (services, cat);
+    }
+
+    /// 自ネットワークのNITが更新された際に呼ばれる。
+    fn on_nit_updated(&mut self, services: &ServiceMap, nit: &psi::table::Nit) {
+        let _ = (services, nit);
+    }
+
     /// `service`のEITが更新された際に呼ばれる。
     ///
     /// このメソッドが呼ばれた後に`Service::present_event`等が`None`を返す場合、
     /// EIT未受信ではないためイベントが存在しないことを表す。
     fn on_eit_updated(&mut self, services: &ServiceMap, service: &Service, is_present: bool);
 
+    /// `service`における現在のイベントの進行状態が変化した際に呼ばれる。
+    fn on_running_status_changed(
+        &mut self,
+        services: &ServiceMap,
+        service: &Service,
+        new_status: RunningStatus,
+    ) {
+        let _ = (services, service, new_status);
+    }
+
     /// 映像パケットを受信した際に呼ばれる。
     fn on_video_packet(
         &mut self,
@@ -75,6 +99,26 @@ pub trait Shooter {
         caption: &Caption,
     );
 
+    /// 映像・音声パケットのスクランブル状態が変化した際に呼ばれる。
+    ///
+    /// B-CASカード等による復号が開始・停止したことを検知するのに使うことができる。
+    fn on_scrambling_changed(
+        &mut self,
+        services: &ServiceMap,
+        pid: Pid,
+        new_state: TransportScramblingControl,
+    ) {
+        let _ = (services, pid, new_state);
+    }
+
+    /// スクランブルされた映像・音声パケットを受信した際に呼ばれる。
+    ///
+    /// パケットごとに呼ばれるため、状態の変化のみを知りたい場合は
+    /// [`Shooter::on_scrambling_changed`]を使う。
+    fn on_scrambled_packet(&mut self, services: &ServiceMap, pid: Pid) {
+        let _ = (services, pid);
+    }
+
     /// PCRが更新された際に呼ばれる。
     ///
     /// PCRが更新された全サービス識別が`service_ids`で渡される。
@@ -158,8 +202,20 @@ pub struct Service {
 
     provider_name: AribString,
     service_name: AribString,
+    /// SDTのロゴ伝送記述子によるロゴ識別
+    logo_id: Option<u16>,
+    /// SDTのロゴ伝送記述子（CDT伝送方式1）によるダウンロードデータ識別
+    logo_download_data_id: Option<u16>,
+    /// SDTのサービス記述子によるサービス形式種別
+    service_type: Option<psi::desc::ServiceType>,
+    /// SDTによるサービスの進行状態
+    service_running_status: RunningStatus,
+    /// SDTによるスクランブルの有無
+    free_ca_mode: bool,
     present_event: Option<EventInfo>,
     following_event: Option<EventInfo>,
+    /// 現在のイベントにおける進行状態
+    running_status: RunningStatus,
 }
 
 impl Service {
@@ -205,6 +261,18 @@ impl Service {
         &*self.audio_streams
     }
 
+    /// PCRを伝送するストリームを返す。
+    ///
+    /// PCRは映像ストリームで伝送されることが多いため映像ストリームから先に探し、
+    /// 見つからない場合は音声ストリームから探す。
+    /// PCRが映像・音声以外のPIDで伝送される場合は`None`を返す。
+    pub fn pcr_pid_stream(&self) -> Option<&Stream> {
+        self.video_streams
+            .iter()
+            .chain(&*self.audio_streams)
+            .find(|stream| stream.pid == self.pcr_pid)
+    }
+
     /// 字幕ストリーム。
     #[inline]
     pub fn caption_stream(&self) -> Option<&Stream> {
@@ -217,8 +285,21 @@ impl Service {
         self.superimpose_stream.as_ref()
     }
 
+    /// 映像・音声・字幕・文字スーパーの全ストリームを順に返すイテレーターを返す。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::filters::sorter::Service;
+    /// use isdb::Pid;
+    ///
+    /// // サービスに含まれる全ストリームのPIDを集める
+    /// fn stream_pids(service: &Service) -> Vec<Pid> {
+    ///     service.all_streams().map(|stream| stream.pid()).collect()
+    /// }
+    /// ```
     #[inline]
-    fn all_streams(&self) -> impl Iterator<Item = &Stream> {
+    pub fn all_streams(&self) -> impl Iterator<Item = &Stream> + '_ {
         std::iter::empty()
             .chain(&*self.video_streams)
             .chain(&*self.audio_streams)
@@ -238,6 +319,46 @@ impl Service {
         &*self.service_name
     }
 
+    /// SDTのロゴ伝送記述子で指定されたロゴ識別。
+    ///
+    /// CDT伝送方式1・2の場合のみ値を返す。
+    #[inline]
+    pub fn logo_id(&self) -> Option<u16> {
+        self.logo_id
+    }
+
+    /// SDTのロゴ伝送記述子で指定された、ロゴを伝送するCDTのダウンロードデータ識別。
+    ///
+    /// CDT伝送方式1の場合のみ値を返す。
+    #[inline]
+    pub fn logo_download_data_id(&self) -> Option<u16> {
+        self.logo_download_data_id
+    }
+
+    /// SDTのサービス記述子で指定されたサービス形式種別。
+    ///
+    /// SDT未受信、またはサービス記述子が無い場合は`None`を返す。
+    #[inline]
+    pub fn service_type(&self) -> Option<psi::desc::ServiceType> {
+        self.service_type
+    }
+
+    /// SDTで送出された、サービスの進行状態。
+    ///
+    /// SDT未受信の場合は[`RunningStatus::Undefined`]を返す。
+    #[inline]
+    pub fn service_running_status(&self) -> RunningStatus {
+        self.service_running_status
+    }
+
+    /// SDTで送出された、サービスがスクランブルされているかどうか。
+    ///
+    /// SDT未受信の場合は`false`を返す。
+    #[inline]
+    pub fn free_ca_mode(&self) -> bool {
+        self.free_ca_mode
+    }
+
     /// 現在のイベント情報。
     ///
     /// EIT未受信、またはイベントが存在しない場合に`None`を返す。
@@ -254,6 +375,64 @@ impl Service {
         self.following_event.as_ref()
     }
 
+    /// `is_present`が`true`なら現在の、`false`なら次のイベント情報を返す。
+    #[inline]
+    fn event(&self, is_present: bool) -> Option<&EventInfo> {
+        if is_present {
+            self.present_event.as_ref()
+        } else {
+            self.following_event.as_ref()
+        }
+    }
+
+    /// `is_present`が`true`なら現在の、`false`なら次のイベントにおける番組名を返す。
+    ///
+    /// EIT未受信、イベントが存在しない、または短形式イベント記述子が無い場合に`None`を返す。
+    #[inline]
+    pub fn event_name(&self, is_present: bool) -> Option<&AribStr> {
+        self.event(is_present)?.name.as_deref()
+    }
+
+    /// `is_present`が`true`なら現在の、`false`なら次のイベントにおける番組情報を返す。
+    ///
+    /// EIT未受信、イベントが存在しない、または短形式イベント記述子が無い場合に`None`を返す。
+    #[inline]
+    pub fn event_text(&self, is_present: bool) -> Option<&AribStr> {
+        self.event(is_present)?.text.as_deref()
+    }
+
+    /// 現在のイベントにおける番組名を返す。
+    ///
+    /// [`Service::event_name`]に`true`を渡した場合と同じ。
+    #[inline]
+    pub fn present_event_name(&self) -> Option<&AribStr> {
+        self.event_name(true)
+    }
+
+    /// 現在のイベントにおけるイベントリレー先を、サービス識別とイベント識別の組で返す。
+    ///
+    /// 現在のイベントにイベントグループ記述子でリレーが記述されていない場合は`None`を返す。
+    #[inline]
+    pub fn relay_event(&self) -> Option<(ServiceId, EventId)> {
+        self.present_event.as_ref()?.relay_to
+    }
+
+    /// 現在のイベントにおけるイベント移動元を、サービス識別とイベント識別の組で返す。
+    ///
+    /// 現在のイベントにイベントグループ記述子で移動が記述されていない場合は`None`を返す。
+    #[inline]
+    pub fn movement_from(&self) -> Option<(ServiceId, EventId)> {
+        self.present_event.as_ref()?.movement_from
+    }
+
+    /// EIT[p/f]で送出された、現在のイベントにおける進行状態。
+    ///
+    /// EIT未受信、またはイベントが存在しない場合は[`RunningStatus::Undefined`]を返す。
+    #[inline]
+    pub fn running_status(&self) -> RunningStatus {
+        self.running_status
+    }
+
     fn find_stream(streams: &[Stream], component_tag: Option<u8>) -> Option<&Stream> {
         component_tag
             .and_then(|component_tag| {
@@ -277,6 +456,14 @@ impl Service {
         Self::find_stream(&*self.video_streams, video_tag)
     }
 
+    /// `pid`と一致するPIDの映像ストリームを検索する。
+    ///
+    /// [`find_video_stream`][Service::find_video_stream]とは異なり、
+    /// 一致する映像ストリームがない場合はデフォルトESではなく`None`を返す。
+    pub fn find_video_stream_by_pid(&self, pid: Pid) -> Option<&Stream> {
+        self.video_streams.iter().find(|s| s.pid == pid)
+    }
+
     /// `audio_tag`と一致するコンポーネントタグの音声ストリームを検索する。
     ///
     /// `audio_tag`に`None`を指定した場合、または指定されたコンポーネントタグと一致する音声ストリームがない場合、
@@ -288,6 +475,89 @@ impl Service {
     pub fn find_audio_stream(&self, audio_tag: Option<u8>) -> Option<&Stream> {
         Self::find_stream(&*self.audio_streams, audio_tag)
     }
+
+    /// 現在のイベントにおいて主コンポーネントとされる音声ストリームを返す。
+    ///
+    /// 音声コンポーネント記述子で`main_component_flag`が立っている音声ストリームを返すが、
+    /// そのような音声ストリームがない場合はデフォルトESを返す。
+    pub fn preferred_audio_stream(&self) -> Option<&Stream> {
+        let main_tag = self
+            .present_event
+            .as_ref()
+            .and_then(|event| {
+                event
+                    .audio_components
+                    .iter()
+                    .find(|ac| ac.main_component_flag)
+            })
+            .map(|ac| ac.component_tag);
+        Self::find_stream(&self.audio_streams, main_tag)
+    }
+
+    /// PMTの内容からストリーム一覧を設定する。
+    ///
+    /// 設定したストリームごとにそのPIDとタグを引数として`on_stream`が呼ばれる。
+    fn fill_streams<F: FnMut(Pid, Tag)>(&mut self, pmt: &psi::table::Pmt, mut on_stream: F) {
+        self.video_streams.clear();
+        self.audio_streams.clear();
+        self.caption_stream = None;
+        self.superimpose_stream = None;
+
+        for stream in &*pmt.streams {
+            let video_encode_format = stream
+                .descriptors
+                .get::<psi::desc::VideoDecodeControlDescriptor>()
+                .map(|vdcd| vdcd.video_encode_format);
+            let component_tag = stream
+                .descriptors
+                .get::<psi::desc::StreamIdDescriptor>()
+                .map(|sid| sid.component_tag);
+            let make_stream = || Stream {
+                pid: stream.elementary_pid,
+                stream_type: stream.stream_type,
+                component_tag,
+                video_encode_format,
+            };
+
+            let tag = match (stream.stream_type, component_tag) {
+                (t, _) if t.is_video() => {
+                    self.video_streams.push(make_stream());
+                    Tag::Video
+                }
+
+                (t, _) if t.is_audio() => {
+                    self.audio_streams.push(make_stream());
+                    Tag::Audio
+                }
+
+                (psi::desc::StreamType::CAPTION, Some(0x30 | 0x87))
+                    if self.caption_stream.is_none() =>
+                {
+                    // 字幕のデフォルトES
+                    self.caption_stream = Some(make_stream());
+                    Tag::Caption
+                }
+
+                (psi::desc::StreamType::CAPTION, Some(0x38 | 0x88))
+                    if self.superimpose_stream.is_none() =>
+                {
+                    // 文字スーパーのデフォルトES
+                    self.superimpose_stream = Some(make_stream());
+                    Tag::Superimpose
+                }
+
+                _ => continue,
+            };
+
+            on_stream(stream.elementary_pid, tag);
+        }
+
+        // コンポーネントタグの昇順でソート
+        let f = |s: &Stream| s.component_tag;
+        self.video_streams.sort_unstable_by_key(f);
+        self.audio_streams.sort_unstable_by_key(f);
+        self.pmt_filled = true;
+    }
 }
 
 /// 番組に関する情報。
@@ -299,6 +569,8 @@ pub struct EventInfo {
     pub start_time: time::DateTime,
     /// 番組の継続時間（秒単位）。
     pub duration: u32,
+    /// 進行状態。
+    pub running_status: RunningStatus,
     /// 番組名。
     pub name: Option<AribString>,
     /// 番組情報。
@@ -311,6 +583,10 @@ pub struct EventInfo {
     pub audio_components: Vec<AudioComponent>,
     /// 分類。
     pub genres: Option<SmallVec<[psi::desc::ContentGenre; 7]>>,
+    /// イベントリレー先のサービス識別とイベント識別。
+    pub relay_to: Option<(ServiceId, EventId)>,
+    /// イベント移動元のサービス識別とイベント識別。
+    pub movement_from: Option<(ServiceId, EventId)>,
 }
 
 /// 拡張番組情報の要素。
@@ -425,14 +701,174 @@ impl<'a> Caption<'a> {
 /// サービス識別からサービス情報を得るための、順序を保持する連想配列。
 pub type ServiceMap = indexmap::IndexMap<ServiceId, Service, fxhash::FxBuildHasher>;
 
+/// [`ServiceMap`]に対する拡張メソッド。
+pub trait ServiceMapExt {
+    /// サービス名からサービスを検索する。
+    ///
+    /// 比較は全角英数字・記号・空白を半角に寄せた上で行われ、
+    /// 完全一致するサービスが無い場合は前方一致する最初のサービスを返す。
+    /// `query`が空の場合は`None`を返す。
+    fn by_name(&self, query: &AribStr) -> Option<(ServiceId, &Service)>;
+
+    /// サービス識別とサービス名の組を順に返すイテレーターを返す。
+    fn names(&self) -> ServiceNames<'_>;
+
+    /// サービス識別の昇順でサービスを返すイテレーターを返す。
+    ///
+    /// 挿入順に依存しないため、PATの更新によってサービスの並びが変わっても順序は変わらない。
+    fn iter_ordered(&self) -> OrderedServices<'_>;
+
+    /// [`iter_ordered`][ServiceMapExt::iter_ordered]の順序における`id`の位置を返す。
+    fn get_ordered_index(&self, id: ServiceId) -> Option<usize>;
+}
+
+impl ServiceMapExt for ServiceMap {
+    fn by_name(&self, query: &AribStr) -> Option<(ServiceId, &Service)> {
+        let query = normalize_name(query);
+        if query.is_empty() {
+            return None;
+        }
+
+        let names: SmallVec<[_; 4]> = self
+            .values()
+            .map(|service| (service, normalize_name(&service.service_name)))
+            .collect();
+        names
+            .iter()
+            .find(|(_, name)| *name == query)
+            .or_else(|| names.iter().find(|(_, name)| name.starts_with(&*query)))
+            .map(|&(service, _)| (service.service_id, service))
+    }
+
+    #[inline]
+    fn names(&self) -> ServiceNames<'_> {
+        ServiceNames(self.values())
+    }
+
+    fn iter_ordered(&self) -> OrderedServices<'_> {
+        let mut services: Vec<&Service> = self.values().collect();
+        services.sort_unstable_by_key(|service| service.service_id);
+        OrderedServices(services.into_iter())
+    }
+
+    fn get_ordered_index(&self, id: ServiceId) -> Option<usize> {
+        if !self.contains_key(&id) {
+            return None;
+        }
+        Some(self.keys().filter(|&&key| key < id).count())
+    }
+}
+
+/// [`ServiceMapExt::iter_ordered`]で返される、サービス識別の昇順でサービスを返すイテレーター。
+#[derive(Debug, Clone)]
+pub struct OrderedServices<'a>(std::vec::IntoIter<&'a Service>);
+
+impl<'a> Iterator for OrderedServices<'a> {
+    type Item = (ServiceId, &'a Service);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|service| (service.service_id, service))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for OrderedServices<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|service| (service.service_id, service))
+    }
+}
+
+impl<'a> ExactSizeIterator for OrderedServices<'a> {}
+
+/// [`ServiceMapExt::names`]で返される、サービス識別とサービス名の組を返すイテレーター。
+#[derive(Debug, Clone)]
+pub struct ServiceNames<'a>(indexmap::map::Values<'a, ServiceId, Service>);
+
+impl<'a> Iterator for ServiceNames<'a> {
+    type Item = (ServiceId, &'a AribStr);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|service| (service.service_id, service.service_name()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for ServiceNames<'a> {}
+
+/// 比較用にサービス名を文字列化し、全角英数字・記号・空白を半角に変換する。
+fn normalize_name(name: &AribStr) -> String {
+    name.to_string(decode::Options::DEFAULT)
+        .chars()
+        .map(|c| match c {
+            // 全角英数字・記号
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFF01 + 0x21).unwrap_or(c),
+            // 全角空白
+            '\u{3000}' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+/// `pmt`から最小限のサービスを構築し、デマルチプレクサを介さずに`services`に追加する。
+///
+/// 同じサービス識別のサービスが既にある場合はその内容を置き換える。
+/// PMTのPIDは分からないため、構築されるサービスにおけるPMTのPIDは[`Pid::NULL`]となる。
+///
+/// テスト用の関数で、`testing`フィーチャーが必要。
+#[cfg(any(test, feature = "testing"))]
+pub fn inject_pmt(services: &mut ServiceMap, pmt: psi::table::Pmt) {
+    let mut service = Service {
+        service_id: pmt.program_number,
+        pmt_pid: Pid::NULL,
+        pcr_pid: pmt.pcr_pid,
+        pcr: None,
+        pmt_filled: false,
+        video_streams: Vec::new(),
+        audio_streams: Vec::new(),
+        caption_stream: None,
+        superimpose_stream: None,
+        provider_name: AribString::new(),
+        service_name: AribString::new(),
+        logo_id: None,
+        logo_download_data_id: None,
+        service_type: None,
+        service_running_status: RunningStatus::Undefined,
+        free_ca_mode: false,
+        present_event: None,
+        following_event: None,
+        running_status: RunningStatus::Undefined,
+    };
+    service.fill_streams(&pmt, |_, _| {});
+    services.insert(service.service_id, service);
+}
+
 /// 仕分け用フィルター。
 pub struct Sorter<T> {
     shooter: T,
     repo: psi::Repository,
 
     services: ServiceMap,
+    // PATで示されたNITのPID
+    nit_pid: Pid,
     // PAT前のEIT
     events: FxHashMap<ServiceId, (Option<EventInfo>, Option<EventInfo>)>,
+    // 映像・音声パケットにおける最後のスクランブル状態
+    scrambling: PidTable<TransportScramblingControl>,
 }
 
 impl<T> Sorter<T> {
@@ -443,7 +879,9 @@ impl<T> Sorter<T> {
             repo: psi::Repository::new(),
 
             services: ServiceMap::default(),
+            nit_pid: Pid::NIT,
             events: FxHashMap::default(),
+            scrambling: PidTable::from_fn(|_| TransportScramblingControl::default()),
         }
     }
 
@@ -469,10 +907,31 @@ impl<T> Sorter<T> {
         &self.services
     }
 
+    /// 現在のTSにおけるすべてのサービスを可変参照で返す。
+    ///
+    /// テスト用に任意のサービスを注入するためのもので、`testing`フィーチャーが必要。
+    #[cfg(any(test, feature = "testing"))]
+    #[inline]
+    pub fn services_mut(&mut self) -> &mut ServiceMap {
+        &mut self.services
+    }
+
     /// すべてのサービス及び内包する`Shooter`を、前者は参照で、後者は可変参照で返す。
     pub fn pair(&mut self) -> (&ServiceMap, &mut T) {
         (&self.services, &mut self.shooter)
     }
+
+    /// PSIテーブルのバージョン情報を消去する。
+    ///
+    /// ストリームをシークした後にこのメソッドを呼び出すことで、
+    /// シーク前と同じバージョンのPATやPMT等であっても再度処理されるようになる。
+    ///
+    /// 映像・音声パケットについて記録しているスクランブル状態も消去する。
+    #[inline]
+    pub fn reset_repositories(&mut self) {
+        self.repo.clear();
+        self.scrambling.fill(TransportScramblingControl::default());
+    }
 }
 
 mod sealed {
@@ -481,7 +940,9 @@ mod sealed {
     pub enum Tag {
         // PSI
         Pat,
+        Cat,
         Pmt,
+        Nit,
         Sdt,
         Eit,
         Tot,
@@ -504,6 +965,8 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
 
     fn on_setup(&mut self, table: &mut demux::Table<Self::Tag>) {
         table.set_as_psi(Pid::PAT, Tag::Pat);
+        table.set_as_psi(Pid::CAT, Tag::Cat);
+        table.set_as_psi(Pid::NIT, Tag::Nit);
         table.set_as_psi(Pid::SDT, Tag::Sdt);
         table.set_as_psi(Pid::H_EIT, Tag::Eit);
         table.set_as_psi(Pid::L_EIT, Tag::Eit);
@@ -516,6 +979,19 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
     }
 
     fn on_packet_storing(&mut self, ctx: &mut demux::Context<Self::Tag>) {
+        if matches!(ctx.tag(), Tag::Video | Tag::Audio) {
+            let pid = ctx.packet().pid();
+            let state = ctx.packet().transport_scrambling_control();
+            if self.scrambling[pid] != state {
+                self.scrambling[pid] = state;
+                self.shooter
+                    .on_scrambling_changed(&self.services, pid, state);
+            }
+            if state.is_scrambled() {
+                self.shooter.on_scrambled_packet(&self.services, pid);
+            }
+        }
+
         // PMTのpcr_pidとESのPIDが共有される場合があるのでon_custom_packetではなくこちらで処理する
         let Some(pcr) = ctx.packet().adaptation_field().and_then(|af| af.pcr()) else {
             return;
@@ -542,6 +1018,14 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                     return;
                 };
 
+                // PATにNITが記述されていない場合は既定のPIDとする
+                let nit_pid = pat.network_pid().unwrap_or(Pid::NIT);
+                if nit_pid != self.nit_pid {
+                    ctx.table().unset(self.nit_pid);
+                    ctx.table().set_as_psi(nit_pid, Tag::Nit);
+                    self.nit_pid = nit_pid;
+                }
+
                 // メモリを解放するためself.eventsを置き換える
                 let mut events = std::mem::take(&mut self.events);
 
@@ -553,6 +1037,9 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                     entry.or_insert_with(|| {
                         let (present_event, following_event) =
                             events.remove(&service_id).unwrap_or_else(|| (None, None));
+                        let running_status = present_event
+                            .as_ref()
+                            .map_or(RunningStatus::Undefined, |e| e.running_status);
 
                         Service {
                             service_id,
@@ -566,8 +1053,14 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                             superimpose_stream: None,
                             provider_name: AribString::new(),
                             service_name: AribString::new(),
+                            logo_id: None,
+                            logo_download_data_id: None,
+                            service_type: None,
+                            service_running_status: RunningStatus::Undefined,
+                            free_ca_mode: false,
                             present_event,
                             following_event,
+                            running_status,
                         }
                     });
 
@@ -611,68 +1104,12 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
 
                 let mut lost_pids: FxHashSet<Pid> = service.all_streams().map(|s| s.pid).collect();
 
-                service.video_streams.clear();
-                service.audio_streams.clear();
-                service.caption_stream = None;
-                service.superimpose_stream = None;
-
-                for stream in &*pmt.streams {
-                    let video_encode_format = stream
-                        .descriptors
-                        .get::<psi::desc::VideoDecodeControlDescriptor>()
-                        .map(|vdcd| vdcd.video_encode_format);
-                    let component_tag = stream
-                        .descriptors
-                        .get::<psi::desc::StreamIdDescriptor>()
-                        .map(|sid| sid.component_tag);
-                    let make_stream = || Stream {
-                        pid: stream.elementary_pid,
-                        stream_type: stream.stream_type,
-                        component_tag,
-                        video_encode_format,
-                    };
-
-                    let tag = match (stream.stream_type, component_tag) {
-                        (t, _) if t.is_video() => {
-                            service.video_streams.push(make_stream());
-                            Tag::Video
-                        }
-
-                        (t, _) if t.is_audio() => {
-                            service.audio_streams.push(make_stream());
-                            Tag::Audio
-                        }
-
-                        (psi::desc::StreamType::CAPTION, Some(0x30 | 0x87))
-                            if service.caption_stream.is_none() =>
-                        {
-                            // 字幕のデフォルトES
-                            service.caption_stream = Some(make_stream());
-                            Tag::Caption
-                        }
-
-                        (psi::desc::StreamType::CAPTION, Some(0x38 | 0x88))
-                            if service.superimpose_stream.is_none() =>
-                        {
-                            // 文字スーパーのデフォルトES
-                            service.superimpose_stream = Some(make_stream());
-                            Tag::Superimpose
-                        }
-
-                        _ => continue,
-                    };
-
-                    if !ctx.table().is_pes(stream.elementary_pid) {
-                        ctx.table().set_as_pes(stream.elementary_pid, tag);
+                service.fill_streams(&pmt, |pid, tag| {
+                    if !ctx.table().is_pes(pid) {
+                        ctx.table().set_as_pes(pid, tag);
                     }
-                    lost_pids.remove(&stream.elementary_pid);
-                }
-
-                // コンポーネントタグの昇順でソート
-                let f = |s: &Stream| s.component_tag;
-                service.video_streams.sort_unstable_by_key(f);
-                service.audio_streams.sort_unstable_by_key(f);
-                service.pmt_filled = true;
+                    lost_pids.remove(&pid);
+                });
 
                 // 消えたPIDを設定解除
                 for &lost_pid in &lost_pids {
@@ -684,6 +1121,20 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                     self.services.get(&pmt.program_number).unwrap(),
                 );
             }
+            Tag::Cat => {
+                let Some(cat) = self.repo.read::<psi::table::Cat>(psi) else {
+                    return;
+                };
+
+                self.shooter.on_cat_updated(&self.services, &cat);
+            }
+            Tag::Nit => {
+                let Some(nit) = self.repo.read::<psi::table::Nit>(psi) else {
+                    return;
+                };
+
+                self.shooter.on_nit_updated(&self.services, &nit);
+            }
             Tag::Sdt => {
                 let Some(psi::table::Sdt::Actual(sdt)) = self.repo.read(psi) else {
                     return;
@@ -693,10 +1144,30 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                     let Some(service) = self.services.get_mut(&svc.service_id) else {
                         continue;
                     };
+
+                    (service.logo_id, service.logo_download_data_id) =
+                        match svc
+                            .descriptors
+                            .get::<psi::desc::LogoTransmissionDescriptor>()
+                        {
+                            Some(psi::desc::LogoTransmissionDescriptor::Cdt1(cdt1)) => {
+                                (Some(cdt1.logo_id), Some(cdt1.download_data_id))
+                            }
+                            Some(psi::desc::LogoTransmissionDescriptor::Cdt2(cdt2)) => {
+                                (Some(cdt2.logo_id), None)
+                            }
+                            _ => (None, None),
+                        };
+
+                    service.service_running_status = svc.running_status;
+                    service.free_ca_mode = svc.free_ca_mode;
+
                     let Some(sd) = svc.descriptors.get::<psi::desc::ServiceDescriptor>() else {
+                        service.service_type = None;
                         continue;
                     };
 
+                    service.service_type = Some(sd.service_type);
                     sd.service_provider_name
                         .clone_into(&mut service.provider_name);
                     sd.service_name.clone_into(&mut service.service_name);
@@ -778,31 +1249,61 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
                         .get::<psi::desc::ContentDescriptor>()
                         .map(|cd| cd.genres.into());
 
+                    let mut relay_to = None;
+                    let mut movement_from = None;
+                    for egd in event
+                        .descriptors
+                        .get_all::<psi::desc::EventGroupDescriptor>()
+                    {
+                        let target = match egd.group {
+                            psi::desc::EventGroup::Relay(_) => &mut relay_to,
+                            psi::desc::EventGroup::Movement(_) => &mut movement_from,
+                            _ => continue,
+                        };
+                        if let Some(ev) = egd.events.first() {
+                            target.get_or_insert((ev.service_id, ev.event_id));
+                        }
+                    }
+
                     EventInfo {
                         event_id: event.event_id,
                         start_time: event.start_time.clone(),
                         duration: event.duration,
+                        running_status: event.running_status,
                         name,
                         text,
                         extended_items,
                         video_components,
                         audio_components,
                         genres,
+                        relay_to,
+                        movement_from,
                     }
                 });
 
                 if let Some(service) = self.services.get_mut(&eit.service_id) {
+                    let mut status_changed = false;
                     if is_present {
+                        let running_status = event
+                            .as_ref()
+                            .map_or(RunningStatus::Undefined, |e| e.running_status);
+                        status_changed = service.running_status != running_status;
+                        service.running_status = running_status;
                         service.present_event = event;
                     } else {
                         service.following_event = event;
                     }
 
-                    self.shooter.on_eit_updated(
-                        &self.services,
-                        self.services.get(&eit.service_id).unwrap(),
-                        is_present,
-                    );
+                    let service = self.services.get(&eit.service_id).unwrap();
+                    self.shooter
+                        .on_eit_updated(&self.services, service, is_present);
+                    if status_changed {
+                        self.shooter.on_running_status_changed(
+                            &self.services,
+                            service,
+                            service.running_status,
+                        );
+                    }
                 } else {
                     let events = self
                         .events
@@ -925,3 +1426,670 @@ impl<T: Shooter> demux::Filter for Sorter<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    fn service(service_id: u16, name: &[u8]) -> Service {
+        Service {
+            service_id: ServiceId::new(service_id).unwrap(),
+            pmt_pid: Pid::new(0x1FC8),
+            pcr_pid: Pid::NULL,
+            pcr: None,
+            pmt_filled: false,
+            video_streams: Vec::new(),
+            audio_streams: Vec::new(),
+            caption_stream: None,
+            superimpose_stream: None,
+            provider_name: AribString::new(),
+            service_name: AribStr::from_bytes(name).to_owned(),
+            logo_id: None,
+            logo_download_data_id: None,
+            service_type: None,
+            service_running_status: RunningStatus::Undefined,
+            free_ca_mode: false,
+            present_event: None,
+            following_event: None,
+            running_status: RunningStatus::Undefined,
+        }
+    }
+
+    #[test]
+    fn test_service_by_name() {
+        let mut services = ServiceMap::default();
+        for service in [
+            // ＮＨＫ総合１
+            service(1024, b"\x23\x4E\x23\x48\x23\x4B\x41\x6D\x39\x67\x23\x31"),
+            // ＮＨＫ総合２
+            service(1025, b"\x23\x4E\x23\x48\x23\x4B\x41\x6D\x39\x67\x23\x32"),
+            // ＮＨＫＥテレ１
+            service(
+                1032,
+                b"\x23\x4E\x23\x48\x23\x4B\x23\x45\x25\x46\x25\x6C\x23\x31",
+            ),
+        ] {
+            services.insert(service.service_id, service);
+        }
+
+        assert_eq!(services.names().len(), 3);
+        assert_eq!(
+            services.names().map(|(id, _)| id.get()).collect::<Vec<_>>(),
+            [1024, 1025, 1032],
+        );
+
+        // 全角の「ＮＨＫ総合２」に完全一致
+        let query = AribStr::from_bytes(b"\x23\x4E\x23\x48\x23\x4B\x41\x6D\x39\x67\x23\x32");
+        assert_eq!(services.by_name(query).map(|(id, _)| id.get()), Some(1025));
+
+        // 英数字集合（半角）の「NHK」で前方一致
+        let query = AribStr::from_bytes(b"\x1B\x28\x4A\x89NHK");
+        assert_eq!(services.by_name(query).map(|(id, _)| id.get()), Some(1024));
+
+        assert_matches!(services.by_name(AribStr::from_bytes(b"")), None);
+        assert_eq!(
+            services
+                .by_name(AribStr::from_bytes(b"\x23\x42"))
+                .map(|(id, _)| id),
+            None,
+        );
+    }
+
+    /// サービス0x0400（PMT_PID=0x01F0）と0x0401（PMT_PID=0x01F1）を含み、NIT_PID=0x0010のPAT。
+    const PAT: &[u8] = &hex_literal::hex!(
+        "
+00 B0 15 7F E0 C7 00 00 00 00 E0 10 04 00 E1 F0
+04 01 E1 F1 35 49 90 AC
+"
+    );
+    /// [`PAT`]のNIT_PIDを0x0011としたもの。
+    const PAT_NIT_0011: &[u8] = &hex_literal::hex!(
+        "
+00 B0 15 7F E0 C7 00 00 00 00 E0 11 04 00 E1 F0
+04 01 E1 F1 C7 49 3A CA
+"
+    );
+    /// サービス0x0400のみを含むPAT。
+    const PAT_SINGLE: &[u8] = &hex_literal::hex!(
+        "
+00 B0 0D 00 01 C1 00 00 04 00 FF C8 91 99 A4 39
+"
+    );
+    /// サービス0x0400のPMT。PCR_PID=0x0100、MPEG2映像（PID=0x0100）。
+    const PMT: &[u8] = &hex_literal::hex!(
+        "
+02 B0 12 04 00 C1 00 00 E1 00 F0 00 02 E1 00 F0
+00 6D 31 53 67
+"
+    );
+    /// CA_PID=0x0080のCAT。
+    const CAT: &[u8] = &hex_literal::hex!(
+        "
+01 B0 0F FF FF C1 00 00 09 04 00 05 E0 80 4F BA
+D5 EC
+"
+    );
+    /// ネットワーク0x0004のNIT。
+    const NIT: &[u8] = &hex_literal::hex!(
+        "
+40 F0 13 00 04 C1 00 00 F0 00 F0 06 40 10 00 04
+F0 00 A1 16 68 D7
+"
+    );
+    /// サービス0x0400のSDT。
+    const SDT: &[u8] = &hex_literal::hex!(
+        "
+42 F0 1F 00 01 C1 00 00 00 04 FF 04 00 FC 80 0E
+48 03 01 00 00 CF 07 01 FE 05 F0 03 00 21 9B 1C
+44 EA
+"
+    );
+    /// [`SDT`]の進行状態が非実行中、スクランブルありに変わったもの。
+    const SDT_V1: &[u8] = &hex_literal::hex!(
+        "
+42 F0 1F 00 01 C3 00 00 00 04 FF 04 00 FC 30 0E
+48 03 01 00 00 CF 07 01 FE 05 F0 03 00 21 9E 87
+A2 A7
+"
+    );
+    /// [`SDT`]からサービス記述子を除いたもの。
+    const SDT_V2: &[u8] = &hex_literal::hex!(
+        "
+42 F0 1A 00 01 C5 00 00 00 04 FF 04 00 FC 80 09
+CF 07 01 FE 05 F0 03 00 21 D8 79 F8 D9
+"
+    );
+    /// サービス0x0400における、進行状態が実行中であるイベント0x0001のEIT[p/f]（現在）。
+    const EIT_PRESENT: &[u8] = &hex_literal::hex!(
+        "
+4E F0 1B 04 00 C1 00 01 00 01 00 04 01 4E 00 01
+E2 D9 12 00 00 00 30 00 80 00 3B F8 A6 0B
+"
+    );
+    /// [`EIT_PRESENT`]のバージョンだけを変えたもの。
+    const EIT_PRESENT_V1: &[u8] = &hex_literal::hex!(
+        "
+4E F0 1B 04 00 C3 00 01 00 01 00 04 01 4E 00 01
+E2 D9 12 00 00 00 30 00 80 00 B4 4A 21 24
+"
+    );
+    /// [`EIT_PRESENT`]の進行状態が非実行中に変わったもの。
+    const EIT_PRESENT_V2: &[u8] = &hex_literal::hex!(
+        "
+4E F0 1B 04 00 C5 00 01 00 01 00 04 01 4E 00 01
+E2 D9 12 00 00 00 30 00 20 00 82 73 5B 5C
+"
+    );
+    /// [`EIT_PRESENT`]のバージョンを変え、イベント共有・サービス0x0408のイベント0x0010へのイベントリレー・
+    /// サービス0x0401のイベント0x0020からのイベント移動のイベントグループ記述子を加えたもの。
+    const EIT_PRESENT_GROUP: &[u8] = &hex_literal::hex!(
+        "
+4E F0 30 04 00 C9 00 01 00 01 00 04 01 4E 00 01
+E2 D9 12 00 00 00 30 00 80 15 D6 05 11 04 00 00
+01 D6 05 21 04 08 00 10 D6 05 31 04 01 00 20 B7
+B0 70 C5
+"
+    );
+    /// サービス0x0400における、進行状態が数秒以内に開始であるイベント0x0002のEIT[p/f]（次）。
+    const EIT_FOLLOWING: &[u8] = &hex_literal::hex!(
+        "
+4E F0 1B 04 00 C7 01 01 00 01 00 04 01 4E 00 02
+E2 D9 12 00 00 00 30 00 40 00 56 12 9C 4A
+"
+    );
+
+    /// `pid`で`section`を送るパケットを生成する。
+    fn psi_packet(pid: u16, cc: u8, section: &[u8]) -> crate::Packet {
+        let [hi, lo] = pid.to_be_bytes();
+        let mut packet = crate::Packet([0xFF; 188]);
+        packet.0[..5].copy_from_slice(&[0x47, 0x40 | hi, lo, 0x10 | cc, 0x00]);
+        packet.0[5..5 + section.len()].copy_from_slice(section);
+        packet
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        pat: usize,
+        ca_pids: Vec<Pid>,
+        nits: Vec<psi::table::NetworkId>,
+        scrambling: Vec<(Pid, TransportScramblingControl)>,
+        scrambled_packets: Vec<Pid>,
+        running_statuses: Vec<RunningStatus>,
+    }
+
+    impl Shooter for Counter {
+        fn on_pat_updated(&mut self, _: &ServiceMap) {
+            self.pat += 1;
+        }
+        fn on_cat_updated(&mut self, _: &ServiceMap, cat: &psi::table::Cat) {
+            self.ca_pids.extend(
+                cat.descriptors
+                    .get_all::<psi::desc::ConditionalAccessDescriptor>()
+                    .map(|cad| cad.ca_pid),
+            );
+        }
+        fn on_nit_updated(&mut self, _: &ServiceMap, nit: &psi::table::Nit) {
+            self.nits.push(nit.network_id);
+        }
+        fn on_pmt_updated(&mut self, _: &ServiceMap, _: &Service) {}
+        fn on_eit_updated(&mut self, _: &ServiceMap, _: &Service, _: bool) {}
+        fn on_running_status_changed(
+            &mut self,
+            _: &ServiceMap,
+            _: &Service,
+            new_status: RunningStatus,
+        ) {
+            self.running_statuses.push(new_status);
+        }
+        fn on_scrambling_changed(
+            &mut self,
+            _: &ServiceMap,
+            pid: Pid,
+            new_state: TransportScramblingControl,
+        ) {
+            self.scrambling.push((pid, new_state));
+        }
+        fn on_scrambled_packet(&mut self, _: &ServiceMap, pid: Pid) {
+            self.scrambled_packets.push(pid);
+        }
+        fn on_video_packet(
+            &mut self,
+            _: &ServiceMap,
+            _: Pid,
+            _: Option<time::Timestamp>,
+            _: Option<time::Timestamp>,
+            _: &[u8],
+        ) {
+        }
+        fn on_audio_packet(
+            &mut self,
+            _: &ServiceMap,
+            _: Pid,
+            _: Option<time::Timestamp>,
+            _: Option<time::Timestamp>,
+            _: &[u8],
+        ) {
+        }
+        fn on_caption(&mut self, _: &ServiceMap, _: Pid, _: Option<time::Timestamp>, _: &Caption) {}
+        fn on_superimpose(
+            &mut self,
+            _: &ServiceMap,
+            _: Pid,
+            _: Option<time::Timestamp>,
+            _: &Caption,
+        ) {
+        }
+    }
+
+    #[test]
+    fn test_reset_repositories() {
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT));
+        assert_eq!(demuxer.filter().shooter().pat, 1);
+        assert_eq!(demuxer.filter().services().len(), 2);
+
+        // 同じバージョンのPATは処理されない
+        demuxer.feed(&psi_packet(0x0000, 1, PAT));
+        assert_eq!(demuxer.filter().shooter().pat, 1);
+
+        // シーク後を想定
+        demuxer.reset_packets();
+        demuxer.filter_mut().reset_repositories();
+        demuxer.feed(&psi_packet(0x0000, 5, PAT));
+        assert_eq!(demuxer.filter().shooter().pat, 2);
+        assert_eq!(demuxer.filter().services().len(), 2);
+    }
+
+    #[test]
+    fn test_reset_scrambling() {
+        let video_packet = |tsc: u8, cc: u8| {
+            let mut packet = crate::Packet([0xFF; 188]);
+            packet.0[..4].copy_from_slice(&[0x47, 0x01, 0x00, tsc << 6 | 0x10 | cc]);
+            packet
+        };
+
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT));
+        demuxer.feed(&psi_packet(0x01F0, 0, PMT));
+
+        demuxer.feed(&video_packet(0b10, 0));
+        demuxer.feed(&video_packet(0b10, 1));
+        assert_eq!(
+            demuxer.filter().shooter().scrambling,
+            [(Pid::new(0x0100), TransportScramblingControl::EVEN_KEY)],
+        );
+        // スクランブルされたパケットは状態が変わらなくてもパケットごとに通知される
+        assert_eq!(
+            demuxer.filter().shooter().scrambled_packets,
+            [Pid::new(0x0100), Pid::new(0x0100)],
+        );
+
+        // シーク後、シーク前と同じ状態であっても通知される
+        demuxer.reset_packets();
+        demuxer.filter_mut().reset_repositories();
+        demuxer.feed(&video_packet(0b10, 5));
+        assert_eq!(
+            demuxer.filter().shooter().scrambling,
+            [
+                (Pid::new(0x0100), TransportScramblingControl::EVEN_KEY),
+                (Pid::new(0x0100), TransportScramblingControl::EVEN_KEY),
+            ],
+        );
+
+        // スクランブルされていないパケットは通知されない
+        demuxer.feed(&video_packet(0b00, 6));
+        assert_eq!(demuxer.filter().shooter().scrambled_packets.len(), 3);
+    }
+
+    #[test]
+    fn test_event_name() {
+        let event = |name: &[u8], text: &[u8]| EventInfo {
+            event_id: EventId::new(1).unwrap(),
+            start_time: time::DateTime::from_unix_timestamp(0).unwrap(),
+            duration: 60,
+            running_status: RunningStatus::Running,
+            name: Some(AribStr::from_bytes(name).to_owned()),
+            text: Some(AribStr::from_bytes(text).to_owned()),
+            extended_items: Vec::new(),
+            video_components: Vec::new(),
+            audio_components: Vec::new(),
+            genres: None,
+            relay_to: None,
+            movement_from: None,
+        };
+
+        let mut svc = service(1024, b"");
+        assert_eq!(svc.event_name(true), None);
+        assert_eq!(svc.present_event_name(), None);
+
+        svc.present_event = Some(event(b"\x23\x41", b"\x23\x42"));
+        svc.following_event = Some(event(b"\x23\x43", b"\x23\x44"));
+        assert_eq!(
+            svc.present_event_name(),
+            Some(AribStr::from_bytes(b"\x23\x41"))
+        );
+        assert_eq!(svc.event_name(true), svc.present_event_name());
+        assert_eq!(svc.event_text(true), Some(AribStr::from_bytes(b"\x23\x42")));
+        assert_eq!(
+            svc.event_name(false),
+            Some(AribStr::from_bytes(b"\x23\x43"))
+        );
+        assert_eq!(
+            svc.event_text(false),
+            Some(AribStr::from_bytes(b"\x23\x44"))
+        );
+    }
+
+    #[test]
+    fn test_pcr_pid_stream() {
+        let stream = |pid, stream_type| Stream {
+            pid: Pid::new(pid),
+            stream_type,
+            component_tag: None,
+            video_encode_format: None,
+        };
+
+        let mut svc = service(1024, b"");
+        svc.video_streams
+            .push(stream(0x0100, psi::desc::StreamType::MPEG2_VIDEO));
+        svc.audio_streams
+            .push(stream(0x0110, psi::desc::StreamType::AAC));
+        assert!(svc.pcr_pid_stream().is_none());
+
+        svc.pcr_pid = Pid::new(0x0100);
+        assert_eq!(svc.pcr_pid_stream().map(|s| s.pid), Some(Pid::new(0x0100)));
+
+        svc.pcr_pid = Pid::new(0x0110);
+        assert_eq!(svc.pcr_pid_stream().map(|s| s.pid), Some(Pid::new(0x0110)));
+
+        svc.pcr_pid = Pid::new(0x01FF);
+        assert!(svc.pcr_pid_stream().is_none());
+    }
+
+    #[test]
+    fn test_find_video_stream_by_pid() {
+        let stream = |pid, component_tag| Stream {
+            pid: Pid::new(pid),
+            stream_type: psi::desc::StreamType::MPEG2_VIDEO,
+            component_tag: Some(component_tag),
+            video_encode_format: None,
+        };
+
+        let mut svc = service(1024, b"");
+        svc.video_streams.push(stream(0x0100, 0x00));
+        svc.video_streams.push(stream(0x0101, 0x01));
+
+        let found = |pid| svc.find_video_stream_by_pid(Pid::new(pid)).map(|s| s.pid);
+        assert_eq!(found(0x0100), Some(Pid::new(0x0100)));
+        assert_eq!(found(0x0101), Some(Pid::new(0x0101)));
+        // デフォルトESにはフォールバックしない
+        assert_eq!(found(0x0102), None);
+    }
+
+    #[test]
+    fn test_inject_pmt() {
+        let (descriptors, _) = psi::desc::DescriptorBlock::read(&[0xF0, 0x00]).unwrap();
+        let pmt_stream = |pid, stream_type| psi::table::PmtStream {
+            stream_type,
+            elementary_pid: Pid::new(pid),
+            descriptors: descriptors.clone(),
+        };
+        let pmt = psi::table::Pmt {
+            program_number: ServiceId::new(1024).unwrap(),
+            pcr_pid: Pid::new(0x01FF),
+            descriptors: descriptors.clone(),
+            streams: vec![
+                pmt_stream(0x0110, psi::desc::StreamType::AAC),
+                pmt_stream(0x0100, psi::desc::StreamType::MPEG2_VIDEO),
+                // 字幕のコンポーネントタグが無いので無視される
+                pmt_stream(0x0130, psi::desc::StreamType::CAPTION),
+            ],
+        };
+
+        let mut sorter = Sorter::new(Counter::default());
+        inject_pmt(sorter.services_mut(), pmt);
+
+        let svc = &sorter.services()[&ServiceId::new(1024).unwrap()];
+        assert!(svc.pmt_filled());
+        assert_eq!(svc.pcr_pid(), Pid::new(0x01FF));
+        assert_eq!(
+            svc.video_streams()
+                .iter()
+                .map(|s| s.pid)
+                .collect::<Vec<_>>(),
+            [Pid::new(0x0100)],
+        );
+        assert_eq!(
+            svc.audio_streams()
+                .iter()
+                .map(|s| s.pid)
+                .collect::<Vec<_>>(),
+            [Pid::new(0x0110)],
+        );
+        assert!(svc.caption_stream().is_none());
+    }
+
+    #[test]
+    fn test_cat_updated() {
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0001, 0, CAT));
+        assert_eq!(demuxer.filter().shooter().ca_pids, [Pid::new(0x0080)]);
+
+        // 同じバージョンのCATは処理されない
+        demuxer.feed(&psi_packet(0x0001, 1, CAT));
+        assert_eq!(demuxer.filter().shooter().ca_pids.len(), 1);
+    }
+
+    #[test]
+    fn test_sdt_logo() {
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_SINGLE));
+        demuxer.feed(&psi_packet(0x0011, 0, SDT));
+
+        let service = &demuxer.filter().services()[&ServiceId::new(0x0400).unwrap()];
+        assert_eq!(service.logo_id(), Some(0x0005));
+        assert_eq!(service.logo_download_data_id(), Some(0x0021));
+    }
+
+    #[test]
+    fn test_sdt_service() {
+        let service_id = ServiceId::new(0x0400).unwrap();
+
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_SINGLE));
+
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.service_type(), None);
+        assert_eq!(service.service_running_status(), RunningStatus::Undefined);
+        assert!(!service.free_ca_mode());
+
+        demuxer.feed(&psi_packet(0x0011, 0, SDT));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.service_type(), Some(psi::desc::ServiceType(0x01)));
+        assert_eq!(service.service_running_status(), RunningStatus::Running);
+        assert!(!service.free_ca_mode());
+
+        demuxer.feed(&psi_packet(0x0011, 1, SDT_V1));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.service_running_status(), RunningStatus::NotRunning);
+        assert!(service.free_ca_mode());
+
+        // サービス記述子がなくなればサービス形式種別も消える
+        demuxer.feed(&psi_packet(0x0011, 2, SDT_V2));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.service_type(), None);
+        assert_eq!(service.service_running_status(), RunningStatus::Running);
+        assert_eq!(service.logo_id(), Some(0x0005));
+    }
+
+    #[test]
+    fn test_running_status() {
+        let service_id = ServiceId::new(0x0400).unwrap();
+
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_SINGLE));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.running_status(), RunningStatus::Undefined);
+
+        demuxer.feed(&psi_packet(0x0012, 0, EIT_PRESENT));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.running_status(), RunningStatus::Running);
+        assert_eq!(
+            service.present_event().unwrap().running_status,
+            RunningStatus::Running
+        );
+        assert_eq!(
+            demuxer.filter().shooter().running_statuses,
+            [RunningStatus::Running]
+        );
+
+        // 進行状態が変わらなければ通知されない
+        demuxer.feed(&psi_packet(0x0012, 1, EIT_PRESENT_V1));
+        assert_eq!(demuxer.filter().shooter().running_statuses.len(), 1);
+
+        demuxer.feed(&psi_packet(0x0012, 2, EIT_PRESENT_V2));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.running_status(), RunningStatus::NotRunning);
+        assert_eq!(
+            demuxer.filter().shooter().running_statuses,
+            [RunningStatus::Running, RunningStatus::NotRunning]
+        );
+
+        // 次のイベントの進行状態は反映されない
+        demuxer.feed(&psi_packet(0x0012, 3, EIT_FOLLOWING));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(
+            service.following_event().unwrap().running_status,
+            RunningStatus::StartsSoon
+        );
+        assert_eq!(service.running_status(), RunningStatus::NotRunning);
+        assert_eq!(demuxer.filter().shooter().running_statuses.len(), 2);
+
+        // PATより先に受信したEITの進行状態も反映される
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0012, 0, EIT_PRESENT));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_SINGLE));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.running_status(), RunningStatus::Running);
+    }
+
+    #[test]
+    fn test_event_group() {
+        let service_id = ServiceId::new(0x0400).unwrap();
+
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_SINGLE));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.relay_event(), None);
+        assert_eq!(service.movement_from(), None);
+
+        // イベントグループ記述子がなければリレーも移動もない
+        demuxer.feed(&psi_packet(0x0012, 0, EIT_PRESENT));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(service.relay_event(), None);
+        assert_eq!(service.movement_from(), None);
+
+        // イベント共有は無視される
+        demuxer.feed(&psi_packet(0x0012, 1, EIT_PRESENT_GROUP));
+        let service = &demuxer.filter().services()[&service_id];
+        assert_eq!(
+            service.relay_event(),
+            Some((
+                ServiceId::new(0x0408).unwrap(),
+                EventId::new(0x0010).unwrap()
+            )),
+        );
+        assert_eq!(
+            service.movement_from(),
+            Some((
+                ServiceId::new(0x0401).unwrap(),
+                EventId::new(0x0020).unwrap()
+            )),
+        );
+        let event = service.present_event().unwrap();
+        assert_eq!(event.relay_to, service.relay_event());
+        assert_eq!(event.movement_from, service.movement_from());
+
+        // 次のイベントは関係ない
+        demuxer.feed(&psi_packet(0x0012, 2, EIT_FOLLOWING));
+        let service = &demuxer.filter().services()[&service_id];
+        assert!(service.relay_event().is_some());
+        assert_eq!(service.following_event().unwrap().relay_to, None);
+    }
+
+    #[test]
+    fn test_nit_updated() {
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0010, 0, NIT));
+        assert_eq!(
+            demuxer.filter().shooter().nits,
+            [psi::table::NetworkId::new(0x0004).unwrap()],
+        );
+
+        // 同じバージョンのNITは処理されない
+        demuxer.feed(&psi_packet(0x0010, 1, NIT));
+        assert_eq!(demuxer.filter().shooter().nits.len(), 1);
+    }
+
+    #[test]
+    fn test_nit_pid_from_pat() {
+        let mut demuxer = demux::Demuxer::new(Sorter::new(Counter::default()));
+        demuxer.feed(&psi_packet(0x0000, 0, PAT_NIT_0011));
+
+        // 既定のPIDでは処理されない
+        demuxer.feed(&psi_packet(0x0010, 0, NIT));
+        assert!(demuxer.filter().shooter().nits.is_empty());
+
+        demuxer.feed(&psi_packet(0x0011, 0, NIT));
+        assert_eq!(
+            demuxer.filter().shooter().nits,
+            [psi::table::NetworkId::new(0x0004).unwrap()],
+        );
+    }
+
+    #[test]
+    fn test_service_iter_ordered() {
+        let mut services = ServiceMap::default();
+        for id in [1032, 1024, 1025] {
+            let service = service(id, b"");
+            services.insert(service.service_id, service);
+        }
+
+        let ids = |services: &ServiceMap| {
+            services
+                .iter_ordered()
+                .map(|(id, _)| id.get())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&services), [1024, 1025, 1032]);
+        assert_eq!(services.iter_ordered().len(), 3);
+        assert_eq!(
+            services.iter_ordered().next_back().map(|(id, _)| id.get()),
+            Some(1032),
+        );
+
+        let index = |id| services.get_ordered_index(ServiceId::new(id).unwrap());
+        assert_eq!(index(1024), Some(0));
+        assert_eq!(index(1025), Some(1));
+        assert_eq!(index(1032), Some(2));
+        assert_eq!(index(1040), None);
+
+        // 挿入順が変わっても順序は変わらない
+        let mut reordered = ServiceMap::default();
+        for (id, service) in services.iter().rev() {
+            reordered.insert(*id, service.clone());
+        }
+        assert_eq!(ids(&reordered), [1024, 1025, 1032]);
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        // １２３
+        let name = AribStr::from_bytes(b"\x23\x31\x23\x32\x23\x33");
+        assert_eq!(normalize_name(name), "123");
+        // ＡＢ　Ｃ
+        let name = AribStr::from_bytes(b"\x23\x41\x23\x42\x21\x21\x23\x43");
+        assert_eq!(normalize_name(name), "AB C");
+    }
+}
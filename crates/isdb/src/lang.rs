@@ -30,8 +30,134 @@ impl LangCode {
     pub const ETC: LangCode = LangCode(*b"etc");
 }
 
+impl LangCode {
+    /// 言語コードが示す言語を返す。
+    ///
+    /// 不明な言語コードの場合は`None`を返す。
+    #[inline]
+    pub fn language(&self) -> Option<Language> {
+        Language::from_bytes(self.0)
+    }
+}
+
 impl fmt::Display for LangCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.0.escape_ascii().fmt(f)
     }
 }
+
+/// 言語コードが示す言語。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    /// 日本語。
+    Japanese,
+    /// 英語。
+    English,
+    /// ドイツ語。
+    German,
+    /// フランス語。
+    French,
+    /// イタリア語。
+    Italian,
+    /// ロシア語。
+    Russian,
+    /// 中国語。
+    Chinese,
+    /// 韓国語。
+    Korean,
+    /// スペイン語。
+    Spanish,
+    /// ポルトガル語。
+    Portuguese,
+    /// 外国語。
+    Foreign,
+}
+
+impl Language {
+    /// ISO 639-2で規定される3文字の言語コードから言語を返す。
+    ///
+    /// 大文字・小文字は区別せず、書誌コード（`chi`等）と用語コード（`zho`等）のどちらも受け付ける。
+    /// 不明な言語コードの場合は`None`を返す。
+    pub fn from_bytes(code: [u8; 3]) -> Option<Language> {
+        let lang = match &code.map(|c| c.to_ascii_lowercase()) {
+            b"jpn" => Language::Japanese,
+            b"eng" => Language::English,
+            b"deu" | b"ger" => Language::German,
+            b"fra" | b"fre" => Language::French,
+            b"ita" => Language::Italian,
+            b"rus" => Language::Russian,
+            b"zho" | b"chi" => Language::Chinese,
+            b"kor" => Language::Korean,
+            b"spa" => Language::Spanish,
+            b"por" => Language::Portuguese,
+            b"etc" => Language::Foreign,
+            _ => return None,
+        };
+        Some(lang)
+    }
+
+    /// 言語の日本語名を返す。
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "英語",
+            Language::German => "ドイツ語",
+            Language::French => "フランス語",
+            Language::Italian => "イタリア語",
+            Language::Russian => "ロシア語",
+            Language::Chinese => "中国語",
+            Language::Korean => "韓国語",
+            Language::Spanish => "スペイン語",
+            Language::Portuguese => "ポルトガル語",
+            Language::Foreign => "外国語",
+        }
+    }
+
+    /// 言語の英語名を返す。
+    pub fn english_name(&self) -> &'static str {
+        match self {
+            Language::Japanese => "Japanese",
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Italian => "Italian",
+            Language::Russian => "Russian",
+            Language::Chinese => "Chinese",
+            Language::Korean => "Korean",
+            Language::Spanish => "Spanish",
+            Language::Portuguese => "Portuguese",
+            Language::Foreign => "Foreign",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_bytes() {
+        let lookup = |code: &[u8; 3]| Language::from_bytes(*code);
+
+        assert_eq!(lookup(b"jpn"), Some(Language::Japanese));
+        assert_eq!(lookup(b"eng"), Some(Language::English));
+        assert_eq!(lookup(b"chi"), Some(Language::Chinese));
+        assert_eq!(lookup(b"zho"), Some(Language::Chinese));
+        assert_eq!(lookup(b"kor"), Some(Language::Korean));
+        assert_eq!(lookup(b"por"), Some(Language::Portuguese));
+        assert_eq!(lookup(b"spa"), Some(Language::Spanish));
+        assert_eq!(lookup(b"JPN"), Some(Language::Japanese));
+        assert_eq!(lookup(b"xxx"), None);
+
+        assert_eq!(LangCode::DEU.language(), Some(Language::German));
+        assert_eq!(LangCode::ETC.language(), Some(Language::Foreign));
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(Language::Japanese.native_name(), "日本語");
+        assert_eq!(Language::Japanese.english_name(), "Japanese");
+        assert_eq!(Language::Chinese.native_name(), "中国語");
+        assert_eq!(Language::Korean.english_name(), "Korean");
+    }
+}
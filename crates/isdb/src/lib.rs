@@ -15,6 +15,7 @@ pub mod pid;
 pub mod psi;
 pub mod time;
 mod utils;
+pub mod write;
 
 pub use eight::str::{AribStr, AribString};
 pub use packet::Packet;
@@ -1,19 +1,77 @@
 //! MPEG2-TSのパケット。
 
 use std::fmt;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
+
+use thiserror::Error;
 
 use crate::pid::Pid;
 use crate::time::Timestamp;
+use crate::utils::BytesExt;
 
 const SYNC_BYTE: u8 = 0x47;
 const PACKET_SIZE: usize = 188;
+/// [`Packet::from_bytes`]で不正とする未定義のPID範囲。
+///
+/// `0x0002`（TSDT）と`0x0003`（IPMP CIT）はISO 13818-1で定義されているため含まない。
+const UNDEFINED_PIDS: std::ops::RangeInclusive<u16> = 0x0004..=0x000F;
 
 /// MPEG2-TSのパケット。
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Packet(pub [u8; PACKET_SIZE]);
 
+/// [`Packet::from_bytes`]や[`Packet::from_slice`]で発生するエラー。
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum PacketError {
+    /// バイト列の長さが188バイトに満たない。
+    #[error("insufficient length of a TS packet")]
+    InsufficientLength,
+
+    /// 同期バイトが`0x47`ではない。
+    #[error("invalid sync byte: {0:#04X}")]
+    InvalidSyncByte(u8),
+
+    /// PIDが未定義の範囲（`0x0004`～`0x000F`）にある。
+    #[error("undefined pid: {0:?}")]
+    UndefinedPid(Pid),
+}
+
 impl Packet {
+    /// ヌルパケット（PIDが`0x1FFF`のパケット）を生成する。
+    ///
+    /// アダプテーションフィールドを持たず、ペイロードは全て`0xFF`で埋められる。
+    #[must_use]
+    pub fn null() -> Packet {
+        let mut packet = Packet([0xFF; PACKET_SIZE]);
+        packet.0[..4].copy_from_slice(&[SYNC_BYTE, 0x1F, 0xFF, 0x10]);
+        packet
+    }
+
+    /// 188バイトのバイト列からTSパケットを生成する。
+    ///
+    /// 同期バイトが不正な場合や、PIDが未定義の範囲にある場合はエラーを返す。
+    pub fn from_bytes(buf: [u8; PACKET_SIZE]) -> Result<Packet, PacketError> {
+        let packet = Packet(buf);
+        if packet.sync_byte() != SYNC_BYTE {
+            return Err(PacketError::InvalidSyncByte(packet.sync_byte()));
+        }
+        if UNDEFINED_PIDS.contains(&packet.pid().get()) {
+            return Err(PacketError::UndefinedPid(packet.pid()));
+        }
+        Ok(packet)
+    }
+
+    /// バイト列の先頭188バイトをコピーしてTSパケットを生成する。
+    ///
+    /// `buf`が188バイトに満たない場合はエラーを返す。
+    /// その他のエラーについては[`Packet::from_bytes`]と同様。
+    pub fn from_slice(buf: &[u8]) -> Result<Packet, PacketError> {
+        let buf = buf
+            .get(..PACKET_SIZE)
+            .ok_or(PacketError::InsufficientLength)?;
+        Packet::from_bytes(buf.try_into().unwrap())
+    }
+
     /// `r`からTSパケットを順次読み込むイテレーターを生成する。
     ///
     /// # サンプル
@@ -93,6 +151,27 @@ impl Packet {
         }
     }
 
+    /// TSパケットを188バイトのバイト列として`w`に書き込む。
+    ///
+    /// 連続性指標やアダプテーションフィールドを含め、パケットの内容はそのまま書き込まれる。
+    #[inline]
+    pub fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.0)
+    }
+
+    /// TSパケットを188バイトのバイト列に変換する。
+    #[inline]
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; PACKET_SIZE] {
+        self.0
+    }
+
+    /// ヌルパケットかどうかを返す。
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.pid() == Pid::NULL
+    }
+
     /// パケットが正常かどうかを返す。
     ///
     /// 同期バイトやトランスポートエラーインジケーターによるエラー検知に加え、
@@ -166,6 +245,12 @@ impl Packet {
         (self.0[3] & 0b11000000) >> 6
     }
 
+    /// トランスポートスクランブル制御を[`TransportScramblingControl`]として返す。
+    #[inline]
+    pub fn transport_scrambling_control(&self) -> TransportScramblingControl {
+        TransportScramblingControl(self.scrambling_control())
+    }
+
     /// パケットがスクランブル処理されているかを返す。
     #[inline]
     pub fn is_scrambled(&self) -> bool {
@@ -184,6 +269,17 @@ impl Packet {
         self.0[3] & 0b00001111
     }
 
+    /// 連続性指標（4ビット）を設定する。
+    ///
+    /// # パニック
+    ///
+    /// `cc`が15より大きい場合、このメソッドはパニックする。
+    #[inline]
+    pub fn set_continuity_counter(&mut self, cc: u8) {
+        assert!(cc <= 0x0F, "連続性指標は4ビット");
+        self.0[3] = (self.0[3] & 0b11110000) | cc;
+    }
+
     /// パケットがアダプテーションフィールドを含むかどうかを返す。
     #[inline]
     pub fn has_adaptation_field(&self) -> bool {
@@ -208,6 +304,12 @@ impl Packet {
         AdaptationField::new(self)
     }
 
+    /// アダプテーションフィールド内のトランスポートプライベートデータを返す。
+    #[inline]
+    pub fn transport_private_data(&self) -> Option<&[u8]> {
+        self.adaptation_field()?.transport_private_data()
+    }
+
     /// パケットがペイロードを含むかどうかを返す。
     #[inline]
     pub fn has_payload(&self) -> bool {
@@ -265,6 +367,34 @@ impl fmt::Debug for Packet {
     }
 }
 
+/// トランスポートスクランブル制御（2ビット）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransportScramblingControl(pub u8);
+
+impl TransportScramblingControl {
+    /// スクランブルなし。
+    pub const NOT_SCRAMBLED: TransportScramblingControl = TransportScramblingControl(0b00);
+    /// 未定義。
+    pub const UNDEFINED: TransportScramblingControl = TransportScramblingControl(0b01);
+    /// 偶数鍵でスクランブル。
+    pub const EVEN_KEY: TransportScramblingControl = TransportScramblingControl(0b10);
+    /// 奇数鍵でスクランブル。
+    pub const ODD_KEY: TransportScramblingControl = TransportScramblingControl(0b11);
+
+    /// スクランブル処理されているかを返す。
+    #[inline]
+    pub fn is_scrambled(&self) -> bool {
+        self.0 & 0b10 != 0
+    }
+}
+
+impl Default for TransportScramblingControl {
+    #[inline]
+    fn default() -> Self {
+        TransportScramblingControl::NOT_SCRAMBLED
+    }
+}
+
 /// TSパケット内のアダプテーションフィールド。
 #[derive(Debug)]
 pub struct AdaptationField<'a>(&'a [u8]);
@@ -410,10 +540,27 @@ impl<'a> AdaptationField<'a> {
     }
 
     /// スプライスカウントダウンを返す。
-    pub fn splice_countdown(&self) -> Option<u8> {
+    ///
+    /// 値は符号付きであり、編集点を過ぎたパケットでは負数となる。
+    pub fn splice_countdown(&self) -> Option<i8> {
         self.splice_countdown_offset()
             .and_then(|offset| self.0.get(offset))
-            .copied()
+            .map(|&v| v as i8)
+    }
+
+    fn extension_offset(&self) -> Option<usize> {
+        if !self.extension_flag() {
+            None
+        } else if let Some(offset) = self.private_data_offset() {
+            let len = *self.0.get(offset)?;
+            Some(offset + 1 + len as usize)
+        } else {
+            Some(
+                1 + if self.pcr_flag() { 6 } else { 0 }
+                    + if self.original_pcr_flag() { 6 } else { 0 }
+                    + if self.splicing_point_flag() { 1 } else { 0 },
+            )
+        }
     }
 
     /// プライベートデータを返す。
@@ -422,6 +569,102 @@ impl<'a> AdaptationField<'a> {
         let len = *self.0.get(offset)?;
         self.0.get(offset + len as usize..)
     }
+
+    /// トランスポートプライベートデータを返す。
+    ///
+    /// 戻り値には`transport_private_data_length`は含まれない。
+    pub fn transport_private_data(&self) -> Option<&'a [u8]> {
+        let offset = self.private_data_offset()?;
+        let len = *self.0.get(offset)?;
+        self.0.get(offset + 1..offset + 1 + len as usize)
+    }
+
+    /// アダプテーションフィールド拡張を返す。
+    ///
+    /// 戻り値には`adaptation_field_extension_length`は含まれない。
+    pub fn extension(&self) -> Option<&'a [u8]> {
+        let offset = self.extension_offset()?;
+        let len = *self.0.get(offset)?;
+        self.0.get(offset + 1..offset + 1 + len as usize)
+    }
+
+    /// アダプテーションフィールド拡張を解析して返す。
+    #[inline]
+    pub fn adaptation_field_extension(&self) -> Option<AdaptationFieldExtension<'a>> {
+        self.extension().and_then(AdaptationFieldExtension::new)
+    }
+}
+
+/// アダプテーションフィールド拡張。
+#[derive(Debug)]
+pub struct AdaptationFieldExtension<'a>(&'a [u8]);
+
+impl<'a> AdaptationFieldExtension<'a> {
+    #[inline]
+    fn new(data: &'a [u8]) -> Option<AdaptationFieldExtension<'a>> {
+        if data.is_empty() {
+            None
+        } else {
+            Some(AdaptationFieldExtension(data))
+        }
+    }
+
+    /// LTWフラグを返す。
+    #[inline]
+    pub fn ltw_flag(&self) -> bool {
+        // Safety: 生成時に確認済み
+        unsafe { crate::utils::assume!(!self.0.is_empty()) }
+
+        self.0[0] & 0b10000000 != 0
+    }
+
+    /// 区分レートフラグを返す。
+    #[inline]
+    pub fn piecewise_rate_flag(&self) -> bool {
+        // Safety: 生成時に確認済み
+        unsafe { crate::utils::assume!(!self.0.is_empty()) }
+
+        self.0[0] & 0b01000000 != 0
+    }
+
+    /// シームレス編集フラグを返す。
+    #[inline]
+    pub fn seamless_splice_flag(&self) -> bool {
+        // Safety: 生成時に確認済み
+        unsafe { crate::utils::assume!(!self.0.is_empty()) }
+
+        self.0[0] & 0b00100000 != 0
+    }
+
+    fn piecewise_rate_offset(&self) -> Option<usize> {
+        if !self.piecewise_rate_flag() {
+            None
+        } else {
+            Some(1 + if self.ltw_flag() { 2 } else { 0 })
+        }
+    }
+
+    /// LTWオフセットを返す。
+    ///
+    /// `ltw_valid_flag`が立っていない場合は`None`を返す。
+    pub fn ltw_offset(&self) -> Option<u16> {
+        if !self.ltw_flag() {
+            return None;
+        }
+
+        let ltw = self.0.get(1..=2)?.read_be_16();
+        if ltw & 0x8000 == 0 {
+            return None;
+        }
+        Some(ltw & 0x7FFF)
+    }
+
+    /// 区分レートを返す。
+    pub fn piecewise_rate(&self) -> Option<u32> {
+        let offset = self.piecewise_rate_offset()?;
+        let data = self.0.get(offset..offset + 3)?;
+        Some(((data[0] as u32 & 0b00111111) << 16) | ((data[1] as u32) << 8) | data[2] as u32)
+    }
 }
 
 /// [`Packet::iter`]から返される。TSパケットを順次読み込むイテレーター。
@@ -516,6 +759,91 @@ FF FF FF FF FF FF FF FF FF FF FF FF
         }
     }
 
+    #[test]
+    fn test_packet_write_to() {
+        for packet in [PACKET_1, PACKET_2, PACKET_3] {
+            let mut buf = Vec::new();
+            packet.write_to(&mut buf).unwrap();
+            assert_eq!(buf, packet.to_bytes());
+            assert_eq!(Packet::read(&mut &*buf).unwrap(), Some(packet.clone()));
+
+            let mut buf = Vec::new();
+            for _ in 0..3 {
+                packet.write_to(&mut buf).unwrap();
+            }
+            let packets: Vec<_> = Packet::iter(&*buf).collect::<io::Result<_>>().unwrap();
+            assert_eq!(packets, [packet.clone(), packet.clone(), packet.clone()]);
+
+            // トレイトオブジェクトにも書き込める
+            let mut buf = Vec::new();
+            let w: &mut dyn Write = &mut buf;
+            packet.write_to(w).unwrap();
+            assert_eq!(buf, packet.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_packet_null() {
+        let packet = Packet::null();
+        assert!(packet.is_normal());
+        assert!(packet.is_null());
+        assert_eq!(packet.pid(), Pid::NULL);
+        assert!(!packet.has_adaptation_field());
+        assert_eq!(packet.continuity_counter(), 0);
+        assert_eq!(packet.payload(), Some(&[0xFF; PACKET_SIZE - 4][..]));
+
+        for packet in [PACKET_1, PACKET_2, PACKET_3] {
+            assert!(!packet.is_null());
+        }
+    }
+
+    #[test]
+    fn test_packet_from_bytes() {
+        for packet in [PACKET_1, PACKET_2, PACKET_3] {
+            assert_eq!(Packet::from_bytes(packet.0), Ok(packet));
+        }
+
+        let mut buf = PACKET_1.0;
+        buf[0] = 0x48;
+        assert_eq!(
+            Packet::from_bytes(buf),
+            Err(PacketError::InvalidSyncByte(0x48)),
+        );
+
+        for pid in [0x0004, 0x0005, 0x000F] {
+            let mut buf = PACKET_1.0;
+            buf[1] = 0x00;
+            buf[2] = pid;
+            assert_eq!(
+                Packet::from_bytes(buf),
+                Err(PacketError::UndefinedPid(Pid::new(pid as u16))),
+            );
+        }
+
+        // TSDTとIPMP CITは定義済み
+        for pid in [0x0002, 0x0003] {
+            let mut buf = PACKET_1.0;
+            buf[1] = 0x00;
+            buf[2] = pid;
+            assert_eq!(
+                Packet::from_bytes(buf).map(|p| p.pid()),
+                Ok(Pid::new(pid as u16)),
+            );
+        }
+    }
+
+    #[test]
+    fn test_packet_from_slice() {
+        let mut buf = PACKET_2.0.to_vec();
+        buf.extend_from_slice(&PACKET_3.0);
+        assert_eq!(Packet::from_slice(&buf), Ok(PACKET_2));
+        assert_eq!(Packet::from_slice(&buf[PACKET_SIZE..]), Ok(PACKET_3));
+        assert_eq!(
+            Packet::from_slice(&buf[..PACKET_SIZE - 1]),
+            Err(PacketError::InsufficientLength),
+        );
+    }
+
     #[test]
     fn test_packet_read_err() {
         struct ReadErr(io::ErrorKind);
@@ -646,6 +974,95 @@ FF FF FF FF FF FF FF FF FF FF FF FF
         assert_eq!(PACKET_3.payload(), None);
     }
 
+    #[test]
+    fn test_packet_set_continuity_counter() {
+        let mut packet = PACKET_2;
+        packet.set_continuity_counter(15);
+        assert_eq!(packet.continuity_counter(), 15);
+        assert_eq!(packet.adaptation_field_control(), 0b11);
+        assert_eq!(packet.scrambling_control(), 0b00);
+        assert_eq!(packet.0[..3], PACKET_2.0[..3]);
+        assert_eq!(packet.0[4..], PACKET_2.0[4..]);
+
+        packet.set_continuity_counter(0);
+        assert_eq!(packet.continuity_counter(), 0);
+        assert_eq!(packet.adaptation_field_control(), 0b11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_packet_set_continuity_counter_overflow() {
+        let mut packet = PACKET_1;
+        packet.set_continuity_counter(16);
+    }
+
+    #[test]
+    fn test_packet_private_data() {
+        let mut packet = Packet([0xFF; 188]);
+        packet.0[..4].copy_from_slice(&[SYNC_BYTE, 0x01, 0x00, 0b00100000]);
+        packet.0[4] = 183;
+        // 編集点・プライベートデータ・拡張フラグ
+        packet.0[5] = 0b00000111;
+        // スプライスカウントダウン
+        packet.0[6] = 0x05;
+        // トランスポートプライベートデータ
+        packet.0[7..11].copy_from_slice(&[3, 0xAA, 0xBB, 0xCC]);
+        // アダプテーションフィールド拡張
+        packet.0[11..14].copy_from_slice(&[2, 0x00, 0x11]);
+
+        let af = packet.adaptation_field().unwrap();
+        assert!(af.pcr().is_none());
+        assert_eq!(af.splice_countdown(), Some(0x05));
+        assert_eq!(af.transport_private_data(), Some(&[0xAA, 0xBB, 0xCC][..]));
+        assert_eq!(af.extension(), Some(&[0x00, 0x11][..]));
+        let afe = af.adaptation_field_extension().unwrap();
+        assert!(!afe.ltw_flag());
+        assert!(!afe.piecewise_rate_flag());
+        assert!(!afe.seamless_splice_flag());
+        assert_eq!(
+            packet.transport_private_data(),
+            Some(&[0xAA, 0xBB, 0xCC][..])
+        );
+
+        assert_eq!(PACKET_1.transport_private_data(), None);
+        let af = PACKET_3.adaptation_field().unwrap();
+        assert!(af.transport_private_data().is_none());
+        assert!(af.extension().is_none());
+    }
+
+    #[test]
+    fn test_packet_adaptation_field_extension() {
+        let mut packet = Packet([0xFF; 188]);
+        packet.0[..4].copy_from_slice(&[SYNC_BYTE, 0x01, 0x00, 0b00100000]);
+        packet.0[4] = 183;
+        // 編集点・拡張フラグ
+        packet.0[5] = 0b00000101;
+        // スプライスカウントダウン
+        packet.0[6] = 0xFE;
+        // アダプテーションフィールド拡張
+        packet.0[7..19].copy_from_slice(&[
+            11, 0b11111111, // LTW
+            0x81, 0x23, // 区分レート
+            0xC1, 0x23, 0x45, // シームレス編集
+            0x21, 0x00, 0x01, 0x00, 0x01,
+        ]);
+
+        let af = packet.adaptation_field().unwrap();
+        assert_eq!(af.splice_countdown(), Some(-2));
+        let afe = af.adaptation_field_extension().unwrap();
+        assert!(afe.ltw_flag());
+        assert!(afe.piecewise_rate_flag());
+        assert!(afe.seamless_splice_flag());
+        assert_eq!(afe.ltw_offset(), Some(0x0123));
+        assert_eq!(afe.piecewise_rate(), Some(0x012345));
+
+        assert!(PACKET_3
+            .adaptation_field()
+            .unwrap()
+            .adaptation_field_extension()
+            .is_none());
+    }
+
     #[test]
     fn test_packet_iter() {
         let data = [PACKET_1.0, PACKET_2.0, PACKET_3.0].concat();
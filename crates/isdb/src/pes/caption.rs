@@ -1,7 +1,7 @@
 //! ARIB STD-B24第一編で規定される字幕に関する定義。
 
 use crate::eight::char::TimeControlMode;
-use crate::eight::str::AribStr;
+use crate::eight::str::{AribStr, AribString};
 use crate::lang::LangCode;
 use crate::utils::{BytesExt, SliceExt};
 
@@ -47,6 +47,16 @@ impl<'a> DataGroup<'a> {
             data_group_data,
         })
     }
+
+    /// 字幕文データの言語識別を返す。
+    ///
+    /// このデータグループが字幕管理データや不明なデータの場合は`None`を返す。
+    pub fn language_tag(&self) -> Option<LanguageTag> {
+        match self.data_group_id & 0x0F {
+            tag @ 1..=8 => Some(LanguageTag(tag - 1)),
+            _ => None,
+        }
+    }
 }
 
 /// `value`から`TimeControlMode`を生成する。
@@ -359,6 +369,23 @@ impl<'a> CaptionData<'a> {
             data_units,
         })
     }
+
+    /// データユニットのうち、字幕本文を順に返すイテレーターを返す。
+    pub fn statement_bodies(&self) -> impl Iterator<Item = &'a AribStr> + '_ {
+        self.data_units.iter().filter_map(|unit| match unit {
+            DataUnit::StatementBody(body) => Some(*body),
+            _ => None,
+        })
+    }
+
+    /// すべての字幕本文を連結した文字列を返す。
+    pub fn text(&self) -> AribString {
+        let mut text = AribString::new();
+        for body in self.statement_bodies() {
+            text.push_str(body);
+        }
+        text
+    }
 }
 
 /// データユニット。
@@ -771,6 +798,38 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_caption_data() {
+        const DATA_GROUP: &[u8] = &hex_literal::hex!(
+            "
+04 00 00 00 1B 3F 00 00 17 1F 20 00 00 04 0C 24
+22 24 1F 28 00 00 02 00 00 1F 20 00 00 02 24 24
+"
+        );
+
+        let data_group = DataGroup::read(DATA_GROUP).unwrap();
+        assert_eq!(data_group.data_group_id, 0x01);
+        assert_eq!(data_group.language_tag(), Some(LanguageTag(0)));
+
+        let data = CaptionData::read(data_group.data_group_data).unwrap();
+        assert_eq!(data.tmd, TimeControlMode::Free);
+        assert_eq!(data.stm, None);
+        assert_eq!(data.data_units.len(), 3);
+        assert_matches!(data.data_units[1], DataUnit::Geometric(&[0x00, 0x00]));
+        assert_eq!(
+            data.statement_bodies().collect::<Vec<_>>(),
+            [
+                AribStr::from_bytes(b"\x0C\x24\x22\x24"),
+                AribStr::from_bytes(b"\x24\x24"),
+            ],
+        );
+        assert_eq!(data.text().as_bytes(), b"\x0C\x24\x22\x24\x24\x24",);
+
+        // 字幕管理データには言語識別が無い
+        let data_group = DataGroup::read(&[0x00, 0x00, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(data_group.language_tag(), None);
+    }
+
     #[test]
     fn test_drcs() {
         /// ■と□からなる文字列を、1を■、0を□としたビット列としてパースする。
@@ -6,6 +6,7 @@ use std::num::NonZeroU32;
 
 use thiserror::Error;
 
+use crate::crc;
 use crate::time::Timestamp;
 use crate::utils::{BytesExt, SliceExt};
 
@@ -172,14 +173,14 @@ impl<'a> PesPacket<'a> {
             }
 
             let (pts, dts) = if pts_dts_flags == 0b10 {
-                if data.len() < 9 + 5 {
+                if pes_header_data_length < 5 {
                     return Err(PesError::Corrupted);
                 }
 
                 let pts = Timestamp::read_pts(data[9..=13].try_into().unwrap());
                 (pts, pts)
             } else if pts_dts_flags == 0b11 {
-                if data.len() < 9 + 5 + 5 {
+                if pes_header_data_length < 5 + 5 {
                     return Err(PesError::Corrupted);
                 }
 
@@ -190,6 +191,26 @@ impl<'a> PesPacket<'a> {
                 (None, None)
             };
 
+            let previous_pes_packet_crc = if pes_crc_flag {
+                // PTS・DTSの後にESCR、ES_rate、trick_mode_control、additional_copy_infoが続く
+                let pos =
+                    9 + match pts_dts_flags {
+                        0b10 => 5,
+                        0b11 => 10,
+                        _ => 0,
+                    } + if escr_flag { 6 } else { 0 }
+                        + if es_rate_flag { 3 } else { 0 }
+                        + if dsm_trick_mode_flag { 1 } else { 0 }
+                        + if additional_copy_info_flag { 1 } else { 0 };
+                if mid < pos + 2 {
+                    return Err(PesError::Corrupted);
+                }
+
+                Some(data[pos..pos + 2].read_be_16())
+            } else {
+                None
+            };
+
             let option = PesHeaderOption {
                 pes_scrambling_control,
                 pes_priority,
@@ -205,6 +226,7 @@ impl<'a> PesPacket<'a> {
                 pes_header_data_length,
                 pts,
                 dts,
+                previous_pes_packet_crc,
             };
 
             (Some(option), mid)
@@ -215,8 +237,6 @@ impl<'a> PesPacket<'a> {
         // `data.len() < mid`であることは確認済み
         let (header_data, data) = data.split_at(mid);
 
-        // TODO: crc16をチェック
-
         let header = PesHeader { stream_id, option };
 
         Ok(PesPacket {
@@ -226,6 +246,29 @@ impl<'a> PesPacket<'a> {
         })
     }
 
+    /// `previous`を直前のPESパケットとして、このパケットが持つCRC16を確認する。
+    ///
+    /// PESパケットのCRCフィールドは直前のPESパケットのデータを対象とするため、
+    /// パース時には確認されない。
+    /// CRCフィールドが存在しない場合は常に`Ok(())`を返す。
+    pub fn verify_previous_crc(&self, previous: &PesPacket) -> Result<(), PesError> {
+        let Some(crc) = self
+            .header
+            .option
+            .as_ref()
+            .and_then(|option| option.previous_pes_packet_crc)
+        else {
+            return Ok(());
+        };
+
+        let digest = crc::digest16(crc::INIT16, previous.data);
+        if crc::digest16(digest, &crc.to_be_bytes()) != crc::CRC16_RESIDUE {
+            return Err(PesError::Crc16);
+        }
+
+        Ok(())
+    }
+
     /// PESパケットをパースして[`PesPacket`]として返す。
     pub fn parse(data: &'a [u8]) -> Result<PesPacket<'a>, PesError> {
         if data.len() < 6 {
@@ -277,6 +320,10 @@ pub struct PesHeaderOption {
     pub pts: Option<Timestamp>,
     /// DTS（Decoding Time Stamp）。
     pub dts: Option<Timestamp>,
+    /// 直前のPESパケットのデータに対するCRC16。
+    ///
+    /// previous_PES_packet_CRC。
+    pub previous_pes_packet_crc: Option<u16>,
 }
 
 /// 同期型PES・非同期型に共通するPESデータ。
@@ -352,3 +399,65 @@ impl<'a> IndependentPes<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn test_pes_pts_dts() {
+        // PTSのみ
+        let data = hex_literal::hex!("00 00 01 E0 00 00 84 80 05 29 8D 15 CF 13 FF");
+        let packet = PesPacket::parse(&data).unwrap();
+        let option = packet.header.option.unwrap();
+        assert_eq!(option.pts, Some(Timestamp::new(0x1_2345_6789, 0)));
+        assert_eq!(option.dts, option.pts);
+        assert_eq!(packet.data, [0xFF]);
+
+        // PTSとDTS
+        let data = hex_literal::hex!("00 00 01 E0 00 00 84 C0 0A 39 8D 15 CF 13 19 8D 15 00 01 FF");
+        let packet = PesPacket::parse(&data).unwrap();
+        let option = packet.header.option.unwrap();
+        assert_eq!(option.pts, Some(Timestamp::new(0x1_2345_6789, 0)));
+        assert_eq!(option.dts, Some(Timestamp::new(0x1_2345_0000, 0)));
+        assert_eq!(packet.data, [0xFF]);
+
+        // マーカービットが不正
+        let data = hex_literal::hex!("00 00 01 E0 00 00 84 80 05 29 8D 15 CF 12 FF");
+        let packet = PesPacket::parse(&data).unwrap();
+        assert_eq!(packet.header.option.unwrap().pts, None);
+
+        // ヘッダデータ長がPTSに足りない
+        let data = hex_literal::hex!("00 00 01 E0 00 00 84 80 04 29 8D 15 CF 13");
+        assert_matches!(PesPacket::parse(&data), Err(PesError::Corrupted));
+    }
+
+    #[test]
+    fn test_pes_crc16() {
+        let previous = hex_literal::hex!("00 00 01 E0 00 00 80 00 00 31 32 33 34 35 36 37 38 39");
+        let previous = PesPacket::parse(&previous).unwrap();
+        assert_eq!(previous.data, b"123456789");
+
+        // PTSの後にCRC
+        let data = hex_literal::hex!("00 00 01 E0 00 00 84 82 07 29 8D 15 CF 13 29 B1 FF");
+        let packet = PesPacket::parse(&data).unwrap();
+        let option = packet.header.option.as_ref().unwrap();
+        assert_eq!(option.pts, Some(Timestamp::new(0x1_2345_6789, 0)));
+        assert_eq!(option.previous_pes_packet_crc, Some(0x29B1));
+        assert_eq!(packet.data, [0xFF]);
+        assert_matches!(packet.verify_previous_crc(&previous), Ok(()));
+
+        // CRCが一致しない
+        let data = hex_literal::hex!("00 00 01 E0 00 00 80 02 02 29 B2 FF");
+        let packet = PesPacket::parse(&data).unwrap();
+        assert_matches!(packet.verify_previous_crc(&previous), Err(PesError::Crc16));
+
+        // CRCフィールドが無い
+        assert_matches!(previous.verify_previous_crc(&packet), Ok(()));
+
+        // ヘッダデータ長がCRCに足りない
+        let data = hex_literal::hex!("00 00 01 E0 00 00 80 02 01 29");
+        assert_matches!(PesPacket::parse(&data), Err(PesError::Corrupted));
+    }
+}
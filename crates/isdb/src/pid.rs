@@ -1,7 +1,11 @@
 //! PID関連。
 
 use std::fmt;
+use std::num::ParseIntError;
 use std::ops;
+use std::str::FromStr;
+
+use thiserror::Error;
 
 use crate::utils::BytesExt;
 
@@ -136,7 +140,60 @@ impl fmt::Debug for Pid {
     }
 }
 
-crate::utils::delegate_fmt!(Pid);
+/// `0x1FFF (8191)`のように16進数と10進数を併記する。
+impl fmt::Display for Pid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // 幅指定などを反映させるため一度文字列にする
+        f.pad(&format!("0x{:04X} ({})", self.0, self.0))
+    }
+}
+
+crate::utils::delegate_radix_fmt!(Pid);
+
+/// 文字列から[`Pid`]への変換時に発生するエラー。
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParsePidError {
+    /// 数値として解釈できない。
+    #[error("invalid PID: {0}")]
+    Invalid(#[from] ParseIntError),
+
+    /// PIDとして範囲外。
+    #[error("PID out of range")]
+    OutOfRange,
+}
+
+impl FromStr for Pid {
+    type Err = ParsePidError;
+
+    /// `0x1FFF`のような16進数、または`8191`のような10進数の文字列から`Pid`を生成する。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pid = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16)?,
+            None => s.parse()?,
+        };
+        Pid::try_new(pid).ok_or(ParsePidError::OutOfRange)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Pid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pid = u16::deserialize(deserializer)?;
+        Pid::try_new(pid).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(pid as u64),
+                &"a PID in range 0..=0x1FFF",
+            )
+        })
+    }
+}
 
 /// [`Pid`]をキーにして値`V`にアクセスができるテーブル。
 ///
@@ -279,6 +336,7 @@ impl<V> std::borrow::BorrowMut<[V]> for PidTable<V> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_matches::assert_matches;
 
     #[test]
     fn test_pid() {
@@ -287,6 +345,18 @@ mod tests {
         assert_eq!(Pid::try_new(0x1FFF), Some(Pid::NULL));
         assert_eq!(Pid::try_new(0x2000), None);
 
+        assert_eq!("0".parse(), Ok(Pid::PAT));
+        assert_eq!("8191".parse(), Ok(Pid::NULL));
+        assert_eq!("0x1FFF".parse(), Ok(Pid::NULL));
+        assert_eq!("0X1fff".parse(), Ok(Pid::NULL));
+        assert_eq!("0x0011".parse(), Ok(Pid::SDT));
+        assert_eq!("8192".parse::<Pid>(), Err(ParsePidError::OutOfRange));
+        assert_eq!("0x2000".parse::<Pid>(), Err(ParsePidError::OutOfRange));
+        assert_matches!("".parse::<Pid>(), Err(ParsePidError::Invalid(_)));
+        assert_matches!("0x".parse::<Pid>(), Err(ParsePidError::Invalid(_)));
+        assert_matches!("1FFF".parse::<Pid>(), Err(ParsePidError::Invalid(_)));
+        assert_matches!("-1".parse::<Pid>(), Err(ParsePidError::Invalid(_)));
+
         std::panic::catch_unwind(|| Pid::read(&[])).unwrap_err();
         std::panic::catch_unwind(|| Pid::read(&[0x00])).unwrap_err();
         assert_eq!(Pid::read(&u16::to_be_bytes(0x0000)), Pid::new(0x0000));
@@ -307,10 +377,12 @@ mod tests {
         assert_eq!(Pid::NULL.get(), 0x1FFF);
         assert_eq!(u16::from(Pid::NULL), 0x1FFF);
 
-        assert_eq!(format!("{}", Pid::PAT), "0");
-        assert_eq!(format!("{:4}", Pid::PAT), "   0");
-        assert_eq!(format!("{}", Pid::NULL), "8191");
-        assert_eq!(format!("{:4}", Pid::NULL), "8191");
+        assert_eq!(format!("{}", Pid::PAT), "0x0000 (0)");
+        assert_eq!(format!("{:12}", Pid::PAT), "0x0000 (0)  ");
+        assert_eq!(format!("{:>12}", Pid::PAT), "  0x0000 (0)");
+        assert_eq!(format!("{}", Pid::NIT), "0x0010 (16)");
+        assert_eq!(format!("{}", Pid::NULL), "0x1FFF (8191)");
+        assert_eq!(format!("{:4}", Pid::NULL), "0x1FFF (8191)");
 
         assert_eq!(format!("{:b}", Pid::PAT), "0");
         assert_eq!(format!("{:13b}", Pid::PAT), "            0");
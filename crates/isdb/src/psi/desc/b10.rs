@@ -127,6 +127,83 @@ impl StreamType {
                 | StreamType::DOLBY_DIGITAL_PLUS
         )
     }
+
+    /// ストリーム形式が字幕・文字スーパーを示す場合に`true`を返す。
+    #[inline]
+    pub fn is_caption(&self) -> bool {
+        *self == StreamType::CAPTION
+    }
+
+    /// ストリーム形式とその名前の一覧。
+    const NAMES: &'static [(StreamType, &'static str)] = &[
+        (StreamType::MPEG1_VIDEO, "MPEG-1 video"),
+        (StreamType::MPEG2_VIDEO, "MPEG-2 video"),
+        (StreamType::MPEG1_AUDIO, "MPEG-1 audio"),
+        (StreamType::MPEG2_AUDIO, "MPEG-2 audio"),
+        (StreamType::PRIVATE_SECTIONS, "private sections"),
+        // ARIBでは字幕・文字スーパーに使われる
+        (StreamType::PRIVATE_DATA, "ARIB caption"),
+        (StreamType::MHEG, "MHEG"),
+        (StreamType::DSM_CC, "DSM-CC"),
+        (StreamType::ITU_T_REC_H222_1, "H.222.1"),
+        (StreamType::ISO_IEC_13818_6_TYPE_A, "DSM-CC type A"),
+        (StreamType::ISO_IEC_13818_6_TYPE_B, "DSM-CC type B"),
+        (StreamType::ISO_IEC_13818_6_TYPE_C, "DSM-CC type C"),
+        // ARIBではデータ放送に使われる
+        (StreamType::ISO_IEC_13818_6_TYPE_D, "ARIB data carousel"),
+        (StreamType::ISO_IEC_13818_1_AUXILIARY, "auxiliary"),
+        (StreamType::AAC, "AAC audio"),
+        (StreamType::MPEG4_VISUAL, "MPEG-4 visual"),
+        (StreamType::MPEG4_AUDIO, "MPEG-4 audio"),
+        (StreamType::ISO_IEC_14496_1_IN_PES, "ISO/IEC 14496-1 in PES"),
+        (
+            StreamType::ISO_IEC_14496_1_IN_SECTIONS,
+            "ISO/IEC 14496-1 in sections",
+        ),
+        (StreamType::ISO_IEC_13818_6_DOWNLOAD, "DSM-CC download"),
+        (StreamType::METADATA_IN_PES, "metadata in PES"),
+        (StreamType::METADATA_IN_SECTIONS, "metadata in sections"),
+        (
+            StreamType::METADATA_IN_DATA_CAROUSEL,
+            "metadata in data carousel",
+        ),
+        (
+            StreamType::METADATA_IN_OBJECT_CAROUSEL,
+            "metadata in object carousel",
+        ),
+        (
+            StreamType::METADATA_IN_DOWNLOAD_PROTOCOL,
+            "metadata in download protocol",
+        ),
+        (StreamType::IPMP, "IPMP"),
+        (StreamType::H264, "H.264 video"),
+        (StreamType::H265, "H.265 video"),
+        (StreamType::USER_PRIVATE, "user private"),
+        (StreamType::AC3, "AC-3 audio"),
+        (StreamType::DTS, "DTS audio"),
+        (StreamType::TRUEHD, "TrueHD audio"),
+        (StreamType::DOLBY_DIGITAL_PLUS, "Dolby Digital Plus audio"),
+    ];
+
+    /// ストリーム形式の名前を返す。
+    ///
+    /// 定義されていないストリーム形式の場合は`None`を返す。
+    pub fn name(&self) -> Option<&'static str> {
+        Self::NAMES
+            .iter()
+            .find(|(st, _)| st == self)
+            .map(|&(_, name)| name)
+    }
+
+    /// [`StreamType::name`]が返す名前からストリーム形式を返す。
+    ///
+    /// 大文字・小文字は区別しない。該当するストリーム形式が無い場合は`None`を返す。
+    pub fn from_name(name: &str) -> Option<StreamType> {
+        Self::NAMES
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|&(st, _)| st)
+    }
 }
 
 impl fmt::Debug for StreamType {
@@ -135,6 +212,15 @@ impl fmt::Debug for StreamType {
     }
 }
 
+impl fmt::Display for StreamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "unknown(0x{:02X})", self.0),
+        }
+    }
+}
+
 /// ネットワーク名記述子。
 #[derive(Debug, PartialEq, Eq)]
 pub struct NetworkNameDescriptor<'a> {
@@ -466,6 +552,57 @@ impl<'a> Descriptor<'a> for ComponentDescriptor<'a> {
     }
 }
 
+impl<'a> ComponentDescriptor<'a> {
+    /// 映像のコンポーネントであるかどうかを返す。
+    #[inline]
+    pub fn is_video(&self) -> bool {
+        // 0x01はMPEG-2、0x05はH.264|MPEG-4 AVC、0x09はH.265|HEVC
+        matches!(self.stream_content, 0x01 | 0x05 | 0x09)
+    }
+
+    /// コンポーネント種別から映像の解像度を`(幅, 高さ)`として返す。
+    ///
+    /// 映像のコンポーネントでない場合や解像度が不明な場合は`None`を返す。
+    pub fn video_resolution(&self) -> Option<(u16, u16)> {
+        if !self.is_video() {
+            return None;
+        }
+
+        // ARIB STD-B10 第2部 表6-5
+        match self.component_type & 0xF0 {
+            // 480i（525i）
+            0x00 => Some((720, 480)),
+            // 2160p
+            0x90 => Some((3840, 2160)),
+            // 480p（525p）
+            0xA0 => Some((720, 480)),
+            // 1080i（1125i）
+            0xB0 => Some((1920, 1080)),
+            // 720p（750p）
+            0xC0 => Some((1280, 720)),
+            // 240p
+            0xD0 => Some((320, 240)),
+            // 1080p（1125p）
+            0xE0 => Some((1920, 1080)),
+            // 180p
+            0xF0 => Some((320, 180)),
+            _ => None,
+        }
+    }
+
+    /// 映像がハイビジョン（720p以上）であるかどうかを返す。
+    #[inline]
+    pub fn is_high_definition(&self) -> bool {
+        matches!(self.video_resolution(), Some((_, height)) if height >= 720)
+    }
+
+    /// 映像がワンセグ（部分受信階層）向けであるかどうかを返す。
+    #[inline]
+    pub fn is_one_seg(&self) -> bool {
+        self.stream_content == 0x05 && matches!(self.component_type & 0xF0, 0xD0 | 0xF0)
+    }
+}
+
 /// ストリーム識別記述子。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StreamIdDescriptor {
@@ -2246,3 +2383,136 @@ impl Descriptor<'_> for LdtLinkageDescriptor {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::psi::desc::DescriptorBlock;
+
+    #[test]
+    fn test_stream_type_name() {
+        assert_eq!(StreamType::MPEG2_VIDEO.name(), Some("MPEG-2 video"));
+        assert_eq!(StreamType::H264.name(), Some("H.264 video"));
+        assert_eq!(StreamType::AAC.name(), Some("AAC audio"));
+        assert_eq!(StreamType::CAPTION.name(), Some("ARIB caption"));
+        assert_eq!(
+            StreamType::DATA_CARROUSEL.name(),
+            Some("ARIB data carousel")
+        );
+        assert_eq!(StreamType::UNINITIALIZED.name(), None);
+        assert_eq!(StreamType(0x30).name(), None);
+        assert_eq!(StreamType::INVALID.name(), None);
+
+        // 名前があるのは定義されているストリーム形式だけ
+        for st in (0..=u8::MAX).map(StreamType) {
+            assert_eq!(st.name().is_some(), st.is_known(), "{:?}", st);
+        }
+
+        assert!(StreamType::CAPTION.is_caption());
+        assert!(StreamType::PRIVATE_DATA.is_caption());
+        assert!(!StreamType::AAC.is_caption());
+        assert!(!StreamType::DATA_CARROUSEL.is_caption());
+
+        assert_eq!(StreamType::H265.to_string(), "H.265 video");
+        assert_eq!(StreamType::CAPTION.to_string(), "ARIB caption");
+        assert_eq!(StreamType(0x30).to_string(), "unknown(0x30)");
+        assert_eq!(StreamType(0x0).to_string(), "unknown(0x00)");
+        assert_eq!(format!("{:?}", StreamType::AAC), "StreamType(0x0F)");
+    }
+
+    #[test]
+    fn test_stream_type_from_name() {
+        assert_eq!(StreamType::from_name("H.264 video"), Some(StreamType::H264));
+        assert_eq!(StreamType::from_name("aac audio"), Some(StreamType::AAC));
+        assert_eq!(
+            StreamType::from_name("ARIB caption"),
+            Some(StreamType::CAPTION)
+        );
+        assert_eq!(StreamType::from_name("unknown(0x30)"), None);
+        assert_eq!(StreamType::from_name(""), None);
+
+        for st in (0..=u8::MAX).map(StreamType) {
+            if let Some(name) = st.name() {
+                assert_eq!(StreamType::from_name(name), Some(st));
+            }
+        }
+    }
+
+    #[test]
+    fn test_descriptor_block_raw() {
+        let data = [
+            // ストリーム識別記述子
+            0x52, 0x01, 0x30, //
+            // 長さ0の未知の記述子
+            0xFF, 0x00, //
+            // ストリーム識別記述子
+            0x52, 0x01, 0x31,
+        ];
+        let (block, rem) = DescriptorBlock::read_with_len(&data, data.len() as u16).unwrap();
+        assert!(rem.is_empty());
+
+        assert_eq!(
+            block.raw_iter().collect::<Vec<_>>(),
+            [(0x52, &[0x30][..]), (0xFF, &[][..]), (0x52, &[0x31][..])],
+        );
+        assert!(block.has_tag(StreamIdDescriptor::TAG));
+        assert!(block.has_tag(0xFF));
+        assert!(!block.has_tag(ComponentDescriptor::TAG));
+
+        let (block, _) = DescriptorBlock::read_with_len(&[], 0).unwrap();
+        assert_eq!(block.raw_iter().count(), 0);
+        assert!(!block.has_tag(StreamIdDescriptor::TAG));
+    }
+
+    fn component(stream_content: u8, component_type: u8) -> ComponentDescriptor<'static> {
+        ComponentDescriptor {
+            stream_content,
+            component_type,
+            component_tag: 0x00,
+            lang_code: LangCode::JPN,
+            text: AribStr::from_bytes(&[]),
+        }
+    }
+
+    #[test]
+    fn test_component_video_resolution() {
+        let c = component(0x01, 0xB3);
+        assert!(c.is_video());
+        assert_eq!(c.video_resolution(), Some((1920, 1080)));
+        assert!(c.is_high_definition());
+        assert!(!c.is_one_seg());
+
+        let c = component(0x05, 0xC4);
+        assert_eq!(c.video_resolution(), Some((1280, 720)));
+        assert!(c.is_high_definition());
+
+        let c = component(0x01, 0x01);
+        assert_eq!(c.video_resolution(), Some((720, 480)));
+        assert!(!c.is_high_definition());
+
+        // 下位4ビットが予約値でも上位4ビットで判断するため480iとなる
+        let c = component(0x01, 0x0F);
+        assert_eq!(c.video_resolution(), Some((720, 480)));
+        let c = component(0x01, 0x00);
+        assert_eq!(c.video_resolution(), Some((720, 480)));
+
+        // 上位4ビットが予約値の場合は不明
+        let c = component(0x01, 0x50);
+        assert_eq!(c.video_resolution(), None);
+        assert!(!c.is_high_definition());
+
+        // ワンセグ
+        let c = component(0x05, 0xD3);
+        assert_eq!(c.video_resolution(), Some((320, 240)));
+        assert!(c.is_one_seg());
+        assert!(!c.is_high_definition());
+        assert!(component(0x05, 0xF1).is_one_seg());
+        assert!(!component(0x01, 0xD3).is_one_seg());
+
+        // 映像でない
+        let c = component(0x02, 0x03);
+        assert!(!c.is_video());
+        assert_eq!(c.video_resolution(), None);
+        assert!(!c.is_one_seg());
+    }
+}
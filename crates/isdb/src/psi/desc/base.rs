@@ -78,6 +78,18 @@ impl<'a> DescriptorBlock<'a> {
         DescriptorIter(self.0)
     }
 
+    /// 内包する記述子群を、パースせずにタグと内容の組で返すイテレーターを返す。
+    #[inline]
+    pub fn raw_iter(&self) -> impl Iterator<Item = (u8, &'a [u8])> + 'a {
+        self.iter().map(|d| (d.tag, d.data))
+    }
+
+    /// 内包する記述子群に`tag`と一致するタグの記述子があるかどうかを返す。
+    #[inline]
+    pub fn has_tag(&self, tag: u8) -> bool {
+        self.iter().any(|d| d.tag == tag)
+    }
+
     /// 内包する記述子群から`T`のタグと一致する記述子を読み取って返す。
     ///
     /// `T`のタグと一致する記述子がない場合は`None`を返す。
@@ -208,7 +208,7 @@ impl Repository {
             return;
         };
 
-        if idx <= versions.len() {
+        if idx >= versions.len() {
             return;
         }
 
@@ -216,9 +216,141 @@ impl Repository {
         versions[idx] = 0xFF;
     }
 
+    /// `table_id`と`table_id_extension`で示されるサブテーブルの全セクションを未読み取りとする。
+    ///
+    /// 他のサブテーブルのバージョンには影響しない。
+    pub fn reset_subtable(&mut self, table_id: u8, table_id_extension: u16) {
+        if let Some(versions) = self
+            .subtable_versions
+            .get_mut(&(table_id, table_id_extension))
+        {
+            // バージョン番号は5ビットであるため0x20以上は無効値
+            versions.fill(0xFF);
+        }
+    }
+
+    /// `table_id`で示されるテーブルについて、全てのサブテーブルを未読み取りとする。
+    ///
+    /// 他のテーブルのバージョンには影響しない。
+    pub fn reset_table(&mut self, table_id: u8) {
+        for (_, versions) in self
+            .subtable_versions
+            .iter_mut()
+            .filter(|((id, _), _)| *id == table_id)
+        {
+            // バージョン番号は5ビットであるため0x20以上は無効値
+            versions.fill(0xFF);
+        }
+    }
+
     /// `Repository`の内容を消去して初期化する。
     #[inline]
     pub fn clear(&mut self) {
         self.subtable_versions.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy;
+
+    impl<'a> PsiTable<'a> for Dummy {
+        fn read(_: &PsiSection<'a>) -> Option<Dummy> {
+            Some(Dummy)
+        }
+    }
+
+    fn section(
+        table_id: u8,
+        table_id_extension: u16,
+        version_number: u8,
+        section_number: u8,
+    ) -> PsiSection<'static> {
+        PsiSection {
+            table_id,
+            syntax: Some(PsiSectionSyntax {
+                table_id_extension,
+                version_number,
+                current_next_indicator: true,
+                section_number,
+                last_section_number: 1,
+            }),
+            data: &[],
+            crc32: 0,
+        }
+    }
+
+    fn read_all(repo: &mut Repository, sections: &[PsiSection]) -> Vec<bool> {
+        sections
+            .iter()
+            .map(|psi| repo.read::<Dummy>(psi).is_some())
+            .collect()
+    }
+
+    #[test]
+    fn test_repository_unset() {
+        let sections = [
+            section(0x42, 1, 0, 0),
+            section(0x42, 1, 0, 1),
+            section(0x42, 2, 0, 0),
+        ];
+
+        let mut repo = Repository::new();
+        assert_eq!(read_all(&mut repo, &sections), [true, true, true]);
+
+        // 指定したセクションのみが未読み取りとなる
+        repo.unset(&sections[1]);
+        assert_eq!(read_all(&mut repo, &sections), [false, true, false]);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false]);
+
+        // 記録されている数を超えるセクション番号は無視される
+        let mut psi = section(0x42, 1, 0, 2);
+        psi.syntax.as_mut().unwrap().last_section_number = 2;
+        repo.unset(&psi);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false]);
+    }
+
+    #[test]
+    fn test_repository_reset_subtable() {
+        let sections = [
+            section(0x42, 1, 0, 0),
+            section(0x42, 1, 0, 1),
+            section(0x42, 2, 0, 0),
+            section(0x46, 1, 0, 0),
+        ];
+
+        let mut repo = Repository::new();
+        assert_eq!(read_all(&mut repo, &sections), [true, true, true, true]);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false, false]);
+
+        repo.reset_subtable(0x42, 1);
+        assert_eq!(read_all(&mut repo, &sections), [true, true, false, false]);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false, false]);
+
+        // 存在しないサブテーブル
+        repo.reset_subtable(0x42, 3);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false, false]);
+    }
+
+    #[test]
+    fn test_repository_reset_table() {
+        let sections = [
+            section(0x42, 1, 0, 0),
+            section(0x42, 1, 0, 1),
+            section(0x42, 2, 0, 0),
+            section(0x46, 1, 0, 0),
+        ];
+
+        let mut repo = Repository::new();
+        assert_eq!(read_all(&mut repo, &sections), [true, true, true, true]);
+
+        repo.reset_table(0x42);
+        assert_eq!(read_all(&mut repo, &sections), [true, true, true, false]);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false, false]);
+
+        repo.reset_table(0x46);
+        assert_eq!(read_all(&mut repo, &sections), [false, false, false, true]);
+    }
+}
@@ -1303,3 +1303,49 @@ impl<'a> PsiTable<'a> for Itt<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eight::str::AribStr;
+    use crate::psi::desc::{ServiceDescriptor, ServiceType};
+
+    #[test]
+    fn test_sdt() {
+        const SDT: &[u8] = &hex_literal::hex!(
+            "
+42 F0 19 7F E0 C1 00 00 7F E0 FF 04 00 E3 80 08
+48 06 01 00 03 41 42 43 C0 CC B0 36
+"
+        );
+
+        let (psi, len) = PsiSection::parse(SDT).unwrap();
+        assert_eq!(len, SDT.len());
+
+        let Sdt::Actual(sdt) = Sdt::read(&psi).unwrap() else {
+            panic!("not actual");
+        };
+        assert_eq!(
+            sdt.transport_stream_id,
+            TransportStreamId::new(0x7FE0).unwrap()
+        );
+        assert_eq!(sdt.original_network_id, NetworkId::new(0x7FE0).unwrap());
+        assert_eq!(sdt.services.len(), 1);
+
+        let svc = &sdt.services[0];
+        assert_eq!(svc.service_id, ServiceId::new(0x0400).unwrap());
+        assert!(!svc.h_eit_flag);
+        assert!(svc.eit_schedule_flag);
+        assert!(svc.eit_present_following_flag);
+        assert_eq!(svc.running_status, RunningStatus::Running);
+        assert!(!svc.free_ca_mode);
+        assert_eq!(
+            svc.descriptors.get::<ServiceDescriptor>(),
+            Some(ServiceDescriptor {
+                service_type: ServiceType(0x01),
+                service_provider_name: AribStr::from_bytes(b""),
+                service_name: AribStr::from_bytes(b"ABC"),
+            })
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use std::num::NonZeroU16;
 
 use crate::pid::Pid;
-use crate::psi::desc::{DescriptorBlock, StreamType};
+use crate::psi::desc::{ConditionalAccessDescriptor, DescriptorBlock, StreamType};
 use crate::psi::{PsiSection, PsiTable};
 use crate::utils::BytesExt;
 
@@ -51,7 +51,12 @@ pub struct Pat {
     /// トランスポートストリーム識別。
     pub transport_stream_id: TransportStreamId,
 
+    /// バージョン番号（5ビット）。
+    version_number: u8,
+
     /// NITのPID。
+    ///
+    /// PATにNITが記述されていない場合は[`Pid::NULL`]となる。
     pub network_pid: Pid,
 
     /// PMTのPIDを格納する配列。
@@ -61,6 +66,20 @@ pub struct Pat {
 impl Pat {
     /// PATのテーブルID。
     pub const TABLE_ID: u8 = 0x00;
+
+    /// PATに記述されたNITのPIDを返す。
+    ///
+    /// 番組番号が`0`の項目が存在しない場合は`None`を返す。
+    #[inline]
+    pub fn network_pid(&self) -> Option<Pid> {
+        Some(self.network_pid).filter(|&pid| pid != Pid::NULL)
+    }
+
+    /// PATのバージョン番号を返す。
+    #[inline]
+    pub fn version_number(&self) -> u8 {
+        self.version_number
+    }
 }
 
 impl PsiTable<'_> for Pat {
@@ -99,6 +118,7 @@ impl PsiTable<'_> for Pat {
 
         Some(Pat {
             transport_stream_id,
+            version_number: syntax.version_number,
             network_pid,
             pmts,
         })
@@ -158,6 +178,22 @@ pub struct Pmt<'a> {
 impl<'a> Pmt<'a> {
     /// PMTのテーブルID。
     pub const TABLE_ID: u8 = 0x02;
+
+    /// ESループより前に配置される、番組全体に対する記述子の塊。
+    #[inline]
+    pub fn program_info(&self) -> DescriptorBlock<'a> {
+        self.descriptors.clone()
+    }
+
+    /// 番組全体に対する限定受信方式記述子で示されるECMのPID。
+    ///
+    /// 限定受信方式記述子がない場合は`None`を返す。
+    #[inline]
+    pub fn ecm_pid(&self) -> Option<Pid> {
+        self.descriptors
+            .get::<ConditionalAccessDescriptor>()
+            .map(|cad| cad.ca_pid)
+    }
 }
 
 impl<'a> PsiTable<'a> for Pmt<'a> {
@@ -305,3 +341,64 @@ impl<'a> PsiTable<'a> for Nit<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pat() {
+        const PAT: &[u8] = &hex_literal::hex!(
+            "
+00 B0 15 7F E0 C7 00 00 00 00 E0 10 04 00 E1 F0
+04 01 E1 F1 35 49 90 AC
+"
+        );
+
+        let (psi, len) = PsiSection::parse(PAT).unwrap();
+        assert_eq!(len, PAT.len());
+
+        let pat = Pat::read(&psi).unwrap();
+        assert_eq!(
+            pat.transport_stream_id,
+            TransportStreamId::new(0x7FE0).unwrap()
+        );
+        assert_eq!(pat.version_number(), 3);
+        assert_eq!(pat.network_pid(), Some(Pid::new(0x0010)));
+        assert_eq!(
+            pat.pmts,
+            [
+                PatProgram {
+                    program_number: ServiceId::new(0x0400).unwrap(),
+                    program_map_pid: Pid::new(0x01F0),
+                },
+                PatProgram {
+                    program_number: ServiceId::new(0x0401).unwrap(),
+                    program_map_pid: Pid::new(0x01F1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pmt() {
+        const PMT: &[u8] = &hex_literal::hex!(
+            "
+02 B0 1B 04 00 C1 00 00 E1 FF F0 06 09 04 00 05
+E1 01 02 E1 00 F0 03 52 01 00 A2 E0 A6 C7
+"
+        );
+
+        let (psi, len) = PsiSection::parse(PMT).unwrap();
+        assert_eq!(len, PMT.len());
+
+        let pmt = Pmt::read(&psi).unwrap();
+        assert_eq!(pmt.program_number, ServiceId::new(0x0400).unwrap());
+        assert_eq!(pmt.pcr_pid, Pid::new(0x01FF));
+        assert_eq!(pmt.program_info().iter().count(), 1);
+        assert_eq!(pmt.ecm_pid(), Some(Pid::new(0x0101)));
+        assert_eq!(pmt.streams.len(), 1);
+        assert_eq!(pmt.streams[0].stream_type, StreamType(0x02));
+        assert_eq!(pmt.streams[0].elementary_pid, Pid::new(0x0100));
+    }
+}
@@ -122,6 +122,46 @@ impl MjdDate {
             weekday,
         })
     }
+
+    /// `MjdDate`を年・月・日の組に変換する。
+    ///
+    /// 無効な日付（1900年3月1日より前など）の場合は`None`が返る。
+    #[inline]
+    pub fn to_ymd(&self) -> Option<(u16, u8, u8)> {
+        let date = self.to_date()?;
+        Some((date.year as u16, date.month, date.day))
+    }
+
+    /// 年・月・日から`MjdDate`を生成する。
+    ///
+    /// 存在しない日付や修正ユリウス日で表現できない日付（1900年3月1日より前など）の場合は`None`が返る。
+    pub fn from_ymd(year: u16, month: u8, day: u8) -> Option<MjdDate> {
+        if year < 1900 || !(1..=12).contains(&month) || day == 0 {
+            return None;
+        }
+
+        // ARIB STD-B10 付録Cの変換式を整数演算で行う
+        let l = if month <= 2 { 1 } else { 0 };
+        let y = (year - 1900) as u32;
+        let m = month as u32;
+        if y < l {
+            return None;
+        }
+        let mjd = 14956 + day as u32 + (y - l) * 36525 / 100 + (m + 1 + l * 12) * 306001 / 10000;
+        if mjd >= u16::MAX as u32 {
+            return None;
+        }
+
+        // 2月30日のような存在しない日付を弾く
+        let date = MjdDate(mjd as u16);
+        (date.to_ymd()? == (year, month, day)).then_some(date)
+    }
+
+    /// 曜日を日曜日を0、土曜日を6とする数値で返す。
+    #[inline]
+    pub fn day_of_week(&self) -> u8 {
+        ((self.0 as u32 + 3) % 7) as u8
+    }
 }
 
 impl fmt::Display for MjdDate {
@@ -190,12 +230,55 @@ impl DateTime {
         let seconds = minutes * 60 + self.second as u64;
         seconds
     }
+
+    /// 1970年1月1日（UTC）からの経過秒数から日本標準時の`DateTime`を生成する。
+    ///
+    /// 修正ユリウス日で表現できない日付の場合は`None`を返す。
+    pub fn from_unix_timestamp(secs: i64) -> Option<DateTime> {
+        /// 1970/1/1の修正ユリウス日
+        const UNIX_EPOCH_JULIAN_DAY: i64 = 40587;
+        /// 日本標準時の時差。
+        const JST_OFFSET_SECS: i64 = 9 * 60 * 60;
+        const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+        let secs = secs.checked_add(JST_OFFSET_SECS)?;
+        let days = secs.div_euclid(SECS_PER_DAY);
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+
+        // 0xFFFFは無効な日付を表すため除外
+        let mjd = days.checked_add(UNIX_EPOCH_JULIAN_DAY)?;
+        if !(0..u16::MAX as i64).contains(&mjd) {
+            return None;
+        }
+
+        Some(DateTime {
+            date: MjdDate(mjd as u16),
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+        })
+    }
+
+    /// `DateTime`から[`chrono::NaiveDateTime`]に変換する。
+    ///
+    /// 無効な日付の場合は`None`が返る。
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Option<chrono::NaiveDateTime> {
+        let date = self.date.to_date()?;
+        let date = chrono::NaiveDate::from_ymd_opt(date.year, date.month as u32, date.day as u32)?;
+        // 閏秒はchronoの流儀に従いミリ秒に繰り入れる
+        if self.second == 60 {
+            date.and_hms_milli_opt(self.hour as u32, self.minute as u32, 59, 1_000)
+        } else {
+            date.and_hms_opt(self.hour as u32, self.minute as u32, self.second as u32)
+        }
+    }
 }
 
 impl fmt::Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.date.fmt(f)?;
-        f.write_char(' ')?;
+        f.write_char('T')?;
 
         write_hundreds(f, self.hour)?;
         f.write_char(':')?;
@@ -220,6 +303,14 @@ impl fmt::Debug for DateTime {
 
 const FULL_PER_SECS: u64 = 27_000_000;
 
+/// `dur`を27MHz単位の値に変換する。
+///
+/// [`Timestamp::to_duration`]での切り捨てを打ち消すため、27MHz未満の端数は四捨五入する。
+#[inline]
+const fn duration_to_full(dur: Duration) -> u128 {
+    dur.as_secs() as u128 * FULL_PER_SECS as u128 + (dur.subsec_nanos() as u128 * 27 + 500) / 1_000
+}
+
 /// PCRやPTS等を表すタイムスタンプ。時間は27MHz単位で表現される。
 ///
 /// 演算・比較はラップアラウンドを考慮して行われ、自動でオーバーフローする。
@@ -330,6 +421,9 @@ impl Timestamp {
     ///
     /// `dur`の値が`Timestamp`の最大値を超える場合は`Timestamp::MAX`が返る。
     ///
+    /// 27MHz未満の端数は最も近い値に丸められるため、
+    /// 任意の`Timestamp`について`Timestamp::from_duration(ts.to_duration()) == ts`が成り立つ。
+    ///
     /// # サンプル
     ///
     /// ```
@@ -348,16 +442,8 @@ impl Timestamp {
     /// ```
     #[inline]
     pub const fn from_duration(dur: Duration) -> Timestamp {
-        let secs = dur.as_secs();
-        let nanos = dur.subsec_nanos();
-
-        // Option::and_thenはconst fnではない
-        let x = match secs.checked_mul(FULL_PER_SECS) {
-            Some(x) => x.checked_add(nanos as u64 * 27 / 1_000),
-            None => None,
-        };
-        match x {
-            Some(x) if x <= Self::MAX.0 => Timestamp(x),
+        match duration_to_full(dur) {
+            x if x <= Self::MAX.0 as u128 => Timestamp(x as u64),
             _ => Self::MAX,
         }
     }
@@ -375,9 +461,14 @@ impl Timestamp {
 
     /// PTS・DTSを格納する`data`から`Timestamp`を読み取る。
     ///
-    /// `data`が不正な場合は`None`を返す。
+    /// `data`のマーカービットが不正な場合は`None`を返す。
     #[inline]
     pub fn read_pts(data: &[u8; 5]) -> Option<Timestamp> {
+        if data[0] & 0b00000001 == 0 || data[2] & 0b00000001 == 0 || data[4] & 0b00000001 == 0 {
+            log::debug!("invalid Timestamp::marker_bit");
+            return None;
+        }
+
         let timestamp = ((data[0] & 0b00001110) as u64) << 29
             | (((data[1..=2].read_be_16() & 0b11111111_11111110) as u64) << 14)
             | ((data[3..=4].read_be_16() >> 1) as u64);
@@ -494,6 +585,121 @@ impl Timestamp {
         let nanos = (self.0 % FULL_PER_SECS * 1_000 / 27) as u32;
         Duration::new(secs, nanos)
     }
+
+    /// ラップアラウンドさせずに加算する。結果が`Timestamp::MAX`を超える場合は`None`を返す。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::new(1, 0).checked_add(Timestamp::new(2, 0)), Some(Timestamp::new(3, 0)));
+    /// assert_eq!(Timestamp::MAX.checked_add(Timestamp::ZERO), Some(Timestamp::MAX));
+    /// assert_eq!(Timestamp::MAX.checked_add(Timestamp::new(0, 1)), None);
+    /// ```
+    #[inline]
+    pub const fn checked_add(self, rhs: Timestamp) -> Option<Timestamp> {
+        // どちらも43ビットに収まるため`u64`ではオーバーフローしない
+        match self.0 + rhs.0 {
+            x if x > Self::MAX.0 => None,
+            x => Some(Timestamp(x)),
+        }
+    }
+
+    /// ラップアラウンドさせずに減算する。結果が負になる場合は`None`を返す。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::new(3, 0).checked_sub(Timestamp::new(2, 0)), Some(Timestamp::new(1, 0)));
+    /// assert_eq!(Timestamp::ZERO.checked_sub(Timestamp::ZERO), Some(Timestamp::ZERO));
+    /// assert_eq!(Timestamp::ZERO.checked_sub(Timestamp::new(0, 1)), None);
+    /// ```
+    #[inline]
+    pub const fn checked_sub(self, rhs: Timestamp) -> Option<Timestamp> {
+        match self.0.checked_sub(rhs.0) {
+            Some(x) => Some(Timestamp(x)),
+            None => None,
+        }
+    }
+
+    /// 加算する。結果が`Timestamp::MAX`を超える場合は`Timestamp::MAX`を返す。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::new(1, 0).saturating_add(Timestamp::new(2, 0)), Timestamp::new(3, 0));
+    /// assert_eq!(Timestamp::MAX.saturating_add(Timestamp::new(0, 1)), Timestamp::MAX);
+    /// ```
+    #[inline]
+    pub const fn saturating_add(self, rhs: Timestamp) -> Timestamp {
+        match self.checked_add(rhs) {
+            Some(x) => x,
+            None => Self::MAX,
+        }
+    }
+
+    /// 減算する。結果が負になる場合は`Timestamp::ZERO`を返す。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::new(3, 0).saturating_sub(Timestamp::new(2, 0)), Timestamp::new(1, 0));
+    /// assert_eq!(Timestamp::ZERO.saturating_sub(Timestamp::new(0, 1)), Timestamp::ZERO);
+    /// ```
+    #[inline]
+    pub const fn saturating_sub(self, rhs: Timestamp) -> Timestamp {
+        match self.checked_sub(rhs) {
+            Some(x) => x,
+            None => Self::ZERO,
+        }
+    }
+
+    /// 33ビットの範囲でラップアラウンドさせて加算する。
+    ///
+    /// `+`演算子と同じ動作をする。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::MAX.wrapping_add(Timestamp::new(0, 1)), Timestamp::ZERO);
+    /// assert_eq!(Timestamp::MAX.wrapping_add(Timestamp::new(1, 0)), Timestamp::new(0, 299));
+    /// ```
+    #[inline]
+    pub const fn wrapping_add(self, rhs: Timestamp) -> Timestamp {
+        match self.0 + rhs.0 {
+            x if x > Self::MAX.0 => Timestamp(x - Self::MAX.0 - 1),
+            x => Timestamp(x),
+        }
+    }
+
+    /// 33ビットの範囲でラップアラウンドさせて減算する。
+    ///
+    /// `-`演算子と同じ動作をする。
+    ///
+    /// # サンプル
+    ///
+    /// ```
+    /// use isdb::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::ZERO.wrapping_sub(Timestamp::new(0, 1)), Timestamp::MAX);
+    /// assert_eq!(Timestamp::new(0, 299).wrapping_sub(Timestamp::new(1, 0)), Timestamp::MAX);
+    /// ```
+    #[inline]
+    pub const fn wrapping_sub(self, rhs: Timestamp) -> Timestamp {
+        match self.0.checked_sub(rhs.0) {
+            Some(x) => Timestamp(x),
+            None => Timestamp((self.0 + Self::MAX.0 + 1) - rhs.0),
+        }
+    }
 }
 
 impl From<Duration> for Timestamp {
@@ -515,10 +721,7 @@ impl ops::Add for Timestamp {
 
     #[inline]
     fn add(self, rhs: Timestamp) -> Timestamp {
-        match self.0 + rhs.0 {
-            x if x > Self::MAX.0 => Timestamp(x - Self::MAX.0 - 1),
-            x => Timestamp(x),
-        }
+        self.wrapping_add(rhs)
     }
 }
 
@@ -534,7 +737,6 @@ impl ops::Add<Duration> for Timestamp {
 
     #[inline]
     fn add(self, mut rhs: Duration) -> Timestamp {
-        const OVER_FULL: u64 = Timestamp::MAX.0 + 1;
         // `+`がconstでないため`saturating_add`を使っているが飽和するわけではない
         const OVER_DUR: Duration = Timestamp::MAX
             .to_duration()
@@ -545,7 +747,7 @@ impl ops::Add<Duration> for Timestamp {
         }
         let rhs = rhs.as_secs() * FULL_PER_SECS + rhs.subsec_nanos() as u64 * 27 / 1_000;
 
-        Timestamp((self.0 + rhs) % OVER_FULL)
+        self.wrapping_add(Timestamp(rhs))
     }
 }
 
@@ -554,10 +756,7 @@ impl ops::Sub for Timestamp {
 
     #[inline]
     fn sub(self, rhs: Timestamp) -> Timestamp {
-        match self.0.checked_sub(rhs.0) {
-            Some(x) => Timestamp(x),
-            None => Timestamp((self.0 + Self::MAX.0 + 1) - rhs.0),
-        }
+        self.wrapping_sub(rhs)
     }
 }
 
@@ -582,10 +781,7 @@ impl ops::Sub<Duration> for Timestamp {
         }
         let rhs = rhs.as_secs() * FULL_PER_SECS + rhs.subsec_nanos() as u64 * 27 / 1_000;
 
-        match self.0.checked_sub(rhs) {
-            Some(x) => Timestamp(x),
-            None => Timestamp((self.0 + Self::MAX.0 + 1) - rhs),
-        }
+        self.wrapping_sub(Timestamp(rhs))
     }
 }
 
@@ -669,8 +865,178 @@ mod tests {
         assert_eq!(dt.hour, 12);
         assert_eq!(dt.minute, 34);
         assert_eq!(dt.second, 56);
-        assert_eq!(dt.to_string(), "1982-09-06 12:34:56");
+        assert_eq!(dt.to_string(), "1982-09-06T12:34:56");
         assert_eq!(format!("{:?}", dt), "1982-09-06 (Mon) 12:34:56");
         assert_eq!(dt.ntp_timestamp(), 2609152496);
     }
+
+    #[test]
+    fn test_mjd_date_ymd() {
+        // (修正ユリウス日, 年月日, 曜日)
+        const TABLE: &[(u16, (u16, u8, u8), u8)] = &[
+            // ARIB STD-B10 付録Cの例
+            (45218, (1982, 9, 6), 1),
+            (15079, (1900, 3, 1), 4),
+            (40587, (1970, 1, 1), 4),
+            (51603, (2000, 2, 29), 2),
+            (51604, (2000, 3, 1), 3),
+            (60309, (2023, 12, 31), 0),
+            (60310, (2024, 1, 1), 1),
+        ];
+
+        for &(mjd, ymd, dow) in TABLE {
+            let date = MjdDate(mjd);
+            assert_eq!(date.to_ymd(), Some(ymd));
+            assert_eq!(MjdDate::from_ymd(ymd.0, ymd.1, ymd.2), Some(date));
+            assert_eq!(date.day_of_week(), dow);
+        }
+
+        assert_eq!(MjdDate(0xFFFF).to_ymd(), None);
+        assert_eq!(MjdDate::from_ymd(1899, 12, 31), None);
+        assert_eq!(MjdDate::from_ymd(1900, 2, 28), None);
+        assert_eq!(MjdDate::from_ymd(2023, 2, 29), None);
+        assert_eq!(MjdDate::from_ymd(2023, 4, 31), None);
+        assert_eq!(MjdDate::from_ymd(2023, 13, 1), None);
+        assert_eq!(MjdDate::from_ymd(2023, 1, 0), None);
+    }
+
+    #[test]
+    fn test_date_time_from_unix_timestamp() {
+        // 1982-09-06T03:34:56Z
+        let dt = DateTime::from_unix_timestamp(400131296).unwrap();
+        assert_eq!(dt, DateTime::read(&[0xB0, 0xA2, 0x12, 0x34, 0x56]));
+
+        let dt = DateTime::from_unix_timestamp(0).unwrap();
+        assert_eq!(dt.to_string(), "1970-01-01T09:00:00");
+
+        // 日付を跨ぐ
+        let dt = DateTime::from_unix_timestamp(15 * 60 * 60).unwrap();
+        assert_eq!(dt.to_string(), "1970-01-02T00:00:00");
+
+        // 1970年より前
+        let dt = DateTime::from_unix_timestamp(-10 * 60 * 60).unwrap();
+        assert_eq!(dt.to_string(), "1969-12-31T23:00:00");
+
+        assert_eq!(DateTime::from_unix_timestamp(i64::MAX), None);
+        assert_eq!(DateTime::from_unix_timestamp(i64::MIN), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_date_time_to_chrono() {
+        let dt = DateTime::read(&[0xB0, 0xA2, 0x12, 0x34, 0x56]);
+        assert_eq!(
+            dt.to_chrono(),
+            chrono::NaiveDate::from_ymd_opt(1982, 9, 6).and_then(|d| d.and_hms_opt(12, 34, 56))
+        );
+
+        let dt = DateTime {
+            date: MjdDate(0xFFFF),
+            hour: 0,
+            minute: 0,
+            second: 0,
+        };
+        assert_eq!(dt.to_chrono(), None);
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let round_trip = |ts: Timestamp| Timestamp::from_duration(ts.to_duration());
+
+        assert_eq!(round_trip(Timestamp::ZERO), Timestamp::ZERO);
+        assert_eq!(round_trip(Timestamp::MAX), Timestamp::MAX);
+
+        // 1秒未満の端数をすべて検査
+        for full in 0..FULL_PER_SECS {
+            let ts = Timestamp::from_full(full);
+            assert_eq!(round_trip(ts), ts);
+        }
+        // 33ビットの範囲全体から抜き出して検査
+        for full in (0..=Timestamp::MAX.full()).step_by(999_999_937) {
+            let ts = Timestamp::from_full(full);
+            assert_eq!(round_trip(ts), ts);
+        }
+        for full in Timestamp::MAX.full() - FULL_PER_SECS..=Timestamp::MAX.full() {
+            let ts = Timestamp::from_full(full);
+            assert_eq!(round_trip(ts), ts);
+        }
+
+        assert_eq!(
+            Timestamp::from_duration(Duration::from_millis(100)),
+            Timestamp::new(9_000, 0)
+        );
+        assert_eq!(
+            Duration::from(Timestamp::new(9_000, 0)),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn test_timestamp_wrap_boundary() {
+        const MAX: u64 = Timestamp::MAX.full();
+
+        // 33ビットの境界付近の全組み合わせを検査
+        let values = [
+            0,
+            1,
+            299,
+            300,
+            MAX / 2,
+            MAX / 2 + 1,
+            MAX - 300,
+            MAX - 1,
+            MAX,
+        ];
+        for a in values {
+            for b in values {
+                let (ta, tb) = (Timestamp::from_full(a), Timestamp::from_full(b));
+                let sum = a + b;
+                let diff = a as i64 - b as i64;
+
+                assert_eq!(
+                    ta.checked_add(tb),
+                    (sum <= MAX).then(|| Timestamp::from_full(sum)),
+                );
+                assert_eq!(
+                    ta.checked_sub(tb),
+                    (diff >= 0).then(|| Timestamp::from_full(diff as u64)),
+                );
+                assert_eq!(ta.saturating_add(tb), Timestamp::from_full(sum.min(MAX)),);
+                assert_eq!(
+                    ta.saturating_sub(tb),
+                    Timestamp::from_full(diff.max(0) as u64),
+                );
+                assert_eq!(ta.wrapping_add(tb), Timestamp::from_full(sum % (MAX + 1)),);
+                assert_eq!(
+                    ta.wrapping_sub(tb),
+                    Timestamp::from_full(diff.rem_euclid(MAX as i64 + 1) as u64),
+                );
+
+                assert_eq!(ta + tb, ta.wrapping_add(tb));
+                assert_eq!(ta - tb, ta.wrapping_sub(tb));
+                assert_eq!(ta.wrapping_add(tb).wrapping_sub(tb), ta);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_pts() {
+        assert_eq!(
+            Timestamp::read_pts(&[0x21, 0x00, 0x01, 0x00, 0x01]),
+            Some(Timestamp::ZERO)
+        );
+        assert_eq!(
+            Timestamp::read_pts(&[0x29, 0x8D, 0x15, 0xCF, 0x13]),
+            Some(Timestamp::new(0x1_2345_6789, 0))
+        );
+        assert_eq!(
+            Timestamp::read_pts(&[0x2F, 0xFF, 0xFF, 0xFF, 0xFF]),
+            Some(Timestamp::new(2u64.pow(33) - 1, 0))
+        );
+
+        // マーカービットが不正
+        assert_eq!(Timestamp::read_pts(&[0x20, 0x00, 0x01, 0x00, 0x01]), None);
+        assert_eq!(Timestamp::read_pts(&[0x21, 0x00, 0x00, 0x00, 0x01]), None);
+        assert_eq!(Timestamp::read_pts(&[0x21, 0x00, 0x01, 0x00, 0x00]), None);
+    }
 }
@@ -188,6 +188,12 @@ macro_rules! delegate_fmt {
             }
         }
 
+        crate::utils::delegate_radix_fmt!($type);
+    };
+}
+
+macro_rules! delegate_radix_fmt {
+    ($type:ty) => {
         impl std::fmt::Binary for $type {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 self.0.fmt(f)
@@ -217,6 +223,7 @@ macro_rules! delegate_fmt {
 // マクロはpub useできない
 pub(crate) use assume;
 pub(crate) use delegate_fmt;
+pub(crate) use delegate_radix_fmt;
 
 #[cfg(test)]
 mod tests {
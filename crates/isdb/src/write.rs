@@ -0,0 +1,416 @@
+//! MPEG2-TSを書き出すためのモジュール。
+
+use std::io::{self, Write};
+
+use crate::crc;
+use crate::packet::Packet;
+use crate::pes::StreamId;
+use crate::pid::Pid;
+use crate::time::Timestamp;
+
+const PACKET_SIZE: usize = 188;
+const HEADER_SIZE: usize = 4;
+const PAYLOAD_SIZE: usize = PACKET_SIZE - HEADER_SIZE;
+
+/// TSパケットを[`io::Write`]に書き出すためのライター。
+#[derive(Debug)]
+pub struct TsWriter<W> {
+    w: W,
+}
+
+impl<W: Write> TsWriter<W> {
+    /// `w`に書き出す`TsWriter`を生成する。
+    #[inline]
+    pub fn new(w: W) -> TsWriter<W> {
+        TsWriter { w }
+    }
+
+    /// 内包するライターへの参照を返す。
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.w
+    }
+
+    /// 内包するライターへの可変参照を返す。
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w
+    }
+
+    /// `TsWriter`を消費し、内包するライターを返す。
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+
+    /// 188バイトのTSパケットを書き出す。
+    #[inline]
+    pub fn write_packet(&mut self, packet: &Packet) -> io::Result<()> {
+        packet.write_to(&mut self.w)
+    }
+
+    /// 内包するライターをフラッシュする。
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.w.flush()
+    }
+}
+
+/// PIDと連続性指標を管理しつつ、ペイロードをTSパケットに分割する。
+#[derive(Debug, Clone)]
+struct Packetizer {
+    pid: Pid,
+    cc: u8,
+}
+
+impl Packetizer {
+    #[inline]
+    fn new(pid: Pid) -> Packetizer {
+        Packetizer { pid, cc: 0 }
+    }
+
+    /// `data`をTSパケットに分割して書き出す。
+    ///
+    /// `af_stuffing`が真の場合、最後のパケットの余りはアダプテーションフィールドで埋められる。
+    /// 偽の場合はペイロードの末尾が`0xFF`で埋められる。
+    fn write<W: Write>(
+        &mut self,
+        w: &mut TsWriter<W>,
+        data: &[u8],
+        af_stuffing: bool,
+    ) -> io::Result<()> {
+        let mut unit_start = true;
+        for chunk in data.chunks(PAYLOAD_SIZE) {
+            let mut packet = Packet([0xFF; PACKET_SIZE]);
+            let pid = self.pid.get();
+            packet.0[0] = 0x47;
+            packet.0[1] = ((unit_start as u8) << 6) | (pid >> 8) as u8;
+            packet.0[2] = pid as u8;
+
+            let payload_start = if af_stuffing && chunk.len() < PAYLOAD_SIZE {
+                // アダプテーションフィールドあり・ペイロードあり
+                packet.0[3] = 0b0011_0000 | self.cc;
+
+                let af_size = PAYLOAD_SIZE - chunk.len();
+                // adaptation_field_length
+                packet.0[4] = (af_size - 1) as u8;
+                if af_size > 1 {
+                    // 各種フラグは全て0、残りはスタッフィングバイト
+                    packet.0[5] = 0x00;
+                }
+                HEADER_SIZE + af_size
+            } else {
+                // ペイロードのみ
+                packet.0[3] = 0b0001_0000 | self.cc;
+                HEADER_SIZE
+            };
+            packet.0[payload_start..payload_start + chunk.len()].copy_from_slice(chunk);
+
+            w.write_packet(&packet)?;
+
+            self.cc = (self.cc + 1) & 0x0F;
+            unit_start = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// PSIセクションをTSパケットに分割して書き出すためのライター。
+#[derive(Debug, Clone)]
+pub struct PsiSectionWriter {
+    inner: Packetizer,
+}
+
+impl PsiSectionWriter {
+    /// `pid`のパケットとしてPSIセクションを書き出す`PsiSectionWriter`を生成する。
+    ///
+    /// 連続性指標は0から始まる。
+    #[inline]
+    pub fn new(pid: Pid) -> PsiSectionWriter {
+        PsiSectionWriter {
+            inner: Packetizer::new(pid),
+        }
+    }
+
+    /// 書き出すパケットのPID。
+    #[inline]
+    pub fn pid(&self) -> Pid {
+        self.inner.pid
+    }
+
+    /// 次に書き出すパケットの連続性指標。
+    #[inline]
+    pub fn continuity_counter(&self) -> u8 {
+        self.inner.cc
+    }
+
+    /// 次に書き出すパケットの連続性指標を設定する。
+    ///
+    /// `cc`は下位4ビットのみが使われる。
+    #[inline]
+    pub fn set_continuity_counter(&mut self, cc: u8) {
+        self.inner.cc = cc & 0x0F;
+    }
+
+    /// CRC_32を除くPSIセクションを書き出す。
+    ///
+    /// `section`はtable_idから始まり、CRC_32の直前までを含むものとする。
+    /// section_lengthとCRC_32はこのメソッドによって計算される。
+    ///
+    /// `section`が3バイトに満たない場合や、section_lengthが12ビットに収まらない場合は
+    /// [`io::ErrorKind::InvalidInput`]のエラーを返す。
+    pub fn write_section<W: Write>(
+        &mut self,
+        w: &mut TsWriter<W>,
+        section: &[u8],
+    ) -> io::Result<()> {
+        if section.len() < 3 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too short PSI section",
+            ));
+        }
+        let section_length = section.len() - 3 + 4;
+        if section_length > 0x0FFF {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too long PSI section",
+            ));
+        }
+
+        let mut buf = Vec::with_capacity(1 + section.len() + 4);
+        // pointer_field
+        buf.push(0x00);
+        buf.extend_from_slice(section);
+        buf[2] = (buf[2] & 0xF0) | (section_length >> 8) as u8;
+        buf[3] = section_length as u8;
+
+        let crc32 = crc::digest32(crc::INIT, &buf[1..]);
+        buf.extend_from_slice(&crc32.to_be_bytes());
+
+        self.inner.write(w, &buf, false)
+    }
+}
+
+/// ESのペイロードをPESパケットとしてTSパケットに分割して書き出すためのライター。
+#[derive(Debug, Clone)]
+pub struct PesWriter {
+    inner: Packetizer,
+}
+
+impl PesWriter {
+    /// `pid`のパケットとしてPESパケットを書き出す`PesWriter`を生成する。
+    ///
+    /// 連続性指標は0から始まる。
+    #[inline]
+    pub fn new(pid: Pid) -> PesWriter {
+        PesWriter {
+            inner: Packetizer::new(pid),
+        }
+    }
+
+    /// 書き出すパケットのPID。
+    #[inline]
+    pub fn pid(&self) -> Pid {
+        self.inner.pid
+    }
+
+    /// 次に書き出すパケットの連続性指標。
+    #[inline]
+    pub fn continuity_counter(&self) -> u8 {
+        self.inner.cc
+    }
+
+    /// 次に書き出すパケットの連続性指標を設定する。
+    ///
+    /// `cc`は下位4ビットのみが使われる。
+    #[inline]
+    pub fn set_continuity_counter(&mut self, cc: u8) {
+        self.inner.cc = cc & 0x0F;
+    }
+
+    /// `payload`をPESパケットとして書き出す。
+    ///
+    /// PESパケット長が16ビットに収まらない場合、PES_packet_lengthは0（長さ未規定）となる。
+    pub fn write_pes<W: Write>(
+        &mut self,
+        w: &mut TsWriter<W>,
+        stream_id: StreamId,
+        pts: Option<Timestamp>,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        let header_data_length = if pts.is_some() { 5 } else { 0 };
+
+        let mut buf = Vec::with_capacity(9 + header_data_length + payload.len());
+        buf.extend_from_slice(&[0x00, 0x00, 0x01, stream_id.0]);
+
+        let pes_packet_length = 3 + header_data_length + payload.len();
+        let pes_packet_length = u16::try_from(pes_packet_length).unwrap_or(0);
+        buf.extend_from_slice(&pes_packet_length.to_be_bytes());
+
+        // '10'、各種フラグは全て0
+        buf.push(0b1000_0000);
+        match pts {
+            Some(pts) => {
+                // PTS_DTS_flags = '10'
+                buf.push(0b1000_0000);
+                buf.push(header_data_length as u8);
+
+                let base = pts.base();
+                buf.extend_from_slice(&[
+                    0b0010_0001 | ((base >> 29) as u8 & 0b0000_1110),
+                    (base >> 22) as u8,
+                    ((base >> 14) as u8 & 0b1111_1110) | 0b0000_0001,
+                    (base >> 7) as u8,
+                    ((base << 1) as u8 & 0b1111_1110) | 0b0000_0001,
+                ]);
+            }
+            None => {
+                buf.push(0b0000_0000);
+                buf.push(0);
+            }
+        }
+
+        buf.extend_from_slice(payload);
+
+        self.inner.write(w, &buf, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pes::PesPacket;
+    use crate::psi::PsiSection;
+
+    fn packets(buf: &[u8]) -> Vec<Packet> {
+        buf.chunks(PACKET_SIZE)
+            .map(|chunk| Packet(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_psi_round_trip() {
+        let mut original = Packet([0xFF; PACKET_SIZE]);
+        let pat = hex_literal::hex!(
+            "47 40 00 15 00 00 B0 11 00 01 C1 00 00 00 00 E0 10 04 00 E1 01 E7 4A 99 A3"
+        );
+        original.0[..pat.len()].copy_from_slice(&pat);
+
+        let payload = original.payload().unwrap();
+        let pointer_field = payload[0] as usize;
+        let (psi, len) = PsiSection::parse(&payload[1 + pointer_field..]).unwrap();
+        assert_eq!(psi.table_id, 0x00);
+        assert_eq!(psi.crc32, 0xE74A99A3);
+        let section = &payload[1 + pointer_field..][..len - 4];
+
+        let mut writer = PsiSectionWriter::new(original.pid());
+        writer.set_continuity_counter(original.continuity_counter());
+        let mut w = TsWriter::new(Vec::new());
+        writer.write_section(&mut w, section).unwrap();
+
+        assert_eq!(w.get_ref()[..], original.0[..]);
+        assert_eq!(writer.continuity_counter(), 6);
+
+        // section_lengthは書き直される
+        let mut section = section.to_vec();
+        section[1] = 0xB0;
+        section[2] = 0x00;
+        let mut w = TsWriter::new(Vec::new());
+        writer.set_continuity_counter(5);
+        writer.write_section(&mut w, &section).unwrap();
+        assert_eq!(w.get_ref()[..], original.0[..]);
+    }
+
+    #[test]
+    fn test_psi_multi_packets() {
+        let mut section = vec![0x42, 0xF0, 0x00];
+        section.extend((0..400).map(|i| i as u8));
+
+        let mut writer = PsiSectionWriter::new(Pid::SDT);
+        writer.set_continuity_counter(15);
+        let mut w = TsWriter::new(Vec::new());
+        writer.write_section(&mut w, &section).unwrap();
+
+        let packets = packets(w.get_ref());
+        assert_eq!(packets.len(), 3);
+        assert!(packets[0].unit_start_indicator());
+        assert!(!packets[1].unit_start_indicator());
+        assert_eq!(
+            packets
+                .iter()
+                .map(|p| p.continuity_counter())
+                .collect::<Vec<_>>(),
+            [15, 0, 1],
+        );
+        assert!(packets.iter().all(|p| p.pid() == Pid::SDT));
+
+        let buf: Vec<u8> = packets
+            .iter()
+            .flat_map(|p| p.payload().unwrap())
+            .copied()
+            .collect();
+        let (psi, len) = PsiSection::parse(&buf[1..]).unwrap();
+        assert_eq!(len, 403 + 4);
+        assert_eq!(psi.table_id, 0x42);
+        assert!(buf[1 + len..].iter().all(|&b| b == 0xFF));
+
+        let mut w = TsWriter::new(Vec::new());
+        assert_eq!(
+            writer
+                .write_section(&mut w, &[0x42, 0xF0])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput,
+        );
+        assert_eq!(
+            writer.write_section(&mut w, &[0; 4096]).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput,
+        );
+        assert!(w.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_pes_round_trip() {
+        let pts = Timestamp::new(0x1_2345_6789, 0);
+        for len in [0, 1, 169, 170, 171, 300, 1000] {
+            let payload: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+            let mut writer = PesWriter::new(Pid::new(0x0111));
+            let mut w = TsWriter::new(Vec::new());
+            writer
+                .write_pes(&mut w, StreamId(0xE0), Some(pts), &payload)
+                .unwrap();
+
+            let packets = packets(w.get_ref());
+            assert!(packets[0].unit_start_indicator());
+            assert!(packets.iter().all(|p| p.is_normal()));
+            for (i, packet) in packets.iter().enumerate() {
+                assert_eq!(packet.continuity_counter(), i as u8);
+            }
+
+            let buf: Vec<u8> = packets
+                .iter()
+                .flat_map(|p| p.payload().unwrap())
+                .copied()
+                .collect();
+            let pes = PesPacket::parse(&buf).unwrap();
+            assert_eq!(buf.len(), 14 + len);
+            assert_eq!(pes.header.stream_id, StreamId(0xE0));
+            assert_eq!(pes.header.option.unwrap().pts, Some(pts));
+            assert_eq!(pes.data, &*payload);
+        }
+
+        // PTS無し
+        let mut writer = PesWriter::new(Pid::new(0x0130));
+        let mut w = TsWriter::new(Vec::new());
+        writer
+            .write_pes(&mut w, StreamId(0xBD), None, b"abc")
+            .unwrap();
+        let packets = packets(w.get_ref());
+        let pes = PesPacket::parse(packets[0].payload().unwrap()).unwrap();
+        assert_eq!(pes.header.option.unwrap().pts, None);
+        assert_eq!(pes.data, b"abc");
+    }
+}
@@ -3,7 +3,11 @@
 pub mod audio;
 pub mod video;
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops;
+
+use isdb::psi::desc::StreamType;
 
 /// 有理数。
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -48,3 +52,357 @@ impl<T: fmt::Display> fmt::Debug for Rational<T> {
         write!(f, "{}/{}", self.numerator, self.denominator)
     }
 }
+
+macro_rules! impl_integer {
+    ($($t:ty => $wide:ty),*) => {$(
+        impl Rational<$t> {
+            /// 分子と分母を最大公約数で割り、分母を正とした既約分数にした値を返す。
+            ///
+            /// 分母が`0`の場合や、既約分数が`$t`で表現できない場合（`i32::MIN / -1`など）は
+            /// そのままの値を返す。
+            #[allow(unused_comparisons)]
+            pub fn reduce(&self) -> Rational<$t> {
+                fn gcd(mut a: $wide, mut b: $wide) -> $wide {
+                    while b != 0 {
+                        (a, b) = (b, a % b);
+                    }
+                    a
+                }
+
+                if self.denominator == 0 {
+                    return *self;
+                }
+
+                // 符号反転や除算が溢れないよう幅の広い整数型で計算する
+                let n = self.numerator as $wide;
+                let d = self.denominator as $wide;
+                let mut g = gcd(n, d);
+                // 符号付き整数では分母を正とする
+                if (g < 0) != (d < 0) {
+                    g = 0 - g;
+                }
+                match (<$t>::try_from(n / g), <$t>::try_from(d / g)) {
+                    (Ok(n), Ok(d)) => Rational::new(n, d),
+                    _ => *self,
+                }
+            }
+        }
+
+        impl PartialOrd for Rational<$t> {
+            #[inline]
+            fn partial_cmp(&self, other: &Rational<$t>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// 分母を正に揃え、分子と分母を互いに掛け合わせて値を比較する。
+        ///
+        /// 値が等しい場合は分子、分母の順に比較するため、`PartialEq`と矛盾しない。
+        /// 分母が`0`の値の順序は未規定である。
+        impl Ord for Rational<$t> {
+            fn cmp(&self, other: &Rational<$t>) -> Ordering {
+                // 積が溢れないよう幅の広い整数型で計算する
+                #[allow(unused_comparisons)]
+                fn normalize(r: &Rational<$t>) -> ($wide, $wide) {
+                    let n = r.numerator as $wide;
+                    let d = r.denominator as $wide;
+                    if d < 0 {
+                        (0 - n, 0 - d)
+                    } else {
+                        (n, d)
+                    }
+                }
+
+                let (ln, ld) = normalize(self);
+                let (rn, rd) = normalize(other);
+                (ln * rd)
+                    .cmp(&(rn * ld))
+                    .then_with(|| self.numerator.cmp(&other.numerator))
+                    .then_with(|| self.denominator.cmp(&other.denominator))
+            }
+        }
+    )*};
+}
+
+impl_integer!(
+    u8 => u16,
+    u16 => u32,
+    u32 => u64,
+    u64 => u128,
+    usize => u128,
+    i8 => i16,
+    i16 => i32,
+    i32 => i64,
+    i64 => i128,
+    isize => i128
+);
+
+impl<T> ops::Add for Rational<T>
+where
+    T: ops::Add<Output = T> + ops::Mul<Output = T> + Copy,
+{
+    type Output = Rational<T>;
+
+    /// 有理数の和を返す。結果は既約分数にはならない。
+    #[inline]
+    fn add(self, rhs: Rational<T>) -> Rational<T> {
+        Rational::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T> ops::Sub for Rational<T>
+where
+    T: ops::Sub<Output = T> + ops::Mul<Output = T> + Copy,
+{
+    type Output = Rational<T>;
+
+    /// 有理数の差を返す。結果は既約分数にはならない。
+    #[inline]
+    fn sub(self, rhs: Rational<T>) -> Rational<T> {
+        Rational::new(
+            self.numerator * rhs.denominator - rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T> ops::Mul for Rational<T>
+where
+    T: ops::Mul<Output = T>,
+{
+    type Output = Rational<T>;
+
+    /// 有理数の積を返す。結果は既約分数にはならない。
+    #[inline]
+    fn mul(self, rhs: Rational<T>) -> Rational<T> {
+        Rational::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T> ops::Div for Rational<T>
+where
+    T: ops::Mul<Output = T>,
+{
+    type Output = Rational<T>;
+
+    /// 有理数の商を返す。結果は既約分数にはならない。
+    #[inline]
+    fn div(self, rhs: Rational<T>) -> Rational<T> {
+        Rational::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+/// コーデックの種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecKind {
+    /// 映像。
+    Video,
+    /// 音声。
+    Audio,
+    /// 字幕・文字スーパー。
+    Caption,
+}
+
+/// コーデックの情報。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CodecInfo {
+    /// コーデックの名前。
+    pub name: &'static str,
+    /// コーデックの種類。
+    pub kind: CodecKind,
+}
+
+/// ストリーム形式種別からコーデックの情報を引くための一覧。
+#[derive(Debug)]
+pub struct CodecRegistry;
+
+impl CodecRegistry {
+    const CODECS: &'static [(StreamType, CodecInfo)] = &[
+        (
+            StreamType::MPEG2_VIDEO,
+            CodecInfo {
+                name: "MPEG-2 Video",
+                kind: CodecKind::Video,
+            },
+        ),
+        (
+            StreamType::H264,
+            CodecInfo {
+                name: "H.264/AVC",
+                kind: CodecKind::Video,
+            },
+        ),
+        (
+            StreamType::H265,
+            CodecInfo {
+                name: "H.265/HEVC",
+                kind: CodecKind::Video,
+            },
+        ),
+        (
+            StreamType::AAC,
+            CodecInfo {
+                name: "MPEG-2 AAC",
+                kind: CodecKind::Audio,
+            },
+        ),
+        (
+            StreamType::MPEG4_AUDIO,
+            CodecInfo {
+                name: "MPEG-4 AAC",
+                kind: CodecKind::Audio,
+            },
+        ),
+        (
+            StreamType::AC3,
+            CodecInfo {
+                name: "AC-3",
+                kind: CodecKind::Audio,
+            },
+        ),
+        (
+            StreamType::CAPTION,
+            CodecInfo {
+                name: "ARIB字幕",
+                kind: CodecKind::Caption,
+            },
+        ),
+    ];
+
+    /// `st`に対応するコーデックの情報を返す。
+    ///
+    /// 未対応のストリーム形式種別の場合は`None`を返す。
+    pub fn for_stream_type(st: StreamType) -> Option<&'static CodecInfo> {
+        Self::CODECS
+            .iter()
+            .find(|(stream_type, _)| *stream_type == st)
+            .map(|(_, info)| info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rational_frame_rate() {
+        let fps24 = Rational::<u32>::new(24, 1);
+        let fps23_976 = Rational::<u32>::new(24000, 1001);
+        let fps29_97 = Rational::<u32>::new(30000, 1001);
+        let fps30 = Rational::<u32>::new(30, 1);
+        let fps59_94 = Rational::<u32>::new(60000, 1001);
+
+        assert!(fps23_976 < fps24);
+        assert!(fps24 < fps29_97);
+        assert!(fps29_97 < fps30);
+        assert!(fps30 < fps59_94);
+        assert_eq!(fps29_97.partial_cmp(&fps29_97), Some(Ordering::Equal));
+
+        // 値が等しくても表現が異なれば等しくない
+        assert_ne!(Rational::<u32>::new(60, 2), fps30);
+        assert!(Rational::<u32>::new(60, 2) > fps30);
+        assert!(Rational::<u32>::new(60, 2) < fps59_94);
+        assert_eq!(Rational::<u32>::new(60, 2).reduce(), fps30);
+
+        assert_eq!((fps29_97 + fps29_97).reduce(), fps59_94);
+        assert_eq!((fps59_94 - fps29_97).reduce(), fps29_97);
+        assert_eq!(
+            (fps29_97 * Rational::new(2, 1)).reduce(),
+            Rational::new(60000, 1001)
+        );
+        assert_eq!((fps59_94 / fps29_97).reduce(), Rational::new(2, 1));
+    }
+
+    #[test]
+    fn test_rational_reduce() {
+        assert_eq!(
+            Rational::<u32>::new(1920, 1080).reduce(),
+            Rational::new(16, 9)
+        );
+        assert_eq!(Rational::<u32>::new(0, 5).reduce(), Rational::new(0, 1));
+        assert_eq!(Rational::<u32>::new(3, 0).reduce(), Rational::new(3, 0));
+        assert_eq!(Rational::<i32>::new(4, -6).reduce(), Rational::new(-2, 3));
+        assert_eq!(Rational::<i32>::new(-4, -6).reduce(), Rational::new(2, 3));
+        assert_eq!(Rational::<i32>::new(-4, 6).reduce(), Rational::new(-2, 3));
+        // 既約分数が表現できない場合はそのまま
+        assert_eq!(
+            Rational::<i32>::new(i32::MIN, -1).reduce(),
+            Rational::new(i32::MIN, -1)
+        );
+        assert_eq!(
+            Rational::<i32>::new(i32::MIN, i32::MIN).reduce(),
+            Rational::new(1, 1)
+        );
+        assert_eq!(
+            Rational::<i64>::new(i64::MIN, 2).reduce(),
+            Rational::new(i64::MIN / 2, 1)
+        );
+    }
+
+    #[test]
+    fn test_rational_ord() {
+        assert!(Rational::<u32>::new(1, 3) < Rational::new(1, 2));
+        assert!(Rational::<u32>::new(1, 2) < Rational::new(2, 4));
+        assert_eq!(
+            Rational::<u32>::new(2, 4).cmp(&Rational::new(2, 4)),
+            Ordering::Equal
+        );
+
+        // 分母が負でも値で比較する
+        assert!(Rational::<i32>::new(1, -2) < Rational::new(1, 3));
+        assert!(Rational::<i32>::new(-1, -2) > Rational::new(1, 3));
+        assert!(Rational::<i32>::new(-1, 2) < Rational::new(1, -2));
+        assert!(Rational::<i32>::new(i32::MIN, -1) > Rational::new(i32::MAX, 1));
+
+        // 掛け合わせても溢れない
+        assert!(Rational::<u32>::new(u32::MAX, 1) > Rational::new(u32::MAX - 1, 1));
+        assert!(Rational::<u32>::new(u32::MAX - 1, u32::MAX) < Rational::new(1, 1));
+        assert!(Rational::<i64>::new(i64::MIN, 1) < Rational::new(i64::MIN + 1, 1));
+    }
+
+    #[test]
+    fn test_codec_registry() {
+        assert_eq!(
+            CodecRegistry::for_stream_type(StreamType::H264),
+            Some(&CodecInfo {
+                name: "H.264/AVC",
+                kind: CodecKind::Video,
+            }),
+        );
+        assert_eq!(
+            CodecRegistry::for_stream_type(StreamType::AAC),
+            Some(&CodecInfo {
+                name: "MPEG-2 AAC",
+                kind: CodecKind::Audio,
+            }),
+        );
+        assert_eq!(
+            CodecRegistry::for_stream_type(StreamType::CAPTION).map(|info| info.kind),
+            Some(CodecKind::Caption),
+        );
+        assert_eq!(
+            CodecRegistry::for_stream_type(StreamType::MPEG1_VIDEO),
+            None
+        );
+        assert_eq!(CodecRegistry::for_stream_type(StreamType::INVALID), None);
+
+        // 種類はストリーム形式種別の分類と一致する
+        for st in (0..=u8::MAX).map(StreamType) {
+            let Some(info) = CodecRegistry::for_stream_type(st) else {
+                continue;
+            };
+            assert_eq!(info.kind == CodecKind::Video, st.is_video(), "{}", st);
+            assert_eq!(info.kind == CodecKind::Audio, st.is_audio(), "{}", st);
+            assert_eq!(info.kind == CodecKind::Caption, st.is_caption(), "{}", st);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! 非同期の読み取り元から`Extractor`を使うための拡張。
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+use tokio::runtime::Handle;
+
+use super::{Extractor, Sink};
+
+/// 非同期の読み取り元を同期的に読み取るための[`Read`]・[`Seek`]。
+///
+/// ブロッキング可能なスレッドでのみ使用できる。
+struct BlockingRead<R> {
+    inner: R,
+    handle: Handle,
+}
+
+impl<R: AsyncRead + Unpin> Read for BlockingRead<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.handle.block_on(self.inner.read(buf))
+    }
+}
+
+impl<R: AsyncSeek + Unpin> Seek for BlockingRead<R> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.handle.block_on(self.inner.seek(pos))
+    }
+}
+
+impl Extractor {
+    /// 指定された非同期の読み取り元と処理用`Sink`を使い、
+    /// Tokioのブロッキング用スレッドで`Extractor`の処理を開始する。
+    ///
+    /// Tokioのランタイム上で呼び出す必要があり、そうでない場合はパニックする。
+    ///
+    /// 戻り値の[`JoinHandle`][tokio::task::JoinHandle]を使って終了待ちができるが、
+    /// 処理を終了させるためには事前に[`ExtractHandler::shutdown`][super::ExtractHandler::shutdown]
+    /// を呼び出す必要がある。
+    pub fn spawn_async<R, T>(self, read: R, sink: T) -> tokio::task::JoinHandle<()>
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + 'static,
+        T: Sink + Send + 'static,
+    {
+        let read = BlockingRead {
+            inner: read,
+            handle: Handle::current(),
+        };
+        let worker = self.into_worker(read, sink);
+        tokio::task::spawn_blocking(move || worker.run())
+    }
+}
@@ -0,0 +1,146 @@
+//! トレイトオブジェクトとしての[`Sink`]。
+
+use std::io;
+use std::time::Duration;
+
+use isdb::filters::sorter::{Caption, Service, ServiceMap};
+use isdb::packet::TransportScramblingControl;
+use isdb::time::Timestamp;
+use isdb::Pid;
+
+use super::{ExtractorError, Sink, StreamChanged};
+
+/// [`Box`]に包まれたトレイトオブジェクトの[`Sink`]を、型の決まった`Sink`として扱う。
+///
+/// 実行時に`Sink`を組み立てる場合など、具体的な型を[`Extractor`][super::Extractor]に渡せない場合に使う。
+pub struct DynSink(Box<dyn Sink + Send>);
+
+impl DynSink {
+    /// `sink`を包んだ`DynSink`を生成する。
+    #[inline]
+    pub fn new<T: Sink + Send + 'static>(sink: T) -> DynSink {
+        DynSink(Box::new(sink))
+    }
+
+    /// [`Box`]に包まれた`sink`から`DynSink`を生成する。
+    #[inline]
+    pub fn from_box(sink: Box<dyn Sink + Send>) -> DynSink {
+        DynSink(sink)
+    }
+
+    /// 内包する`Sink`を返す。
+    #[inline]
+    pub fn into_inner(self) -> Box<dyn Sink + Send> {
+        self.0
+    }
+}
+
+impl std::fmt::Debug for DynSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("DynSink")
+    }
+}
+
+impl From<Box<dyn Sink + Send>> for DynSink {
+    #[inline]
+    fn from(sink: Box<dyn Sink + Send>) -> DynSink {
+        DynSink(sink)
+    }
+}
+
+impl Sink for DynSink {
+    #[inline]
+    fn on_services_updated(&mut self, services: &ServiceMap) {
+        self.0.on_services_updated(services);
+    }
+
+    #[inline]
+    fn on_streams_updated(&mut self, service: &Service) {
+        self.0.on_streams_updated(service);
+    }
+
+    #[inline]
+    fn on_event_updated(&mut self, service: &Service, is_present: bool) {
+        self.0.on_event_updated(service, is_present);
+    }
+
+    #[inline]
+    fn on_service_changed(&mut self, service: &Service) {
+        self.0.on_service_changed(service);
+    }
+
+    #[inline]
+    fn on_stream_changed(&mut self, immediate: bool, changed: StreamChanged) {
+        self.0.on_stream_changed(immediate, changed);
+    }
+
+    #[inline]
+    fn on_video_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        self.0.on_video_packet(pos, payload);
+    }
+
+    #[inline]
+    fn on_audio_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        self.0.on_audio_packet(pos, payload);
+    }
+
+    #[inline]
+    fn on_caption(&mut self, pos: Option<Duration>, caption: &Caption) {
+        self.0.on_caption(pos, caption);
+    }
+
+    #[inline]
+    fn on_superimpose(&mut self, pos: Option<Duration>, caption: &Caption) {
+        self.0.on_superimpose(pos, caption);
+    }
+
+    #[inline]
+    fn on_scrambling_changed(&mut self, pid: Pid, new_state: TransportScramblingControl) {
+        self.0.on_scrambling_changed(pid, new_state);
+    }
+
+    #[inline]
+    fn on_scrambled_packet(&mut self, pid: Pid) {
+        self.0.on_scrambled_packet(pid);
+    }
+
+    #[inline]
+    fn on_pcr_discontinuity(&mut self, old: Timestamp, new: Timestamp) {
+        self.0.on_pcr_discontinuity(old, new);
+    }
+
+    #[inline]
+    fn on_bitrate_updated(&mut self, video_kbps: u32, audio_kbps: u32) {
+        self.0.on_bitrate_updated(video_kbps, audio_kbps);
+    }
+
+    #[inline]
+    fn on_volume_requested(&mut self, volume: f32) {
+        self.0.on_volume_requested(volume);
+    }
+
+    #[inline]
+    fn on_timestamp_updated(&mut self, timestamp: Duration) {
+        self.0.on_timestamp_updated(timestamp);
+    }
+
+    #[inline]
+    fn on_end_of_stream(&mut self) {
+        self.0.on_end_of_stream();
+    }
+
+    #[inline]
+    fn on_stream_error(&mut self, error: io::Error) {
+        self.0.on_stream_error(error);
+    }
+
+    #[inline]
+    fn on_seek_error(&mut self, error: ExtractorError) {
+        self.0.on_seek_error(error);
+    }
+
+    #[inline]
+    fn needs_es(&self) -> bool {
+        self.0.needs_es()
+    }
+}
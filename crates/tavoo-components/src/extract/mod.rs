@@ -1,22 +1,33 @@
 //! TSファイルを別スレッドで順次処理する。
 
+#[cfg(feature = "tokio")]
+mod async_ext;
+mod boxed;
 mod caption;
+mod multi;
 
+use std::collections::hash_map::Entry;
 use std::fmt;
 use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
+use fxhash::FxHashMap;
 use isdb::filters::sorter::{Service, ServiceMap, Stream};
+use isdb::lang::LangCode;
 use isdb::psi::table::ServiceId;
 use isdb::time::{DateTime, Timestamp};
 use parking_lot::RwLock;
 
 use crate::ring_buf::RingBuf;
 
+pub use boxed::DynSink;
+pub use multi::{multi_sink, multi_sink_vec, MultiSink, MultiSinkVec};
+
 /// 映像・音声ストリームの変更通知。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct StreamChanged {
     /// 映像ストリームのPIDが変わった。
     pub video_pid: bool,
@@ -54,6 +65,18 @@ impl StreamChanged {
     fn any(&self) -> bool {
         self.video_pid || self.video_type || self.audio_pid || self.audio_type
     }
+
+    /// 映像・音声いずれかのストリーム形式が変わり、デコーダーを作り直す必要があるかどうか。
+    #[inline]
+    pub fn is_codec_change(&self) -> bool {
+        self.video_type || self.audio_type
+    }
+
+    /// ストリーム形式は変わらず、PIDのみが変わったかどうか。
+    #[inline]
+    pub fn is_pid_only_change(&self) -> bool {
+        (self.video_pid || self.audio_pid) && !self.is_codec_change()
+    }
 }
 
 /// [`Extractor`]で処理されたTSの情報を受け取るためのトレイト。
@@ -90,6 +113,58 @@ pub trait Sink {
     /// 選択中サービスで文字スーパーのパケットを受信した際に呼ばれる。
     fn on_superimpose(&mut self, pos: Option<Duration>, caption: &isdb::filters::sorter::Caption);
 
+    /// 選択中サービスの映像・音声ストリームにおけるスクランブル状態が変化した際に呼ばれる。
+    ///
+    /// シーク中に変化した場合はシーク完了時に最新の状態で呼ばれる。
+    ///
+    /// 既定では何もしない。
+    fn on_scrambling_changed(
+        &mut self,
+        pid: isdb::Pid,
+        new_state: isdb::packet::TransportScramblingControl,
+    ) {
+        let _ = (pid, new_state);
+    }
+
+    /// 選択中サービスの映像・音声ストリームでスクランブルされたパケットを受信した際に呼ばれる。
+    ///
+    /// 同じPIDについては1秒に1回まで呼ばれる。シーク中は呼ばれない。
+    ///
+    /// 既定では何もしない。
+    fn on_scrambled_packet(&mut self, pid: isdb::Pid) {
+        let _ = pid;
+    }
+
+    /// 既定サービスのPCRが不連続になった際に呼ばれる。
+    ///
+    /// 連続するPCRの差が[`Extractor::with_discontinuity_threshold`]で設定した閾値を超えた場合に、
+    /// 直前のPCRである`old`と新しいPCRである`new`を引数として呼ばれる。
+    /// 差はラップアラウンドを考慮して計算されるため、通常のラップアラウンドでは呼ばれない。
+    /// シーク中は呼ばれない。
+    ///
+    /// 既定では何もしない。
+    fn on_pcr_discontinuity(&mut self, old: Timestamp, new: Timestamp) {
+        let _ = (old, new);
+    }
+
+    /// 選択中サービスにおける映像・音声のビットレートの推定値が変化した際に呼ばれる。
+    ///
+    /// ビットレートは既定サービスのPCRを元に2秒ごとに計算され、
+    /// 前回通知した値から5%を超えて変化した場合にキロビット毎秒単位で通知される。
+    /// シーク中は呼ばれない。
+    ///
+    /// 既定では何もしない。
+    fn on_bitrate_updated(&mut self, video_kbps: u32, audio_kbps: u32) {
+        let _ = (video_kbps, audio_kbps);
+    }
+
+    /// [`ExtractHandler::set_volume`]によって音量の変更が指示された際に呼ばれる。
+    ///
+    /// 既定では何もしない。
+    fn on_volume_requested(&mut self, volume: f32) {
+        let _ = volume;
+    }
+
     /// TS内の日付時刻が更新された際に呼ばれる。ただし[`ExtractHandler::timestamp`]はより細かい間隔で更新される。
     ///
     /// `timestamp`は更新された日付時刻で、1900年1月1日からの経過時間によって表される。
@@ -103,6 +178,19 @@ pub trait Sink {
     /// TSの読み取りは終了する。
     fn on_stream_error(&mut self, error: io::Error);
 
+    /// [`ExtractHandler::set_position`]等によるシークが正常に完了しなかった際に呼ばれる。
+    ///
+    /// シーク中にIOエラーが発生した場合は[`ExtractorError::IoError`]が渡され、
+    /// この直後に[`Sink::on_stream_error`]が呼ばれる。
+    ///
+    /// 前回のシークが完了する前に新たなシークが指示された場合は[`ExtractorError::SeekInProgress`]が渡される。
+    /// この場合、前回のシークは中断され新たなシークが行われる。
+    ///
+    /// 既定では何もしない。
+    fn on_seek_error(&mut self, error: ExtractorError) {
+        let _ = error;
+    }
+
     /// ESを読み取る必要があるかどうかを返す。
     ///
     /// このメソッドが`false`を返すとESの読み取りが一時停止する。
@@ -132,31 +220,200 @@ struct Commands {
     // 0は指示無し、1は`None`、2～は`Some(ServiceId)`
     select_service: AtomicU32,
     /// 映像ストリームを選択する。
-    // 0は指示無し、1～は`Some(u8)`
+    // 0は指示無し、1～は`Some(u8)`、`VIDEO_STREAM_BY_PID`のビットが立っている場合は`Some(Pid)`で、
+    // コンポーネントタグとPIDのどちらで指示されても後から指示された方だけが処理される
     select_video_stream: AtomicU16,
     /// 音声ストリームを選択する。
     // 0は指示無し、1～は`Some(u8)`
     select_audio_stream: AtomicU16,
     /// 再生位置の秒成分を設定する。
     // 0は指示無し、1～は`Some(秒)`
+    // `POSITION_BY_TIMESTAMP`のビットが立っている場合はTOTによる日付時刻（1900年1月1日からの経過時間）で、
+    // 再生位置と日付時刻のどちらで指示されても後から指示された方だけが処理される
     set_position_secs: AtomicU64,
     /// 再生位置のナノ秒成分を設定する。
     // 指示の有無はset_position_secsで指定する
     set_position_nanos: AtomicU32,
+    /// 音量を設定する。
+    // 0は指示無し、1～は`Some(f32のビット表現)`に1を足したもの
+    set_volume: AtomicU64,
     /// TSをリセットして最初から再生する。
     reset: AtomicBool,
     /// TSの読み取りを終了する。
     shutdown: AtomicBool,
 }
 
+/// [`Commands`]で指示する映像ストリームの選択。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoStreamSelection {
+    /// コンポーネントタグによる選択。
+    ComponentTag(u8),
+    /// PIDによる選択。
+    Pid(isdb::Pid),
+}
+
+impl Commands {
+    /// `select_video_stream`がPIDによる指示であることを示すビット。
+    const VIDEO_STREAM_BY_PID: u16 = 1 << 15;
+
+    /// 映像ストリームの選択を指示する。
+    fn store_video_stream(&self, selection: VideoStreamSelection) {
+        let value = match selection {
+            VideoStreamSelection::ComponentTag(component_tag) => component_tag as u16 + 1,
+            // PIDは13ビットのため最上位ビットとは重ならない
+            VideoStreamSelection::Pid(pid) => pid.get() | Self::VIDEO_STREAM_BY_PID,
+        };
+        self.select_video_stream.store(value, Ordering::SeqCst);
+    }
+
+    /// 映像ストリームの選択指示を取り出す。
+    fn take_video_stream(&self) -> Option<VideoStreamSelection> {
+        match self.select_video_stream.swap(0, Ordering::SeqCst) {
+            0 => None,
+            v if v & Self::VIDEO_STREAM_BY_PID != 0 => Some(VideoStreamSelection::Pid(
+                isdb::Pid::new(v & !Self::VIDEO_STREAM_BY_PID),
+            )),
+            v => Some(VideoStreamSelection::ComponentTag((v - 1) as u8)),
+        }
+    }
+
+    /// `set_position_secs`がTOTによる日付時刻での指示であることを示すビット。
+    const POSITION_BY_TIMESTAMP: u64 = 1 << 63;
+
+    /// 再生位置の設定を指示する。
+    ///
+    /// `by_timestamp`が`true`の場合、`pos`はTOTによる日付時刻である。
+    fn store_position(&self, pos: Duration, by_timestamp: bool) {
+        // 秒が`u64::MAX`になるようなシークはしないと思われ
+        let secs = pos
+            .as_secs()
+            .saturating_add(1)
+            .min(Self::POSITION_BY_TIMESTAMP - 1);
+        let secs = if by_timestamp {
+            secs | Self::POSITION_BY_TIMESTAMP
+        } else {
+            secs
+        };
+        self.set_position_secs.store(secs, Ordering::SeqCst);
+        self.set_position_nanos
+            .store(pos.subsec_nanos(), Ordering::SeqCst);
+    }
+
+    /// 再生位置の設定指示を取り出す。
+    ///
+    /// 指示がある場合、再生位置とTOTによる日付時刻での指示かどうかを返す。
+    fn take_position(&self) -> Option<(Duration, bool)> {
+        let secs = self.set_position_secs.swap(0, Ordering::SeqCst);
+        if secs == 0 {
+            return None;
+        }
+
+        let nanos = self.set_position_nanos.load(Ordering::SeqCst);
+        let by_timestamp = secs & Self::POSITION_BY_TIMESTAMP != 0;
+        let secs = secs & !Self::POSITION_BY_TIMESTAMP;
+        Some((Duration::new(secs - 1, nanos), by_timestamp))
+    }
+
+    /// 音量の設定を指示する。
+    fn store_volume(&self, volume: f32) {
+        self.set_volume
+            .store(volume.to_bits() as u64 + 1, Ordering::SeqCst);
+    }
+
+    /// 音量の設定指示を取り出す。
+    fn take_volume(&self) -> Option<f32> {
+        match self.set_volume.swap(0, Ordering::SeqCst) {
+            0 => None,
+            v => Some(f32::from_bits((v - 1) as u32)),
+        }
+    }
+}
+
+/// 処理したパケットの統計情報。
+#[derive(Debug, Default)]
+struct PacketStats {
+    /// 処理したパケットの数。
+    packets: AtomicU64,
+    /// 処理したパケットの内、トランスポートエラーインジケーターが立っていたものの数。
+    errors: AtomicU64,
+}
+
+impl PacketStats {
+    /// `packet`を処理したことを記録する。
+    fn record(&self, packet: &isdb::Packet) {
+        self.packets.fetch_add(1, Ordering::Relaxed);
+        if packet.error_indicator() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 統計情報を初期化する。
+    fn reset(&self) {
+        self.packets.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+    }
+}
+
 /// 処理中のTSにおける状態。
 #[derive(Debug, Default)]
 struct State {
     // TODO: 追っかけ再生に対応
     length: Option<StreamLength>,
-    services: ServiceMap,
+    services: Arc<ServiceMap>,
     selected_stream: Option<SelectedStream>,
     tot: Option<Tot>,
+    /// 既定サービスのPCRを元にした再生位置。
+    ///
+    /// PCR受信の度にサービス一覧と同時に更新される。
+    position: Duration,
+    /// TOTが実時刻と一致しており、日付時刻として実時刻を使うかどうか。
+    realtime_clock: bool,
+    /// 再生位置と名前の組からなるブックマーク一覧。
+    bookmarks: Vec<(Duration, String)>,
+    /// TOTを受信した再生位置とその日付時刻（1900年1月1日からの経過時間）の組からなる履歴。
+    ///
+    /// シークを跨いでファイルを開いている間記録され続け、再生位置順に並ぶ。
+    #[cfg(feature = "chapter-detection")]
+    tot_history: Vec<(Duration, Duration)>,
+}
+
+impl State {
+    fn position(&self) -> Option<Duration> {
+        self.selected_stream.as_ref()?;
+        Some(self.position)
+    }
+
+    fn timestamp(&self) -> Option<Duration> {
+        let tot = self.tot.as_ref()?;
+        if self.realtime_clock {
+            if let Some(now) = now_timestamp() {
+                return Some(now);
+            }
+        }
+
+        let (_, default_service) = self.services.first()?;
+        let pcr = default_service.pcr()?;
+
+        Some(tot.timestamp(pcr))
+    }
+
+    /// 選択中サービスにおける現在のイベントの開始時刻を、再生位置に換算して返す。
+    fn event_start_position(&self) -> Option<Duration> {
+        let selected_stream = self.selected_stream.as_ref()?;
+        let event = self
+            .services
+            .get(&selected_stream.service_id)?
+            .present_event()?;
+        let start_ts = Duration::from_secs(event.start_time.ntp_timestamp());
+
+        let current_ts = self.timestamp()?;
+        let current_pos = self.position()?;
+        Some(if start_ts >= current_ts {
+            current_pos + (start_ts - current_ts)
+        } else {
+            current_pos.saturating_sub(current_ts - start_ts)
+        })
+    }
 }
 
 /// TSを処理するオブジェクト。
@@ -166,11 +423,17 @@ struct State {
 // 実際のところ、処理を行うのは`Worker`である。
 pub struct Extractor {
     state: Arc<RwLock<State>>,
+    services_version: Arc<AtomicU64>,
     commands: Arc<Commands>,
+    stats: Arc<PacketStats>,
     parker: crossbeam_utils::sync::Parker,
     capacity: usize,
     probe_size: u64,
     tail_probe_size: u64,
+    probe_duration: Option<Duration>,
+    rate_limit: u64,
+    realtime_clock_sync: bool,
+    discontinuity_threshold: Duration,
 }
 
 impl Extractor {
@@ -181,11 +444,17 @@ impl Extractor {
 
         Extractor {
             state,
+            services_version: Arc::new(AtomicU64::new(0)),
             commands,
+            stats: Arc::new(PacketStats::default()),
             parker: crossbeam_utils::sync::Parker::new(),
             capacity: 188 * 32,
             probe_size: 188 * 4096,
             tail_probe_size: 188 * 1024,
+            probe_duration: None,
+            rate_limit: 0,
+            realtime_clock_sync: false,
+            discontinuity_threshold: DEFAULT_DISCONTINUITY_THRESHOLD,
         }
     }
 
@@ -194,13 +463,23 @@ impl Extractor {
     pub fn handler(&self) -> ExtractHandler {
         ExtractHandler {
             state: self.state.clone(),
+            services_version: self.services_version.clone(),
             commands: self.commands.clone(),
+            stats: self.stats.clone(),
             unparker: self.parker.unparker().clone(),
         }
     }
 
     /// TSを読み取るのに使うバッファの容量を設定する。
     #[inline]
+    pub fn with_capacity(mut self, capacity: usize) -> Extractor {
+        self.capacity = capacity;
+        self
+    }
+
+    /// TSを読み取るのに使うバッファの容量を設定する。
+    #[deprecated(note = "`Extractor::with_capacity`を使う")]
+    #[inline]
     pub fn capacity(&mut self, capacity: usize) {
         self.capacity = capacity;
     }
@@ -209,16 +488,89 @@ impl Extractor {
     ///
     /// ffmpegの`-probesize`に近い。
     #[inline]
+    pub fn with_probe_size(mut self, probe_size: u64) -> Extractor {
+        self.probe_size = probe_size;
+        self
+    }
+
+    /// ストリーム情報を初期化する際に解析する最大の容量を設定する。
+    #[deprecated(note = "`Extractor::with_probe_size`を使う")]
+    #[inline]
     pub fn probe_size(&mut self, probe_size: u64) {
         self.probe_size = probe_size;
     }
 
+    /// ストリーム情報を初期化する際に解析する時間を設定し、解析する容量の自動調整を有効にする。
+    ///
+    /// 自動調整が有効な場合、解析中に受信したPCRからビットレートを推定し、
+    /// `dur`に相当する容量（188×1024バイト以上、188×65536バイト以下）を解析する。
+    /// ビットレートが推定できるまでは[`with_probe_size`][Extractor::with_probe_size]の容量を上限とする。
+    #[inline]
+    pub fn with_probe_duration(mut self, dur: Duration) -> Extractor {
+        self.probe_duration = Some(dur);
+        self
+    }
+
+    /// ストリーム情報を初期化する際に解析する容量を自動調整するかどうかを設定する。
+    ///
+    /// `true`の場合、[`with_probe_duration`][Extractor::with_probe_duration]で設定された時間
+    /// （既定値は5秒）を元に容量を自動調整する。既定値は`false`。
+    #[inline]
+    pub fn with_auto_probe(mut self, enabled: bool) -> Extractor {
+        self.probe_duration = if enabled {
+            Some(self.probe_duration.unwrap_or(DEFAULT_PROBE_DURATION))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// ストリーム長を取得するために末尾から解析する際の容量を設定する。
+    #[inline]
+    pub fn with_tail_probe_size(mut self, tail_probe_size: u64) -> Extractor {
+        self.tail_probe_size = tail_probe_size;
+        self
+    }
+
     /// ストリーム長を取得するために末尾から解析する際の容量を設定する。
+    #[deprecated(note = "`Extractor::with_tail_probe_size`を使う")]
     #[inline]
     pub fn tail_probe_size(&mut self, tail_probe_size: u64) {
         self.tail_probe_size = tail_probe_size;
     }
 
+    /// TSを読み取る速度の上限をバイト毎秒で設定する。
+    ///
+    /// TSファイルを実時間で再生することで、チューナーを使わずにリアルタイム視聴を模倣するのに使う。
+    /// `0`の場合（既定値）は速度を制限しない。
+    #[inline]
+    pub fn with_rate_limit(mut self, bps: u64) -> Extractor {
+        self.rate_limit = bps;
+        self
+    }
+
+    /// 生放送において、TOTの代わりに実時刻を日付時刻として使うかどうかを設定する。
+    ///
+    /// `true`の場合、最初に受信したTOTと実時刻との差が60秒以内であれば生放送と見做し、
+    /// [`ExtractHandler::timestamp`]等はTOTではなく実時刻を返すようになる。
+    /// 差がそれより大きい場合は録画と見做し、TOTから計算される日付時刻をそのまま使う。
+    ///
+    /// 既定値は`false`。
+    #[inline]
+    pub fn with_realtime_clock_sync(mut self, enabled: bool) -> Extractor {
+        self.realtime_clock_sync = enabled;
+        self
+    }
+
+    /// PCRが不連続であると見做す、連続するPCRの差の閾値を設定する。
+    ///
+    /// 閾値を超えた場合は[`Sink::on_pcr_discontinuity`]が呼ばれる。既定値は2秒。
+    #[inline]
+    pub fn with_discontinuity_threshold(mut self, d: Duration) -> Extractor {
+        self.discontinuity_threshold = d;
+        self
+    }
+
     /// 指定された読み取り元`Read`と処理用`Sink`を使い、新しいスレッドで`Extractor`の処理を開始する。
     ///
     /// 戻り値の[`JoinHandle`][std::thread::JoinHandle]を使って終了待ちができるが、
@@ -227,22 +579,89 @@ impl Extractor {
     where
         R: Read + Seek + Send + 'static,
         T: Sink + Send + 'static,
+    {
+        self.spawn_with_name("isdb-worker", read, sink)
+    }
+
+    /// [`Extractor::spawn`]と同様だが、生成するスレッドに`name`という名前を付ける。
+    ///
+    /// # パニック
+    ///
+    /// スレッドの生成に失敗した場合、このメソッドはパニックする。
+    pub fn spawn_with_name<R, T>(
+        self,
+        name: impl Into<String>,
+        read: R,
+        sink: T,
+    ) -> std::thread::JoinHandle<()>
+    where
+        R: Read + Seek + Send + 'static,
+        T: Sink + Send + 'static,
+    {
+        let builder = std::thread::Builder::new().name(name.into());
+        self.spawn_on(builder, read, sink)
+            .expect("スレッドの生成に失敗")
+    }
+
+    /// [`Extractor::spawn`]と同様だが、`builder`の設定を使ってスレッドを生成する。
+    ///
+    /// スレッドの生成に失敗した場合はエラーを返す。
+    pub fn spawn_on<R, T>(
+        self,
+        builder: std::thread::Builder,
+        read: R,
+        sink: T,
+    ) -> io::Result<std::thread::JoinHandle<()>>
+    where
+        R: Read + Seek + Send + 'static,
+        T: Sink + Send + 'static,
+    {
+        let worker = self.into_worker(read, sink);
+        builder.spawn(move || worker.run())
+    }
+
+    /// `path`のファイルを開き、[`Extractor::spawn`]と同様に新しいスレッドで処理を開始する。
+    ///
+    /// 読み取りは内部でバッファリングされるため、ファイルを`BufReader`で包む必要はない。
+    /// ファイルを開けなかった場合やスレッドの生成に失敗した場合はエラーを返す。
+    pub fn spawn_file<P, T>(self, path: P, sink: T) -> io::Result<std::thread::JoinHandle<()>>
+    where
+        P: AsRef<Path>,
+        T: Sink + Send + 'static,
+    {
+        let file = std::fs::File::open(path)?;
+        let builder = std::thread::Builder::new().name("isdb-worker".into());
+        self.spawn_on(builder, file, sink)
+    }
+
+    fn into_worker<R, T>(self, read: R, sink: T) -> Worker<R, T>
+    where
+        R: Read + Seek,
+        T: Sink,
     {
         let read = io::BufReader::with_capacity(self.capacity, read);
         let demuxer = isdb::demux::Demuxer::new(isdb::filters::sorter::Sorter::new(Selector::new(
-            sink, read, self.state,
+            sink,
+            read,
+            self.state,
+            self.services_version,
+            self.realtime_clock_sync,
+            self.discontinuity_threshold,
         )));
 
-        let worker = Worker {
+        Worker {
             parker: self.parker,
             commands: self.commands,
+            stats: self.stats,
             state: WorkerState::Working,
 
             demuxer,
             probe_size: self.probe_size,
             tail_probe_size: self.tail_probe_size,
-        };
-        std::thread::spawn(move || worker.run())
+            probe_duration: self.probe_duration,
+            rate_limiter: (self.rate_limit > 0).then(|| RateLimiter::new(self.rate_limit)),
+            file_len: None,
+        }
     }
 }
 
@@ -254,6 +673,15 @@ pub enum ExtractorError {
 
     /// シークできないストリーム。
     Unseekable,
+
+    /// 要求を処理するために必要な情報（TOTやEIT等）が揃っていない。
+    InsufficientData,
+
+    /// シーク中にIOエラーが発生した。
+    IoError(io::ErrorKind),
+
+    /// 前回のシークが完了する前に新たなシークが指示された。
+    SeekInProgress,
 }
 
 impl fmt::Display for ExtractorError {
@@ -261,6 +689,9 @@ impl fmt::Display for ExtractorError {
         match self {
             ExtractorError::AlreadyShutdown => f.write_str("既にシャットダウン済み"),
             ExtractorError::Unseekable => f.write_str("シークできないストリーム"),
+            ExtractorError::InsufficientData => f.write_str("必要な情報が揃っていない"),
+            ExtractorError::IoError(kind) => write!(f, "シーク中のIOエラー：{}", kind),
+            ExtractorError::SeekInProgress => f.write_str("シーク中"),
         }
     }
 }
@@ -272,8 +703,12 @@ impl std::error::Error for ExtractorError {}
 pub struct ExtractHandler {
     /// 現在の状態。
     state: Arc<RwLock<State>>,
+    /// サービス一覧が更新される度に加算される値。
+    services_version: Arc<AtomicU64>,
     /// 指示が格納される構造体。
     commands: Arc<Commands>,
+    /// 処理したパケットの統計情報。
+    stats: Arc<PacketStats>,
     /// 指示が出された際にワーカースレッドを起床させるためのハンドル。
     unparker: crossbeam_utils::sync::Unparker,
 }
@@ -296,21 +731,96 @@ impl ExtractHandler {
     }
 
     /// TOTとPCRによって計算される、1900年1月1日からの経過時間を返す。
+    #[inline]
     pub fn timestamp(&self) -> Option<Duration> {
+        self.state.read().timestamp()
+    }
+
+    /// 読み取り開始、または先頭へ巻き戻してから処理したパケットの数を返す。
+    #[inline]
+    pub fn packet_count(&self) -> u64 {
+        self.stats.packets.load(Ordering::Relaxed)
+    }
+
+    /// 読み取り開始、または先頭へ巻き戻してから処理したパケットの内、
+    /// トランスポートエラーインジケーターが立っていたパケットの数を返す。
+    #[inline]
+    pub fn error_count(&self) -> u64 {
+        self.stats.errors.load(Ordering::Relaxed)
+    }
+
+    /// 既定サービスのPCRを元にした現在の再生位置を返す。
+    ///
+    /// 再生セッションの生成を待たずに取得できるが、ストリームが確定するまでは`None`を返す。
+    #[inline]
+    pub fn position(&self) -> Option<Duration> {
+        self.state.read().position()
+    }
+
+    /// サービス一覧や選択中のストリーム、再生位置等をまとめて取得する。
+    ///
+    /// 各値は同時に取得されるため、個別に取得した場合と異なり相互に矛盾しない。
+    pub fn state_snapshot(&self) -> StateSnapshot {
         let state = self.state.read();
-        let tot = state.tot.as_ref()?;
+        StateSnapshot {
+            services: state.services.clone(),
+            selected_stream: state.selected_stream.clone(),
+            duration: state.length.as_ref().map(|br| br.duration()),
+            position: state.position(),
+            timestamp: state.timestamp(),
+        }
+    }
 
-        let (_, default_service) = state.services.first()?;
-        let pcr = default_service.pcr()?;
+    /// ブックマーク一覧を設定する。
+    ///
+    /// 各ブックマークの位置は[`position`][ExtractHandler::position]と同じく、
+    /// 既定サービスのPCRを元にした再生位置である。
+    /// ブックマークはストリームに対するものであるため、先頭へ巻き戻しても保持される。
+    pub fn set_bookmarks(&self, marks: Vec<(Duration, String)>) {
+        self.state.write().bookmarks = marks;
+    }
 
-        Some(tot.timestamp(pcr))
+    /// 設定されたブックマーク一覧を返す。
+    pub fn bookmarks(&self) -> Vec<(Duration, String)> {
+        self.state.read().bookmarks.clone()
+    }
+
+    /// これまでに受信したTOTの不連続点から推定したチャプター位置を返す。
+    ///
+    /// 連続するTOT間で、日付時刻の差が再生位置の差から`min_gap`以上ずれている位置を
+    /// チャプターの境界と見做す。
+    /// 各位置は[`position`][ExtractHandler::position]と同じく、既定サービスのPCRを元にした再生位置である。
+    #[cfg(feature = "chapter-detection")]
+    pub fn infer_chapters(&self, min_gap: Duration) -> Vec<Duration> {
+        infer_chapters(&self.state.read().tot_history, min_gap)
     }
 
     /// 現在のサービス一覧を返す。
     ///
     /// 戻り値はロックを保持しているため、できるだけ早く破棄すべきである。
     pub fn services(&self) -> parking_lot::MappedRwLockReadGuard<ServiceMap> {
-        parking_lot::RwLockReadGuard::map(self.state.read(), |s| &s.services)
+        parking_lot::RwLockReadGuard::map(self.state.read(), |s| &*s.services)
+    }
+
+    /// サービス一覧の版数を返す。
+    ///
+    /// 版数はサービス一覧が更新される度に増加するため、
+    /// [`ServicesSnapshot::version`]と比較することでサービス一覧が更新されたかどうかを
+    /// ロックせずに判定することができる。
+    #[inline]
+    pub fn services_version(&self) -> u64 {
+        self.services_version.load(Ordering::Acquire)
+    }
+
+    /// 現在のサービス一覧をその版数と共に返す。
+    ///
+    /// [`services`][ExtractHandler::services]とは異なり、戻り値はロックを保持しない。
+    pub fn services_snapshot(&self) -> ServicesSnapshot {
+        let state = self.state.read();
+        ServicesSnapshot {
+            version: self.services_version.load(Ordering::Acquire),
+            services: state.services.clone(),
+        }
     }
 
     /// 選択中のサービス・ストリームに関する情報を返す。
@@ -345,13 +855,41 @@ impl ExtractHandler {
         Ok(())
     }
 
+    /// 音量の変更を指示する。
+    ///
+    /// 指示は[`Sink::on_volume_requested`]を通して`Sink`に伝えられるため、
+    /// 再生セッションの有無に関わらず音量を指定することができる。
+    pub fn set_volume(&self, volume: f32) -> Result<(), ExtractorError> {
+        self.check_shutdown()?;
+
+        self.commands.store_volume(volume);
+        self.commands.has_any.store(true, Ordering::SeqCst);
+        self.unparker.unpark();
+        Ok(())
+    }
+
     /// 映像ストリームの選択を指示する。
     pub fn select_video_stream(&self, component_tag: u8) -> Result<(), ExtractorError> {
         self.check_shutdown()?;
 
         self.commands
-            .select_video_stream
-            .store(component_tag as u16 + 1, Ordering::SeqCst);
+            .store_video_stream(VideoStreamSelection::ComponentTag(component_tag));
+        self.commands.has_any.store(true, Ordering::SeqCst);
+        self.unparker.unpark();
+        Ok(())
+    }
+
+    /// PIDによる映像ストリームの選択を指示する。
+    ///
+    /// マルチビューTV等、同一サービス内の複数の映像ストリームを切り替えるのに使う。
+    ///
+    /// [`ExtractHandler::select_video_stream`]による指示が処理される前にこのメソッドを呼んだ場合、
+    /// 先の指示は破棄される。その逆も同様である。
+    pub fn select_video_stream_by_pid(&self, pid: isdb::Pid) -> Result<(), ExtractorError> {
+        self.check_shutdown()?;
+
+        self.commands
+            .store_video_stream(VideoStreamSelection::Pid(pid));
         self.commands.has_any.store(true, Ordering::SeqCst);
         self.unparker.unpark();
         Ok(())
@@ -370,6 +908,9 @@ impl ExtractHandler {
     }
 
     /// 再生位置の設定を指示する。
+    ///
+    /// 前回のシークが完了していない場合も指示は受け付けられ、後から指示された位置へシークする。
+    /// その際、[`Sink::on_seek_error`]に[`ExtractorError::SeekInProgress`]が渡される。
     pub fn set_position(&self, pos: Duration) -> Result<(), ExtractorError> {
         self.check_shutdown()?;
 
@@ -377,18 +918,53 @@ impl ExtractHandler {
             return Err(ExtractorError::Unseekable);
         }
 
-        // 秒が`u64::MAX`になるようなシークはしないと思われ
-        self.commands
-            .set_position_secs
-            .store(pos.as_secs().saturating_add(1), Ordering::SeqCst);
-        self.commands
-            .set_position_nanos
-            .store(pos.subsec_nanos(), Ordering::SeqCst);
+        self.commands.store_position(pos, false);
+        self.commands.has_any.store(true, Ordering::SeqCst);
+        self.unparker.unpark();
+        Ok(())
+    }
+
+    /// TOTによる日付時刻を基準とした再生位置の設定を指示する。
+    ///
+    /// `ts`には[`ExtractHandler::timestamp`]と同様、1900年1月1日からの経過時間を指定する。
+    /// TOTを受信していない場合、この指示は無視される。
+    ///
+    /// [`ExtractHandler::set_position`]による指示が処理される前にこのメソッドを呼んだ場合、
+    /// 先の指示は破棄される。その逆も同様である。
+    pub fn set_position_by_timestamp(&self, ts: Duration) -> Result<(), ExtractorError> {
+        self.check_shutdown()?;
+
+        if self.state.read().length.is_none() {
+            return Err(ExtractorError::Unseekable);
+        }
+
+        self.commands.store_position(ts, true);
         self.commands.has_any.store(true, Ordering::SeqCst);
         self.unparker.unpark();
         Ok(())
     }
 
+    /// 選択中サービスにおける現在のイベントの開始時刻を、再生位置に換算して返す。
+    ///
+    /// 開始時刻は現在のEITにおけるイベントの開始時刻を、TOTによる日付時刻と照らし合わせて再生位置に換算する。
+    /// TOTやEITを受信していない場合は[`ExtractorError::InsufficientData`]を返す。
+    pub fn event_start_position(&self) -> Result<Duration, ExtractorError> {
+        self.state
+            .read()
+            .event_start_position()
+            .ok_or(ExtractorError::InsufficientData)
+    }
+
+    /// 選択中サービスにおける現在のイベントの開始位置へのシークを指示する。
+    ///
+    /// 再生位置は[`ExtractHandler::event_start_position`]で求める。
+    pub fn seek_to_event_start(&self) -> Result<(), ExtractorError> {
+        self.check_shutdown()?;
+
+        let pos = self.event_start_position()?;
+        self.set_position(pos)
+    }
+
     /// TSをリセットし最初から再生し直すことを指示する。
     pub fn reset(&self) -> Result<(), ExtractorError> {
         self.check_shutdown()?;
@@ -410,6 +986,30 @@ impl ExtractHandler {
     }
 }
 
+/// ある時点におけるサービス一覧。
+#[derive(Debug, Clone)]
+pub struct ServicesSnapshot {
+    /// サービス一覧の版数。
+    pub version: u64,
+    /// サービス一覧。
+    pub services: Arc<ServiceMap>,
+}
+
+/// ある時点における[`Extractor`]の状態。
+#[derive(Debug, Clone)]
+pub struct StateSnapshot {
+    /// サービス一覧。
+    pub services: Arc<ServiceMap>,
+    /// 選択中のサービス・ストリーム。
+    pub selected_stream: Option<SelectedStream>,
+    /// ストリームの長さ。
+    pub duration: Option<Duration>,
+    /// 既定サービスのPCRを元にした再生位置。
+    pub position: Option<Duration>,
+    /// TOTとPCRによって計算される、1900年1月1日からの経過時間。
+    pub timestamp: Option<Duration>,
+}
+
 /// 常にソート済みで重複のない集合。
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 struct SortedSet<T>(Vec<T>);
@@ -452,6 +1052,57 @@ pub struct SelectedStream {
     pub caption_pid: Option<isdb::Pid>,
     /// 選択されたサービスにおける文字スーパーのPID。
     pub superimpose_pid: Option<isdb::Pid>,
+    caption_language: Option<LangCode>,
+    superimpose_language: Option<LangCode>,
+}
+
+impl SelectedStream {
+    /// 言語コードが未取得の`SelectedStream`を生成する。
+    pub fn new(
+        service_id: ServiceId,
+        video_stream: Stream,
+        audio_stream: Stream,
+        caption_pid: Option<isdb::Pid>,
+        superimpose_pid: Option<isdb::Pid>,
+    ) -> SelectedStream {
+        SelectedStream {
+            service_id,
+            video_stream,
+            audio_stream,
+            caption_pid,
+            superimpose_pid,
+            caption_language: None,
+            superimpose_language: None,
+        }
+    }
+
+    /// 選択されたサービスにおける字幕の言語コードを返す。
+    ///
+    /// 言語コードは字幕管理データから取得されるため、それを受信するまでは`None`を返す。
+    /// 複数の言語がある場合は第一言語の言語コードを返す。
+    #[inline]
+    pub fn caption_language(&self) -> Option<LangCode> {
+        self.caption_language
+    }
+
+    /// 選択されたサービスにおける文字スーパーの言語コードを返す。
+    ///
+    /// 言語コードは字幕管理データから取得されるため、それを受信するまでは`None`を返す。
+    /// 複数の言語がある場合は第一言語の言語コードを返す。
+    #[inline]
+    pub fn superimpose_language(&self) -> Option<LangCode> {
+        self.superimpose_language
+    }
+}
+
+/// 字幕管理データであればその第一言語の言語コードを返す。
+fn caption_language(caption: &isdb::filters::sorter::Caption) -> Option<LangCode> {
+    match caption {
+        isdb::filters::sorter::Caption::ManagementData(data) => {
+            data.languages.first().map(|lang| lang.lang_code)
+        }
+        isdb::filters::sorter::Caption::Data(_) => None,
+    }
 }
 
 /// 再生時間。
@@ -462,6 +1113,25 @@ struct PlaybackTime {
 }
 
 impl PlaybackTime {
+    /// 直前のタイムスタンプを`prev_ts`、その時点での再生時間を`duration`とする`PlaybackTime`を生成する。
+    #[inline]
+    pub const fn reset_to(prev_ts: Option<Timestamp>, duration: Duration) -> PlaybackTime {
+        PlaybackTime { prev_ts, duration }
+    }
+
+    /// `earlier`から現在位置までの経過時間を返す。
+    ///
+    /// 現在位置のタイムスタンプが不明な場合や、`earlier`が現在位置よりも後の場合は`None`を返す。
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn elapsed_since(&self, earlier: Timestamp) -> Option<Duration> {
+        let prev_ts = self.prev_ts?;
+        if prev_ts >= earlier {
+            Some((prev_ts - earlier).to_duration())
+        } else {
+            None
+        }
+    }
+
     /// 現在の再生時間を更新する。
     ///
     /// 前パケットとの差分を積分していくことでラップアラウンドを回避する。
@@ -508,17 +1178,148 @@ struct Tot {
 }
 
 impl Tot {
+    /// PCRが`pcr`である位置の日付時刻を、1900年1月1日からの経過時間として返す。
+    ///
+    /// `pcr`がTOTを受信した位置より前の場合は、その分を差し引いた日付時刻を返す。
     fn timestamp(&self, pcr: Timestamp) -> Duration {
-        const NANOS_PER_SECS: u64 = 1_000_000_000;
+        let base = Duration::from_secs(self.datetime.ntp_timestamp());
+        if pcr >= self.base_pcr {
+            base + (pcr - self.base_pcr).to_duration()
+        } else {
+            base.saturating_sub((self.base_pcr - pcr).to_duration())
+        }
+    }
+}
+
+/// 再生位置`pos`で受信したTOTの日付時刻`datetime`を、再生位置順に並ぶ履歴`history`に記録する。
+///
+/// シークにより同じTOTを再び受信した場合など、近い再生位置で既に記録されている場合は記録しない。
+#[cfg(feature = "chapter-detection")]
+fn record_tot(history: &mut Vec<(Duration, Duration)>, pos: Duration, datetime: Duration) {
+    /// 同じTOTと見做す再生位置の最大の差。
+    // TOTは5秒に1回程度送出されるため、それより十分短くする
+    const SAME_POS_MAX: Duration = Duration::from_secs(1);
+
+    let index = history.partition_point(|&(p, _)| p < pos);
+    let is_near = |i: usize| {
+        history
+            .get(i)
+            .is_some_and(|&(p, _)| p.abs_diff(pos) < SAME_POS_MAX)
+    };
+    if is_near(index) || index.checked_sub(1).is_some_and(is_near) {
+        return;
+    }
+    history.insert(index, (pos, datetime));
+}
+
+/// 再生位置とTOTの日付時刻の組からなる履歴から、TOTが不連続となる再生位置を返す。
+#[cfg(feature = "chapter-detection")]
+fn infer_chapters(history: &[(Duration, Duration)], min_gap: Duration) -> Vec<Duration> {
+    history
+        .windows(2)
+        .filter_map(|w| {
+            let [(prev_pos, prev_tot), (pos, tot)] = *w else {
+                unreachable!();
+            };
+            // (tot - prev_tot) - (pos - prev_pos)の絶対値を負数を経由せずに求める
+            let diff = (tot + prev_pos).abs_diff(pos + prev_tot);
+            (diff >= min_gap).then_some(pos)
+        })
+        .collect()
+}
+
+/// 連続するPCRの差が`threshold`を超えているかどうかを返す。
+///
+/// 差は[`Timestamp`]の順序に従いラップアラウンドを考慮して計算される。
+fn is_pcr_discontinuous(old: Timestamp, new: Timestamp, threshold: Duration) -> bool {
+    let diff = if new >= old { new - old } else { old - new };
+    diff.to_duration() > threshold
+}
 
-        let diff = (pcr - self.base_pcr).as_nanos();
-        let secs = self.datetime.ntp_timestamp() + diff / NANOS_PER_SECS;
-        let nanos = (diff % NANOS_PER_SECS) as u32;
+/// 映像・音声のビットレートをPCRによる時間を元に計測する。
+#[derive(Debug, Default)]
+struct BitrateMeter {
+    /// 計測区間の開始時点におけるPCR。
+    start: Option<Timestamp>,
+    /// 計測区間内で受信した映像のバイト数。
+    video_bytes: u64,
+    /// 計測区間内で受信した音声のバイト数。
+    audio_bytes: u64,
+    /// 最後に通知した映像・音声のビットレート（kbps）。
+    last: Option<(u32, u32)>,
+}
+
+impl BitrateMeter {
+    /// ビットレートを計算する区間の長さ。
+    const WINDOW: Duration = Duration::from_secs(2);
+    /// 通知するのに必要な、前回通知したビットレートからの変化率。
+    const CHANGE_RATIO: f64 = 0.05;
 
-        Duration::new(secs, nanos)
+    /// 計測区間をやり直す。
+    fn reset(&mut self) {
+        self.start = None;
+        self.video_bytes = 0;
+        self.audio_bytes = 0;
+    }
+
+    /// PCRを受信した際に呼ばれる。
+    ///
+    /// 通知すべきビットレートの推定値がある場合は`(映像, 音声)`として返す。
+    fn on_pcr(&mut self, pcr: Timestamp) -> Option<(u32, u32)> {
+        let Some(start) = self.start else {
+            self.reset();
+            self.start = Some(pcr);
+            return None;
+        };
+
+        let elapsed = (pcr - start).to_duration();
+        if elapsed < Self::WINDOW {
+            return None;
+        }
+
+        let kbps = |bytes: u64| (bytes as f64 * 8. / 1000. / elapsed.as_secs_f64()) as u32;
+        let current = (kbps(self.video_bytes), kbps(self.audio_bytes));
+        self.reset();
+        self.start = Some(pcr);
+
+        let changed =
+            |old: u32, new: u32| old.abs_diff(new) as f64 > old as f64 * Self::CHANGE_RATIO;
+        match self.last {
+            Some((video, audio)) if !changed(video, current.0) && !changed(audio, current.1) => {
+                None
+            }
+            _ => {
+                self.last = Some(current);
+                Some(current)
+            }
+        }
     }
 }
 
+/// 実時刻を日本標準時として1900年1月1日からの経過時間で返す。
+fn now_timestamp() -> Option<Duration> {
+    /// 1900年1月1日から1970年1月1日までの秒数。
+    const UNIX_EPOCH_SECS: u64 = 2_208_988_800;
+    /// 日本標準時の時差。
+    const JST_OFFSET_SECS: u64 = 9 * 60 * 60;
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?;
+    Some(now + Duration::from_secs(UNIX_EPOCH_SECS + JST_OFFSET_SECS))
+}
+
+/// TOTと実時刻との同期状態。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClockSync {
+    /// 同期しない。
+    Disabled,
+    /// 最初のTOTを待っている。
+    Pending,
+    /// 最初のTOTで同期するかどうかを判定済み。
+    Done,
+}
+
 #[derive(Debug)]
 enum Caption {
     Caption(caption::Caption),
@@ -537,6 +1338,8 @@ struct SeekInfo {
     pat_updated: bool,
     eit_updated: SortedSet<(ServiceId, bool)>,
     pmt_updated: SortedSet<ServiceId>,
+    /// PIDごとに保留する最新のスクランブル状態。
+    scrambling_changed: FxHashMap<isdb::Pid, isdb::packet::TransportScramblingControl>,
 
     /// 保留する字幕・文字スーパー。
     last_captions: RingBuf<(Option<Duration>, Caption), 10>,
@@ -548,25 +1351,66 @@ struct Selector<R, T> {
     sink: T,
 
     state: Arc<RwLock<State>>,
+    services_version: Arc<AtomicU64>,
     /// ESのPIDからサービス識別を得るテーブル。
     es2svc: isdb::pid::PidTable<Option<ServiceId>>,
     /// 既定サービスのPCRを元にした再生位置。
     pcr_time: PlaybackTime,
     /// シーク中の情報。シークが完了したら`None`が設定される。
     seek_info: Option<SeekInfo>,
+    clock_sync: ClockSync,
+    /// PCRが不連続であると見做す閾値。
+    discontinuity_threshold: Duration,
+    bitrate_meter: BitrateMeter,
+    /// 選択中の映像・音声ストリームのPID。
+    ///
+    /// パケットごとに`state`のロックを取らずに済むよう、選択を変更する度に更新する。
+    selected_pids: Option<(isdb::Pid, isdb::Pid)>,
+    /// スクランブルされたパケットの通知を制限する。
+    scrambled_limiter: PidRateLimiter,
 }
 
 impl<R: Read + Seek, T: Sink> Selector<R, T> {
     #[inline]
-    fn new(sink: T, read: io::BufReader<R>, state: Arc<RwLock<State>>) -> Selector<R, T> {
+    fn new(
+        sink: T,
+        read: io::BufReader<R>,
+        state: Arc<RwLock<State>>,
+        services_version: Arc<AtomicU64>,
+        realtime_clock_sync: bool,
+        discontinuity_threshold: Duration,
+    ) -> Selector<R, T> {
         Selector {
             read: PositionedRead::new(read),
             sink,
 
             state,
+            services_version,
             es2svc: isdb::pid::PidTable::from_fn(|_| None),
             pcr_time: PlaybackTime::default(),
             seek_info: None,
+            clock_sync: if realtime_clock_sync {
+                ClockSync::Pending
+            } else {
+                ClockSync::Disabled
+            },
+            discontinuity_threshold,
+            bitrate_meter: BitrateMeter::default(),
+            selected_pids: None,
+            scrambled_limiter: PidRateLimiter::new(Duration::from_secs(1))
+                .with_skip(SCRAMBLED_PACKET_SKIP),
+        }
+    }
+
+    /// 状態にサービス一覧と再生位置を反映する。
+    ///
+    /// `modified`が`true`の場合はサービス一覧の版数を更新する。
+    fn store_services(&self, services: &ServiceMap, modified: bool) {
+        let mut state = self.state.write();
+        Arc::make_mut(&mut state.services).clone_from(services);
+        state.position = self.pcr_time.duration;
+        if modified {
+            self.services_version.fetch_add(1, Ordering::AcqRel);
         }
     }
 
@@ -612,20 +1456,26 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
                 log::info!("select_service：映像ストリームが存在しない");
                 return;
             };
-            let Some(audio_stream) = service.find_audio_stream(audio_tag) else {
+            let audio_stream = if audio_tag.is_some() {
+                service.find_audio_stream(audio_tag)
+            } else {
+                service.preferred_audio_stream()
+            };
+            let Some(audio_stream) = audio_stream else {
                 log::info!("select_service：音声ストリームが存在しない");
                 return;
             };
 
             let changed = StreamChanged::new(old_streams, (video_stream, audio_stream));
 
-            state.selected_stream = Some(SelectedStream {
-                service_id: service.service_id(),
-                video_stream: video_stream.clone(),
-                audio_stream: audio_stream.clone(),
-                caption_pid: service.caption_stream().map(|s| s.pid()),
-                superimpose_pid: service.superimpose_stream().map(|s| s.pid()),
-            });
+            state.selected_stream = Some(SelectedStream::new(
+                service.service_id(),
+                video_stream.clone(),
+                audio_stream.clone(),
+                service.caption_stream().map(|s| s.pid()),
+                service.superimpose_stream().map(|s| s.pid()),
+            ));
+            self.selected_pids = Some((video_stream.pid(), audio_stream.pid()));
 
             changed
         };
@@ -640,6 +1490,20 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
     }
 
     fn select_video_stream(&mut self, services: &ServiceMap, component_tag: u8) {
+        self.select_video_stream_with(services, |service| {
+            service.find_video_stream(Some(component_tag))
+        });
+    }
+
+    fn select_video_stream_by_pid(&mut self, services: &ServiceMap, pid: isdb::Pid) {
+        self.select_video_stream_with(services, |service| service.find_video_stream_by_pid(pid));
+    }
+
+    /// `find`で検索した映像ストリームを選択する。
+    fn select_video_stream_with<F>(&mut self, services: &ServiceMap, find: F)
+    where
+        F: FnOnce(&Service) -> Option<&Stream>,
+    {
         let changed = {
             let mut state = self.state.write();
             let Some(selected_stream) = state.selected_stream.as_mut() else {
@@ -648,7 +1512,7 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
             };
 
             let service = &services[&selected_stream.service_id];
-            let Some(video_stream) = service.find_video_stream(Some(component_tag)) else {
+            let Some(video_stream) = find(service) else {
                 log::info!("select_video_stream：映像ストリームが存在しない");
                 return;
             };
@@ -658,6 +1522,7 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
                 selected_stream.video_stream.stream_type() != video_stream.stream_type();
 
             selected_stream.video_stream = video_stream.clone();
+            self.selected_pids = Some((video_stream.pid(), selected_stream.audio_stream.pid()));
 
             StreamChanged {
                 video_pid: video_pid_changed,
@@ -692,6 +1557,7 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
                 selected_stream.audio_stream.stream_type() != audio_stream.stream_type();
 
             selected_stream.audio_stream = audio_stream.clone();
+            self.selected_pids = Some((selected_stream.video_stream.pid(), audio_stream.pid()));
 
             StreamChanged {
                 video_pid: false,
@@ -733,8 +1599,17 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
 
             selected_stream.video_stream = video_stream.clone();
             selected_stream.audio_stream = audio_stream.clone();
-            selected_stream.caption_pid = service.caption_stream().map(|s| s.pid());
-            selected_stream.superimpose_pid = service.superimpose_stream().map(|s| s.pid());
+            self.selected_pids = Some((video_stream.pid(), audio_stream.pid()));
+            let caption_pid = service.caption_stream().map(|s| s.pid());
+            if selected_stream.caption_pid != caption_pid {
+                selected_stream.caption_pid = caption_pid;
+                selected_stream.caption_language = None;
+            }
+            let superimpose_pid = service.superimpose_stream().map(|s| s.pid());
+            if selected_stream.superimpose_pid != superimpose_pid {
+                selected_stream.superimpose_pid = superimpose_pid;
+                selected_stream.superimpose_language = None;
+            }
 
             changed
         };
@@ -766,6 +1641,13 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
         for service_id in &*seek_info.pmt_updated {
             self.sink.on_streams_updated(&state.services[service_id]);
         }
+        if let Some(ss) = &state.selected_stream {
+            for (&pid, &new_state) in &seek_info.scrambling_changed {
+                if ss.video_stream.pid() == pid || ss.audio_stream.pid() == pid {
+                    self.sink.on_scrambling_changed(pid, new_state);
+                }
+            }
+        }
         // 保留していた字幕を放流
         for &(pos, ref caption) in &seek_info.last_captions {
             match caption {
@@ -805,7 +1687,7 @@ impl<R: Read + Seek, T: Sink> Selector<R, T> {
 
 impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T> {
     fn on_pat_updated(&mut self, services: &ServiceMap) {
-        self.state.write().services.clone_from(services);
+        self.store_services(services, true);
 
         // シーク中はイベント発生を保留
         if let Some(seek_info) = &mut self.seek_info {
@@ -826,15 +1708,12 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
     }
 
     fn on_pmt_updated(&mut self, services: &ServiceMap, service: &Service) {
-        self.state.write().services.clone_from(services);
+        self.store_services(services, true);
 
         self.es2svc.fill(None);
         for service in services.values().rev() {
-            std::iter::empty()
-                .chain(service.video_streams())
-                .chain(service.audio_streams())
-                .chain(service.caption_stream())
-                .chain(service.superimpose_stream())
+            service
+                .all_streams()
                 .for_each(|stream| self.es2svc[stream.pid()] = Some(service.service_id()));
         }
 
@@ -857,7 +1736,7 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
     }
 
     fn on_eit_updated(&mut self, services: &ServiceMap, service: &Service, is_present: bool) {
-        self.state.write().services.clone_from(services);
+        self.store_services(services, true);
 
         // シーク中はイベント発生を保留
         if let Some(seek_info) = &mut self.seek_info {
@@ -898,6 +1777,7 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
             let pcr = services.get(&service_id)?.pcr()?;
             Some(self.pcr_time.duration + (pts - pcr).to_duration())
         });
+        self.bitrate_meter.video_bytes += payload.len() as u64;
         self.sink.on_video_packet(pos, payload);
     }
 
@@ -930,6 +1810,7 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
             let pcr = services.get(&service_id)?.pcr()?;
             Some(self.pcr_time.duration + (pts - pcr).to_duration())
         });
+        self.bitrate_meter.audio_bytes += payload.len() as u64;
         self.sink.on_audio_packet(pos, payload);
     }
 
@@ -944,11 +1825,21 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
             return;
         };
 
-        {
+        let new_lang = {
             let state = self.state.read();
-            if !matches!(&state.selected_stream, Some(ss) if ss.caption_pid == Some(pid)) {
+            let Some(ss) = &state.selected_stream else {
+                return;
+            };
+            if ss.caption_pid != Some(pid) {
                 return;
             }
+
+            caption_language(caption).filter(|&lang| ss.caption_language != Some(lang))
+        };
+        if let Some(lang) = new_lang {
+            if let Some(ss) = &mut self.state.write().selected_stream {
+                ss.caption_language = Some(lang);
+            }
         }
 
         // 選択中サービスのPCRと既定サービスのPCRが同じ時刻と見做して位置を計算する
@@ -977,11 +1868,21 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
             return;
         };
 
-        {
+        let new_lang = {
             let state = self.state.read();
-            if !matches!(&state.selected_stream, Some(ss) if ss.superimpose_pid == Some(pid)) {
+            let Some(ss) = &state.selected_stream else {
+                return;
+            };
+            if ss.superimpose_pid != Some(pid) {
                 return;
             }
+
+            caption_language(caption).filter(|&lang| ss.superimpose_language != Some(lang))
+        };
+        if let Some(lang) = new_lang {
+            if let Some(ss) = &mut self.state.write().selected_stream {
+                ss.superimpose_language = Some(lang);
+            }
         }
 
         // 選択中サービスのPCRと既定サービスのPCRが同じ時刻と見做して位置を計算する
@@ -999,17 +1900,71 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
         }
     }
 
-    fn on_pcr(&mut self, services: &ServiceMap, service_ids: &[ServiceId]) {
-        self.state.write().services.clone_from(services);
+    fn on_scrambling_changed(
+        &mut self,
+        _: &ServiceMap,
+        pid: isdb::Pid,
+        new_state: isdb::packet::TransportScramblingControl,
+    ) {
+        // シーク中はイベント発生を保留
+        if let Some(seek_info) = &mut self.seek_info {
+            seek_info.scrambling_changed.insert(pid, new_state);
+            return;
+        }
+
+        {
+            let state = self.state.read();
+            if !matches!(&state.selected_stream, Some(ss)
+                if ss.video_stream.pid() == pid || ss.audio_stream.pid() == pid)
+            {
+                return;
+            }
+        }
 
-        let Some((_, service)) = services.first() else {
+        self.sink.on_scrambling_changed(pid, new_state);
+    }
+
+    fn on_scrambled_packet(&mut self, _: &ServiceMap, pid: isdb::Pid) {
+        // シーク中は通知しない
+        if self.seek_info.is_some() {
             return;
-        };
-        if !service_ids.contains(&service.service_id()) {
+        }
+        if !matches!(self.selected_pids, Some((video, audio)) if video == pid || audio == pid) {
             return;
         }
 
-        self.pcr_time.update(service.pcr().expect("PCRは更新済み"));
+        if self.scrambled_limiter.check_packet(pid, Instant::now) {
+            self.sink.on_scrambled_packet(pid);
+        }
+    }
+
+    fn on_pcr(&mut self, services: &ServiceMap, service_ids: &[ServiceId]) {
+        let pcr = match services.first() {
+            Some((_, service)) if service_ids.contains(&service.service_id()) => {
+                service.pcr().expect("PCRは更新済み")
+            }
+            _ => {
+                self.store_services(services, false);
+                return;
+            }
+        };
+        if let Some(prev_pcr) = self.pcr_time.prev_ts {
+            if self.seek_info.is_none()
+                && is_pcr_discontinuous(prev_pcr, pcr, self.discontinuity_threshold)
+            {
+                self.sink.on_pcr_discontinuity(prev_pcr, pcr);
+                self.bitrate_meter.reset();
+            }
+        }
+
+        if self.seek_info.is_some() {
+            self.bitrate_meter.reset();
+        } else if let Some((video_kbps, audio_kbps)) = self.bitrate_meter.on_pcr(pcr) {
+            self.sink.on_bitrate_updated(video_kbps, audio_kbps);
+        }
+
+        self.pcr_time.update(pcr);
+        self.store_services(services, false);
         self.complete_seek();
     }
 
@@ -1019,12 +1974,37 @@ impl<R: Read + Seek, T: Sink> isdb::filters::sorter::Shooter for Selector<R, T>
         datetime: DateTime,
         _: Option<isdb::psi::desc::LocalTimeOffsetEntry>,
     ) {
+        /// 生放送と見做すTOTと実時刻との最大の差（秒単位）。
+        const MAX_CLOCK_DIFF: u64 = 60;
+
         if let Some(base_pcr) = self.pcr_time.prev_ts {
-            self.state.write().tot = Some(Tot { datetime, base_pcr });
+            let realtime_clock = {
+                let mut state = self.state.write();
+                state.tot = Some(Tot { datetime, base_pcr });
+                #[cfg(feature = "chapter-detection")]
+                record_tot(
+                    &mut state.tot_history,
+                    self.pcr_time.duration,
+                    Duration::from_secs(datetime.ntp_timestamp()),
+                );
+
+                if self.clock_sync == ClockSync::Pending {
+                    self.clock_sync = ClockSync::Done;
+                    state.realtime_clock = now_timestamp().is_some_and(|now| {
+                        now.as_secs().abs_diff(datetime.ntp_timestamp()) <= MAX_CLOCK_DIFF
+                    });
+                    log::trace!("実時刻との同期：{}", state.realtime_clock);
+                }
+
+                state.realtime_clock
+            };
 
             if self.seek_info.is_none() {
-                self.sink
-                    .on_timestamp_updated(Duration::from_secs(datetime.ntp_timestamp()));
+                let timestamp = realtime_clock
+                    .then(now_timestamp)
+                    .flatten()
+                    .unwrap_or_else(|| Duration::from_secs(datetime.ntp_timestamp()));
+                self.sink.on_timestamp_updated(timestamp);
             }
         }
     }
@@ -1047,8 +2027,27 @@ impl<T> PositionedRead<T> {
 
     /// 現在位置を返す。
     #[inline]
-    pub fn pos(&self) -> u64 {
-        self.pos
+    pub fn pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl<T: AsRef<[u8]>> PositionedRead<io::Cursor<T>> {
+    /// メモリ上のバッファ全体の長さを返す。
+    ///
+    /// バッファの長さは常に分かるため、`None`を返すことはない。
+    #[inline]
+    pub fn total_len(&self) -> Option<u64> {
+        Some(self.inner.get_ref().as_ref().len() as u64)
+    }
+
+    /// 現在位置から終端までの長さを返す。
+    ///
+    /// 現在位置が終端を超えている場合は`Some(0)`を返す。
+    #[inline]
+    pub fn remaining(&self) -> Option<u64> {
+        self.total_len().map(|len| len.saturating_sub(self.pos))
     }
 }
 
@@ -1070,6 +2069,67 @@ impl<T: Seek> Seek for PositionedRead<T> {
     }
 }
 
+/// [`Extractor::with_auto_probe`]で使われる既定の解析時間。
+const DEFAULT_PROBE_DURATION: Duration = Duration::from_secs(5);
+/// [`Extractor::with_discontinuity_threshold`]の既定値。
+const DEFAULT_DISCONTINUITY_THRESHOLD: Duration = Duration::from_secs(2);
+/// スクランブルされたパケットの通知を抑制する際、時刻を確認せずに抑制するパケット数。
+const SCRAMBLED_PACKET_SKIP: u32 = 64;
+/// 自動調整時の最小の解析容量。
+const AUTO_PROBE_SIZE_MIN: u64 = 188 * 1024;
+/// 自動調整時の最大の解析容量。
+const AUTO_PROBE_SIZE_MAX: u64 = 188 * 65536;
+/// ビットレートの推定に必要なPCRの最小の間隔。
+const AUTO_PROBE_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// PCRからビットレートを推定し、ストリーム情報を初期化する際の解析容量を決める。
+#[derive(Debug)]
+struct AutoProbe {
+    duration: Duration,
+    /// 最初に受信したPCRのPIDと値、及びその時点での読み取り位置。
+    first: Option<(isdb::Pid, Timestamp, u64)>,
+}
+
+impl AutoProbe {
+    #[inline]
+    fn new(duration: Duration) -> AutoProbe {
+        AutoProbe {
+            duration,
+            first: None,
+        }
+    }
+
+    /// `pos`まで読み取った時点でパケットを受信した際に呼ばれる。
+    ///
+    /// ビットレートが推定できた場合は解析容量を返す。
+    fn on_packet(&mut self, packet: &isdb::Packet, pos: u64) -> Option<u64> {
+        let pcr = packet.adaptation_field()?.pcr()?;
+        let pid = packet.pid();
+        let Some((first_pid, first_pcr, first_pos)) = self.first else {
+            self.first = Some((pid, pcr, pos));
+            return None;
+        };
+        if pid != first_pid {
+            return None;
+        }
+
+        let length = StreamLength {
+            first_pcr,
+            last_pcr: pcr,
+            size: pos - first_pos,
+        };
+        if length.duration() < AUTO_PROBE_MIN_INTERVAL {
+            return None;
+        }
+
+        Some(
+            length
+                .estimate_size(self.duration)
+                .clamp(AUTO_PROBE_SIZE_MIN, AUTO_PROBE_SIZE_MAX),
+        )
+    }
+}
+
 struct Limit<'a, R> {
     inner: R,
     limit: &'a mut u64,
@@ -1099,6 +2159,23 @@ impl<'a, R: Read> Read for Limit<'a, R> {
     }
 }
 
+/// 現在位置`pos`から前方へ`forward`バイトシークする際のシーク先を返す。
+///
+/// シーク先がファイルの終端`file_len`を超える場合は最後のPCRを探す位置に留め、そこから補正させる。
+fn forward_seek_pos(
+    pos: u64,
+    forward: u64,
+    file_len: Option<u64>,
+    tail_probe_size: u64,
+) -> SeekFrom {
+    match file_len {
+        Some(len) if pos.saturating_add(forward) >= len => {
+            SeekFrom::Start(len.saturating_sub(tail_probe_size))
+        }
+        _ => SeekFrom::Current(forward as i64),
+    }
+}
+
 fn next_pcr<R: Read>(mut read: R, pcr_pid: isdb::Pid) -> io::Result<Option<Timestamp>> {
     loop {
         match isdb::Packet::read(&mut read) {
@@ -1115,6 +2192,111 @@ fn next_pcr<R: Read>(mut read: R, pcr_pid: isdb::Pid) -> io::Result<Option<Times
     }
 }
 
+/// 読み取り速度を制限する。
+#[derive(Debug)]
+struct RateLimiter {
+    /// 1秒あたりのバイト数。
+    bytes_per_sec: u64,
+    /// 計測を開始した時刻。
+    start: Option<Instant>,
+    /// 計測開始から読み取ったバイト数。
+    delivered: u64,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_sec: u64) -> RateLimiter {
+        RateLimiter {
+            bytes_per_sec,
+            start: None,
+            delivered: 0,
+        }
+    }
+
+    /// 計測をやり直す。
+    pub fn reset(&mut self) {
+        self.start = None;
+        self.delivered = 0;
+    }
+
+    /// `now`時点で`bytes`バイトを読み取った際、速度を超えないために待つべき時間を返す。
+    fn delay(&mut self, now: Instant, bytes: u64) -> Duration {
+        let start = *self.start.get_or_insert(now);
+        self.delivered += bytes;
+
+        let expected = Duration::from_secs_f64(self.delivered as f64 / self.bytes_per_sec as f64);
+        expected.saturating_sub(now.saturating_duration_since(start))
+    }
+
+    /// `bytes`バイトを読み取ったことを記録し、必要に応じて待機する。
+    pub fn consume(&mut self, bytes: u64) {
+        let delay = self.delay(Instant::now(), bytes);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// PIDごとに通知の頻度を制限する。
+#[derive(Debug)]
+struct PidRateLimiter {
+    /// 同じPIDについて通知する最小の間隔。
+    interval: Duration,
+    /// 時刻を確認した後、[`check_packet`][PidRateLimiter::check_packet]で時刻を確認せずに抑制する回数。
+    skip: u32,
+    /// PIDごとの最後に通知した時刻と、時刻を確認せずに抑制する残りの回数。
+    last: FxHashMap<isdb::Pid, (Instant, u32)>,
+}
+
+impl PidRateLimiter {
+    pub fn new(interval: Duration) -> PidRateLimiter {
+        PidRateLimiter {
+            interval,
+            skip: 0,
+            last: FxHashMap::default(),
+        }
+    }
+
+    /// 時刻を確認せずに抑制する回数を設定する。
+    pub fn with_skip(mut self, skip: u32) -> PidRateLimiter {
+        self.skip = skip;
+        self
+    }
+
+    /// `now`時点で`pid`について通知して良いかを返す。
+    ///
+    /// 通知して良い場合は`now`を通知した時刻として記録する。
+    pub fn check(&mut self, now: Instant, pid: isdb::Pid) -> bool {
+        match self.last.entry(pid) {
+            Entry::Occupied(mut entry) => {
+                let (last, remaining) = entry.get_mut();
+                *remaining = self.skip;
+                if now.saturating_duration_since(*last) < self.interval {
+                    return false;
+                }
+                *last = now;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((now, self.skip));
+            }
+        }
+        true
+    }
+
+    /// パケットごとに`pid`について通知して良いかを返す。
+    ///
+    /// [`check`][PidRateLimiter::check]と同様だが、時刻を確認した後の`skip`回は
+    /// `now`を呼ばずに通知を抑制するため、時刻の取得が間引かれる。
+    pub fn check_packet(&mut self, pid: isdb::Pid, now: impl FnOnce() -> Instant) -> bool {
+        if let Some((_, remaining)) = self.last.get_mut(&pid) {
+            if *remaining > 0 {
+                *remaining -= 1;
+                return false;
+            }
+        }
+        self.check(now(), pid)
+    }
+}
+
 enum WorkerState {
     Working,
     Eos,
@@ -1124,11 +2306,16 @@ enum WorkerState {
 struct Worker<R: Read + Seek, T: Sink> {
     parker: crossbeam_utils::sync::Parker,
     commands: Arc<Commands>,
+    stats: Arc<PacketStats>,
     state: WorkerState,
 
     demuxer: isdb::demux::Demuxer<isdb::filters::sorter::Sorter<Selector<R, T>>>,
     probe_size: u64,
     tail_probe_size: u64,
+    probe_duration: Option<Duration>,
+    rate_limiter: Option<RateLimiter>,
+    /// ストリーム確定時に取得したファイルの長さ。
+    file_len: Option<u64>,
 }
 
 impl<R: Read + Seek, T: Sink> Worker<R, T> {
@@ -1137,6 +2324,13 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
         self.demuxer.filter_mut().shooter_mut()
     }
 
+    /// パケットを処理する。
+    #[inline]
+    fn feed(&mut self, packet: &isdb::Packet) {
+        self.stats.record(packet);
+        self.demuxer.feed(packet);
+    }
+
     /// ストリーム終端。
     #[inline]
     fn on_eos(&mut self) {
@@ -1151,6 +2345,14 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
         self.selector().sink.on_stream_error(e);
     }
 
+    /// シーク中のエラー発生。
+    fn on_seek_error(&mut self, e: io::Error) {
+        self.selector()
+            .sink
+            .on_seek_error(ExtractorError::IoError(e.kind()));
+        self.on_error(e);
+    }
+
     /// EOSやエラーから復帰した。
     #[inline]
     fn on_restored(&mut self) {
@@ -1159,9 +2361,19 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
 
     fn on_rewinded(&mut self, pcr_time: PlaybackTime) {
         self.on_restored();
+        let position = pcr_time.duration;
         self.selector().pcr_time = pcr_time;
-        self.selector().state.write().tot = None;
+        self.selector().bitrate_meter.reset();
+        {
+            let mut state = self.selector().state.write();
+            state.tot = None;
+            state.position = position;
+        }
         self.demuxer.reset_packets();
+        self.demuxer.filter_mut().reset_repositories();
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            rate_limiter.reset();
+        }
     }
 
     /// ストリームを確定させる。
@@ -1169,10 +2381,22 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
     /// ストリームが見つからなかった場合は`false`を返す。
     fn probe_stream(&mut self) -> bool {
         let mut limit = self.probe_size;
+        let probe_start = self.selector().read.pos();
+        let mut auto_probe = self.probe_duration.map(AutoProbe::new);
         loop {
             match isdb::Packet::read(Limit::new(&mut self.selector().read, &mut limit)) {
                 Ok(Some(packet)) => {
-                    self.demuxer.feed(&packet);
+                    if let Some(ap) = &mut auto_probe {
+                        let pos = self.selector().read.pos();
+                        if let Some(probe_size) = ap.on_packet(&packet, pos) {
+                            log::trace!("解析容量：{}", probe_size);
+                            self.probe_size = probe_size;
+                            limit = probe_size.saturating_sub(pos - probe_start);
+                            auto_probe = None;
+                        }
+                    }
+
+                    self.feed(&packet);
 
                     if self.selector().state.read().selected_stream.is_some() {
                         break;
@@ -1212,6 +2436,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
             // ファイルの長さが取得できなくてもエラーにはしない
             return true;
         };
+        self.file_len = Some(len);
 
         'probe: {
             // ファイルサイズがtail_probe_sizeより小さい場合はストリームが確定した位置から読み取り続行
@@ -1253,7 +2478,12 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
     /// `Worker`を終了する必要がある場合には`false`を返す。
     fn next_packet(&mut self) {
         match isdb::Packet::read(&mut self.selector().read) {
-            Ok(Some(packet)) => self.demuxer.feed(&packet),
+            Ok(Some(packet)) => {
+                if let Some(rate_limiter) = &mut self.rate_limiter {
+                    rate_limiter.consume(188);
+                }
+                self.feed(&packet);
+            }
             Ok(None) => self.on_eos(),
             Err(e) => self.on_error(e),
         }
@@ -1271,12 +2501,46 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
         shooter.select_video_stream(services, component_tag);
     }
 
+    fn select_video_stream_by_pid(&mut self, pid: isdb::Pid) {
+        let sorter = self.demuxer.filter_mut();
+        let (services, shooter) = sorter.pair();
+        shooter.select_video_stream_by_pid(services, pid);
+    }
+
     fn select_audio_stream(&mut self, component_tag: u8) {
         let sorter = self.demuxer.filter_mut();
         let (services, shooter) = sorter.pair();
         shooter.select_audio_stream(services, component_tag);
     }
 
+    /// TOTによる日付時刻`ts`を再生位置に変換してシークする。
+    fn set_position_by_timestamp(&mut self, ts: Duration) {
+        let selector = self.selector();
+        let Some(pcr) = selector.pcr_time.prev_ts else {
+            log::debug!("PCR未受信のため日付時刻によるシーク不可");
+            return;
+        };
+        let Some(current_ts) = selector
+            .state
+            .read()
+            .tot
+            .as_ref()
+            .map(|tot| tot.timestamp(pcr))
+        else {
+            log::debug!("TOT未受信のため日付時刻によるシーク不可");
+            return;
+        };
+
+        let current_pos = selector.pcr_time.duration;
+        let pos = if ts >= current_ts {
+            current_pos + (ts - current_ts)
+        } else {
+            current_pos.saturating_sub(current_ts - ts)
+        };
+        log::trace!("日付時刻によるシーク：{:?} -> {:?}", ts, pos);
+        self.set_position(pos);
+    }
+
     fn set_position(&mut self, pos: Duration) {
         /// 先頭へのシークと見做す最大の位置。
         const HEAD_MAX_POS: Duration = Duration::from_secs(1);
@@ -1302,6 +2566,13 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
             Backward,
         }
 
+        if self.selector().seek_info.is_some() {
+            // 前回のシークは中断し、新たなシークを優先する
+            self.selector()
+                .sink
+                .on_seek_error(ExtractorError::SeekInProgress);
+        }
+
         let Some(mut length) = self.selector().state.read().length.clone() else {
             log::warn!("シークできないストリームへのシーク要求");
             return;
@@ -1327,6 +2598,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
             pat_updated: false,
             pmt_updated: SortedSet::new(),
             eit_updated: SortedSet::new(),
+            scrambling_changed: FxHashMap::default(),
             last_captions: RingBuf::new(),
         });
 
@@ -1374,7 +2646,12 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
         // 無限ループにならないよう最大でも6回試行する
         for _ in 0..6 {
             let seek_pos = if let Direction::Forward = dir {
-                SeekFrom::Current(length.estimate_size(diff - FILE_OFFSET) as i64)
+                forward_seek_pos(
+                    self.selector().read.pos(),
+                    length.estimate_size(diff - FILE_OFFSET),
+                    self.file_len,
+                    self.tail_probe_size,
+                )
             } else {
                 let backward = length.estimate_size(diff + FILE_OFFSET);
 
@@ -1385,7 +2662,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
                 }
             };
             if let Err(e) = self.selector().read.seek(seek_pos) {
-                self.on_error(e);
+                self.on_seek_error(e);
                 return;
             }
 
@@ -1402,12 +2679,12 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
                     } else {
                         let diff = (target_pcr_max - pcr).to_duration();
                         log::trace!("シーク：確定（{:?}の{:?}前）", target_pos, diff);
-                        self.on_rewinded(PlaybackTime {
-                            prev_ts: Some(pcr),
-                            // 同じ位置へのシークを繰り返すと約-75ナノ秒ずつズレていくっぽいが、
-                            // この程度なら誤差としたい（complete_seekで観測できる）
-                            duration: target_pos.saturating_sub(diff),
-                        });
+                        // 同じ位置へのシークを繰り返すと約-75ナノ秒ずつズレていくっぽいが、
+                        // この程度なら誤差としたい（complete_seekで観測できる）
+                        self.on_rewinded(PlaybackTime::reset_to(
+                            Some(pcr),
+                            target_pos.saturating_sub(diff),
+                        ));
 
                         return;
                     }
@@ -1434,7 +2711,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
                     return;
                 }
                 Err(e) => {
-                    self.on_error(e);
+                    self.on_seek_error(e);
                     return;
                 }
             }
@@ -1458,6 +2735,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
     fn rewind(&mut self) -> bool {
         match self.selector().read.rewind() {
             Ok(_) => {
+                self.stats.reset();
                 self.on_rewinded(PlaybackTime::default());
                 true
             }
@@ -1478,6 +2756,7 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
                 pat_updated: false,
                 pmt_updated: SortedSet::new(),
                 eit_updated: SortedSet::new(),
+                scrambling_changed: FxHashMap::default(),
                 last_captions: RingBuf::new(),
             });
         }
@@ -1493,9 +2772,12 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
             self.select_service(ServiceId::new((select_service - 1) as u16));
         }
 
-        let select_video_stream = self.commands.select_video_stream.swap(0, Ordering::SeqCst);
-        if select_video_stream > 0 {
-            self.select_video_stream((select_video_stream - 1) as u8);
+        match self.commands.take_video_stream() {
+            Some(VideoStreamSelection::ComponentTag(component_tag)) => {
+                self.select_video_stream(component_tag)
+            }
+            Some(VideoStreamSelection::Pid(pid)) => self.select_video_stream_by_pid(pid),
+            None => {}
         }
 
         let select_audio_stream = self.commands.select_audio_stream.swap(0, Ordering::SeqCst);
@@ -1503,11 +2785,14 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
             self.select_audio_stream((select_audio_stream - 1) as u8);
         }
 
-        let set_position_secs = self.commands.set_position_secs.swap(0, Ordering::SeqCst);
-        if set_position_secs > 0 {
-            let set_position_nanos = self.commands.set_position_nanos.load(Ordering::SeqCst);
-            let pos = Duration::new(set_position_secs - 1, set_position_nanos);
-            self.set_position(pos);
+        match self.commands.take_position() {
+            Some((pos, false)) => self.set_position(pos),
+            Some((ts, true)) => self.set_position_by_timestamp(ts),
+            None => {}
+        }
+
+        if let Some(volume) = self.commands.take_volume() {
+            self.selector().sink.on_volume_requested(volume);
         }
 
         let reset = self.commands.reset.swap(false, Ordering::SeqCst);
@@ -1549,6 +2834,11 @@ impl<R: Read + Seek, T: Sink> Worker<R, T> {
 
             if !has_any_command && !needs_es {
                 self.parker.park();
+
+                // 停止していた間の分を取り戻そうとしないよう計測し直す
+                if let Some(rate_limiter) = &mut self.rate_limiter {
+                    rate_limiter.reset();
+                }
             }
         }
     }
@@ -1564,13 +2854,549 @@ impl<R: Read + Seek, T: Sink> Drop for Worker<R, T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_auto_probe() {
+        fn pcr_packet(pid: u16, base: u64) -> isdb::Packet {
+            let mut packet = isdb::Packet([0xFF; 188]);
+            packet.0[0..6].copy_from_slice(&[0x47, (pid >> 8) as u8, pid as u8, 0x30, 0x07, 0x10]);
+            packet.0[6..12].copy_from_slice(&[
+                (base >> 25) as u8,
+                (base >> 17) as u8,
+                (base >> 9) as u8,
+                (base >> 1) as u8,
+                ((base & 1) as u8) << 7 | 0x7E,
+                0x00,
+            ]);
+            packet
+        }
+
+        let mut ap = AutoProbe::new(Duration::from_secs(5));
+        assert_eq!(ap.on_packet(&isdb::Packet([0xFF; 188]), 0), None);
+        assert_eq!(ap.on_packet(&pcr_packet(0x01FF, 0), 188), None);
+        // 別PIDのPCRは無視
+        assert_eq!(ap.on_packet(&pcr_packet(0x0100, 9000), 188 * 50), None);
+        // 間隔が短すぎる
+        assert_eq!(ap.on_packet(&pcr_packet(0x01FF, 900), 188 * 11), None);
+        // 100ミリ秒で18800バイト
+        assert_eq!(
+            ap.on_packet(&pcr_packet(0x01FF, 9000), 188 * 101),
+            Some(188 * 100 * 50),
+        );
+
+        // 上限・下限
+        let mut ap = AutoProbe::new(Duration::from_secs(5));
+        ap.on_packet(&pcr_packet(0x01FF, 0), 0);
+        assert_eq!(
+            ap.on_packet(&pcr_packet(0x01FF, 90000), 188),
+            Some(AUTO_PROBE_SIZE_MIN),
+        );
+        let mut ap = AutoProbe::new(Duration::from_secs(5));
+        ap.on_packet(&pcr_packet(0x01FF, 0), 0);
+        assert_eq!(
+            ap.on_packet(&pcr_packet(0x01FF, 9000), 188 * 100_000),
+            Some(AUTO_PROBE_SIZE_MAX),
+        );
+    }
+
+    #[test]
+    fn test_commands_video_stream() {
+        let commands = Commands::default();
+        assert_eq!(commands.take_video_stream(), None);
+
+        for selection in [
+            VideoStreamSelection::ComponentTag(0x00),
+            VideoStreamSelection::ComponentTag(0xFF),
+            VideoStreamSelection::Pid(isdb::Pid::new(0x0000)),
+            VideoStreamSelection::Pid(isdb::Pid::new(0x1FFF)),
+        ] {
+            commands.store_video_stream(selection);
+            assert_eq!(commands.take_video_stream(), Some(selection));
+            assert_eq!(commands.take_video_stream(), None);
+        }
+
+        // 後から指示された方だけが残る
+        commands.store_video_stream(VideoStreamSelection::ComponentTag(0x01));
+        commands.store_video_stream(VideoStreamSelection::Pid(isdb::Pid::new(0x0111)));
+        assert_eq!(
+            commands.take_video_stream(),
+            Some(VideoStreamSelection::Pid(isdb::Pid::new(0x0111)))
+        );
+        commands.store_video_stream(VideoStreamSelection::Pid(isdb::Pid::new(0x0111)));
+        commands.store_video_stream(VideoStreamSelection::ComponentTag(0x01));
+        assert_eq!(
+            commands.take_video_stream(),
+            Some(VideoStreamSelection::ComponentTag(0x01))
+        );
+    }
+
+    #[test]
+    fn test_commands_position() {
+        let commands = Commands::default();
+        assert_eq!(commands.take_position(), None);
+
+        commands.store_position(Duration::new(12, 345), false);
+        assert_eq!(
+            commands.take_position(),
+            Some((Duration::new(12, 345), false))
+        );
+        assert_eq!(commands.take_position(), None);
+
+        // 後から指示された方だけが残る
+        commands.store_position(Duration::new(12, 345), false);
+        commands.store_position(Duration::new(3_900_000_000, 678), true);
+        assert_eq!(
+            commands.take_position(),
+            Some((Duration::new(3_900_000_000, 678), true))
+        );
+        assert_eq!(commands.take_position(), None);
+
+        commands.store_position(Duration::new(3_900_000_000, 678), true);
+        commands.store_position(Duration::ZERO, false);
+        assert_eq!(commands.take_position(), Some((Duration::ZERO, false)));
+        assert_eq!(commands.take_position(), None);
+    }
+
+    #[test]
+    fn test_commands_volume() {
+        let commands = Commands::default();
+        assert_eq!(commands.take_volume(), None);
+
+        // ビット表現が0となる0.0も指示無しと区別される
+        for volume in [0.0, 0.5, 1.0, f32::MAX] {
+            commands.store_volume(volume);
+            assert_eq!(commands.take_volume(), Some(volume));
+            assert_eq!(commands.take_volume(), None);
+        }
+
+        // 後から指示された方だけが残る
+        commands.store_volume(0.25);
+        commands.store_volume(0.75);
+        assert_eq!(commands.take_volume(), Some(0.75));
+        assert_eq!(commands.take_volume(), None);
+
+        // ハンドラーからの指示も取り出せる
+        let extractor = Extractor::new();
+        let handler = extractor.handler();
+        assert_eq!(handler.commands.take_volume(), None);
+        handler.set_volume(0.5).unwrap();
+        assert_eq!(handler.commands.take_volume(), Some(0.5));
+        assert_eq!(handler.commands.take_volume(), None);
+    }
+
+    #[test]
+    fn test_stream_changed() {
+        let changed = |video_pid, video_type, audio_pid, audio_type| StreamChanged {
+            video_pid,
+            video_type,
+            audio_pid,
+            audio_type,
+        };
+
+        let c = changed(true, false, false, false);
+        assert!(!c.is_codec_change());
+        assert!(c.is_pid_only_change());
+
+        let c = changed(false, false, true, true);
+        assert!(c.is_codec_change());
+        assert!(!c.is_pid_only_change());
+
+        let c = changed(false, false, false, false);
+        assert!(!c.is_codec_change());
+        assert!(!c.is_pid_only_change());
+    }
+
+    #[cfg(feature = "chapter-detection")]
+    #[test]
+    fn test_infer_chapters() {
+        let secs = Duration::from_secs;
+        let history = [
+            (secs(0), secs(1000)),
+            (secs(5), secs(1005)),
+            (secs(10), secs(1010)),
+            // CMで時刻が飛んだ
+            (secs(15), secs(1200)),
+            (secs(20), secs(1205)),
+            // 時刻が巻き戻った
+            (secs(25), secs(900)),
+            (secs(30), secs(905)),
+        ];
+
+        assert_eq!(infer_chapters(&[], secs(30)), []);
+        assert_eq!(infer_chapters(&history[..3], secs(30)), []);
+        assert_eq!(infer_chapters(&history, secs(30)), [secs(15), secs(25)]);
+        assert_eq!(infer_chapters(&history, secs(300)), [secs(25)]);
+        assert_eq!(infer_chapters(&history, secs(1)), [secs(15), secs(25)]);
+    }
+
+    #[cfg(feature = "chapter-detection")]
+    #[test]
+    fn test_record_tot() {
+        let secs = Duration::from_secs;
+        let millis = Duration::from_millis;
+
+        let mut history = Vec::new();
+        record_tot(&mut history, secs(0), secs(1000));
+        record_tot(&mut history, secs(5), secs(1005));
+        // 前方にシークした
+        record_tot(&mut history, secs(20), secs(1205));
+        // 後方にシークして同じTOTを再び受信した
+        record_tot(&mut history, secs(10), secs(1010));
+        record_tot(&mut history, millis(5_000) + millis(300), secs(1005));
+        record_tot(&mut history, secs(15), secs(1200));
+        record_tot(&mut history, millis(20_000) - millis(300), secs(1205));
+
+        assert_eq!(
+            history,
+            [
+                (secs(0), secs(1000)),
+                (secs(5), secs(1005)),
+                (secs(10), secs(1010)),
+                (secs(15), secs(1200)),
+                (secs(20), secs(1205)),
+            ]
+        );
+        assert_eq!(infer_chapters(&history, secs(30)), [secs(15)]);
+    }
+
+    #[test]
+    fn test_pcr_discontinuity() {
+        let threshold = Duration::from_secs(2);
+        let ts = |secs| Timestamp::from_duration(Duration::from_secs(secs));
+
+        assert!(!is_pcr_discontinuous(ts(10), ts(10), threshold));
+        assert!(!is_pcr_discontinuous(ts(10), ts(12), threshold));
+        assert!(is_pcr_discontinuous(ts(10), ts(13), threshold));
+        // 逆方向への飛び
+        assert!(!is_pcr_discontinuous(ts(10), ts(9), threshold));
+        assert!(is_pcr_discontinuous(ts(10), ts(5), threshold));
+        // ラップアラウンド
+        assert!(!is_pcr_discontinuous(Timestamp::MAX, ts(1), threshold));
+        assert!(is_pcr_discontinuous(Timestamp::MAX, ts(3), threshold));
+    }
+
+    #[test]
+    fn test_bitrate_meter() {
+        let ts = |millis| Timestamp::from_duration(Duration::from_millis(millis));
+
+        // 計測区間の開始前に受信したバイト数は無視される
+        let mut meter = BitrateMeter {
+            video_bytes: 999,
+            ..Default::default()
+        };
+        assert_eq!(meter.on_pcr(ts(0)), None);
+
+        meter.video_bytes = 500_000;
+        meter.audio_bytes = 48_000;
+        assert_eq!(meter.on_pcr(ts(1000)), None);
+        assert_eq!(meter.on_pcr(ts(2000)), Some((2000, 192)));
+
+        // 5%以内の変化は通知しない
+        meter.video_bytes = 520_000;
+        meter.audio_bytes = 48_000;
+        assert_eq!(meter.on_pcr(ts(4000)), None);
+
+        meter.video_bytes = 600_000;
+        meter.audio_bytes = 48_000;
+        assert_eq!(meter.on_pcr(ts(6000)), Some((2400, 192)));
+
+        // やり直し後は次のPCRから計測する
+        meter.reset();
+        meter.video_bytes = 600_000;
+        assert_eq!(meter.on_pcr(ts(8000)), None);
+        assert_eq!(meter.on_pcr(ts(10000)), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_packet_stats() {
+        let mut packet = isdb::Packet([0xFF; 188]);
+        packet.0[0..4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+        let mut error_packet = packet.clone();
+        error_packet.0[1] |= 0x80;
+
+        let stats = PacketStats::default();
+        stats.record(&packet);
+        stats.record(&error_packet);
+        stats.record(&packet);
+        assert_eq!(stats.packets.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.errors.load(Ordering::Relaxed), 1);
+
+        stats.reset();
+        assert_eq!(stats.packets.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.errors.load(Ordering::Relaxed), 0);
+    }
+
+    /// 何もしない`Sink`。
+    struct NullSink;
+
+    impl Sink for NullSink {
+        fn on_services_updated(&mut self, _: &ServiceMap) {}
+        fn on_streams_updated(&mut self, _: &Service) {}
+        fn on_event_updated(&mut self, _: &Service, _: bool) {}
+        fn on_service_changed(&mut self, _: &Service) {}
+        fn on_stream_changed(&mut self, _: bool, _: StreamChanged) {}
+        fn on_video_packet(&mut self, _: Option<Duration>, _: &[u8]) {}
+        fn on_audio_packet(&mut self, _: Option<Duration>, _: &[u8]) {}
+        fn on_caption(&mut self, _: Option<Duration>, _: &isdb::filters::sorter::Caption) {}
+        fn on_superimpose(&mut self, _: Option<Duration>, _: &isdb::filters::sorter::Caption) {}
+        fn on_timestamp_updated(&mut self, _: Duration) {}
+        fn on_end_of_stream(&mut self) {}
+        fn on_stream_error(&mut self, _: io::Error) {}
+        fn needs_es(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_state_snapshot() {
+        fn psi_packet(pid: u16, section: &[u8]) -> isdb::Packet {
+            let mut packet = isdb::Packet([0xFF; 188]);
+            packet.0[0..5].copy_from_slice(&[0x47, 0x40 | (pid >> 8) as u8, pid as u8, 0x10, 0x00]);
+            packet.0[5..5 + section.len()].copy_from_slice(section);
+            packet
+        }
+        fn pcr_packet(pid: u16, base: u64) -> isdb::Packet {
+            let mut packet = isdb::Packet([0xFF; 188]);
+            packet.0[0..6].copy_from_slice(&[0x47, (pid >> 8) as u8, pid as u8, 0x20, 0xB7, 0x10]);
+            packet.0[6..12].copy_from_slice(&[
+                (base >> 25) as u8,
+                (base >> 17) as u8,
+                (base >> 9) as u8,
+                (base >> 1) as u8,
+                ((base & 1) as u8) << 7 | 0x7E,
+                0x00,
+            ]);
+            packet
+        }
+
+        // サービス0x0400（PMT_PID=0x01F0）のみを含むPAT
+        const PAT: &[u8] = &[
+            0x00, 0xB0, 0x0D, 0x00, 0x01, 0xC1, 0x00, 0x00, 0x04, 0x00, 0xE1, 0xF0, 0x1C, 0x6C,
+            0x43, 0xF6,
+        ];
+        // PCR_PID=0x0100、MPEG2映像（PID=0x0100）、AAC音声（PID=0x0110）のPMT
+        const PMT: &[u8] = &[
+            0x02, 0xB0, 0x17, 0x04, 0x00, 0xC1, 0x00, 0x00, 0xE1, 0x00, 0xF0, 0x00, 0x02, 0xE1,
+            0x00, 0xF0, 0x00, 0x0F, 0xE1, 0x10, 0xF0, 0x00, 0x22, 0x0C, 0x14, 0x36,
+        ];
+        // MJD=0xE2D9、12時0分0秒のTOT
+        const TOT: &[u8] = &[
+            0x73, 0x70, 0x0B, 0xE2, 0xD9, 0x12, 0x00, 0x00, 0xF0, 0x00, 0xA4, 0x65, 0x53, 0xB5,
+        ];
+
+        let data = [
+            psi_packet(0x0000, PAT).0,
+            psi_packet(0x01F0, PMT).0,
+            pcr_packet(0x0100, 0).0,
+            pcr_packet(0x0100, 90000).0,
+            psi_packet(0x0014, TOT).0,
+            pcr_packet(0x0100, 180000).0,
+        ]
+        .concat();
+
+        let extractor = Extractor::new();
+        let handler = extractor.handler();
+        let mut worker = extractor.into_worker(io::Cursor::new(data), NullSink);
+
+        let snapshot = handler.state_snapshot();
+        assert!(snapshot.services.is_empty());
+        assert!(snapshot.selected_stream.is_none());
+        assert_eq!(snapshot.duration, None);
+        assert_eq!(snapshot.position, None);
+        assert_eq!(snapshot.timestamp, None);
+
+        // PAT・PMTによりサービスが選択される
+        worker.next_packet();
+        worker.next_packet();
+        let snapshot = handler.state_snapshot();
+        let service_id = ServiceId::new(0x0400).unwrap();
+        assert_eq!(
+            snapshot.services.keys().copied().collect::<Vec<_>>(),
+            [service_id]
+        );
+        let ss = snapshot.selected_stream.unwrap();
+        assert_eq!(ss.service_id, service_id);
+        assert_eq!(ss.video_stream.pid(), isdb::Pid::new(0x0100));
+        assert_eq!(ss.audio_stream.pid(), isdb::Pid::new(0x0110));
+        assert_eq!(snapshot.position, Some(Duration::ZERO));
+        assert_eq!(snapshot.timestamp, None);
+
+        // PCRにより再生位置が進む
+        worker.next_packet();
+        worker.next_packet();
+        let snapshot = handler.state_snapshot();
+        assert_eq!(snapshot.position, Some(Duration::from_secs(1)));
+        assert_eq!(snapshot.position, handler.position());
+        assert_eq!(snapshot.timestamp, None);
+
+        // TOTにより日付時刻が得られる
+        let tot = Duration::from_secs(
+            isdb::time::DateTime {
+                date: isdb::time::MjdDate(0xE2D9),
+                hour: 12,
+                minute: 0,
+                second: 0,
+            }
+            .ntp_timestamp(),
+        );
+        worker.next_packet();
+        worker.next_packet();
+        let snapshot = handler.state_snapshot();
+        assert_eq!(snapshot.position, Some(Duration::from_secs(2)));
+        assert_eq!(snapshot.timestamp, Some(tot + Duration::from_secs(1)));
+        assert_eq!(snapshot.timestamp, handler.timestamp());
+        assert_eq!(snapshot.duration, None);
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let extractor = Extractor::new();
+        let handler = extractor.handler();
+        assert!(handler.bookmarks().is_empty());
+
+        // 指定した順序のまま保持する
+        let marks = vec![
+            (Duration::from_secs(60), "B".to_string()),
+            (Duration::from_secs(10), "A".to_string()),
+            (Duration::from_secs(90), "C".to_string()),
+        ];
+        handler.set_bookmarks(marks.clone());
+        assert_eq!(handler.bookmarks(), marks);
+
+        // 追加
+        let mut marks = handler.bookmarks();
+        marks.push((Duration::from_secs(30), "D".to_string()));
+        handler.set_bookmarks(marks.clone());
+        assert_eq!(handler.bookmarks(), marks);
+
+        // 削除
+        marks.retain(|(_, name)| name != "B");
+        handler.set_bookmarks(marks);
+        assert_eq!(
+            handler.bookmarks(),
+            [
+                (Duration::from_secs(10), "A".to_string()),
+                (Duration::from_secs(90), "C".to_string()),
+                (Duration::from_secs(30), "D".to_string()),
+            ],
+        );
+
+        // 先頭へ巻き戻しても保持される
+        let mut worker = extractor.into_worker(io::Cursor::new(Vec::new()), NullSink);
+        worker.reset();
+        assert_eq!(handler.bookmarks().len(), 3);
+
+        handler.set_bookmarks(Vec::new());
+        assert!(handler.bookmarks().is_empty());
+    }
+
+    #[test]
+    fn test_packet_stats_reset() {
+        let mut packet = isdb::Packet([0xFF; 188]);
+        packet.0[0..4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+        let mut error_packet = packet.clone();
+        error_packet.0[1] |= 0x80;
+        let data = [packet.0, error_packet.0, packet.0].concat();
+
+        let extractor = Extractor::new();
+        let handler = extractor.handler();
+        let mut worker = extractor.into_worker(io::Cursor::new(data.clone()), NullSink);
+        for _ in 0..3 {
+            worker.next_packet();
+        }
+        assert_eq!(handler.packet_count(), 3);
+        assert_eq!(handler.error_count(), 1);
+
+        // 頭出しで初期化される
+        worker.reset();
+        assert_eq!(handler.packet_count(), 0);
+        assert_eq!(handler.error_count(), 0);
+        worker.next_packet();
+        worker.next_packet();
+        assert_eq!(handler.packet_count(), 2);
+        assert_eq!(handler.error_count(), 1);
+
+        // 新たに開いたストリームは別に数える
+        let extractor = Extractor::new();
+        let new_handler = extractor.handler();
+        assert_eq!(new_handler.packet_count(), 0);
+        assert_eq!(new_handler.error_count(), 0);
+        let mut new_worker = extractor.into_worker(io::Cursor::new(data), NullSink);
+        new_worker.next_packet();
+        assert_eq!(new_handler.packet_count(), 1);
+        assert_eq!(new_handler.error_count(), 0);
+        assert_eq!(handler.packet_count(), 2);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let start = Instant::now();
+        let mut rl = RateLimiter::new(188 * 100);
+
+        // 最初のパケットでは計測を開始するだけで、既に1パケット分の時間待つ必要がある
+        assert_eq!(rl.delay(start, 188), Duration::from_millis(10));
+        // 時間が経過していれば待つ必要はない
+        assert_eq!(
+            rl.delay(start + Duration::from_millis(20), 188),
+            Duration::ZERO
+        );
+        // 読み取り過ぎた場合は差分だけ待つ
+        for _ in 0..7 {
+            rl.delay(start + Duration::from_millis(20), 188);
+        }
+        assert_eq!(
+            rl.delay(start + Duration::from_millis(20), 188),
+            Duration::from_millis(80)
+        );
+
+        rl.reset();
+        let now = start + Duration::from_secs(1);
+        assert_eq!(rl.delay(now, 188), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_pid_rate_limiter() {
+        let start = Instant::now();
+        let mut rl = PidRateLimiter::new(Duration::from_secs(1));
+        let video = isdb::Pid::new(0x0111);
+        let audio = isdb::Pid::new(0x0112);
+
+        assert!(rl.check(start, video));
+        // 間隔内の同じPIDは通知しない
+        assert!(!rl.check(start + Duration::from_millis(500), video));
+        // PIDが異なれば通知する
+        assert!(rl.check(start + Duration::from_millis(500), audio));
+        // 間隔が経過すれば再び通知する
+        assert!(rl.check(start + Duration::from_secs(1), video));
+        assert!(!rl.check(start + Duration::from_millis(1200), audio));
+        assert!(rl.check(start + Duration::from_millis(1500), audio));
+    }
+
+    #[test]
+    fn test_pid_rate_limiter_packet() {
+        let start = Instant::now();
+        let mut rl = PidRateLimiter::new(Duration::from_secs(1)).with_skip(2);
+        let video = isdb::Pid::new(0x0111);
+        let audio = isdb::Pid::new(0x0112);
+        let no_clock = || -> Instant { panic!("時刻を確認しない") };
+
+        assert!(rl.check_packet(video, || start));
+        // 時刻を確認した後の2回は時刻を確認せずに抑制する
+        assert!(!rl.check_packet(video, no_clock));
+        assert!(!rl.check_packet(video, no_clock));
+        // PIDごとに数える
+        assert!(rl.check_packet(audio, || start));
+        assert!(!rl.check_packet(video, || start + Duration::from_millis(500)));
+        assert!(!rl.check_packet(video, no_clock));
+        assert!(!rl.check_packet(video, no_clock));
+        // 間隔が経過すれば再び通知する
+        assert!(rl.check_packet(video, || start + Duration::from_secs(1)));
+        assert!(!rl.check_packet(video, no_clock));
+    }
+
     #[test]
     fn test_playback_time() {
         const INIT_DUR: Duration = Duration::from_secs(100);
-        const INIT_PT: PlaybackTime = PlaybackTime {
-            prev_ts: None,
-            duration: INIT_DUR,
-        };
+        const INIT_PT: PlaybackTime = PlaybackTime::reset_to(None, INIT_DUR);
 
         // 普通のパターン
         let mut pt = INIT_PT;
@@ -1601,6 +3427,109 @@ mod tests {
         assert_eq!(INIT_DUR - pt.duration, Duration::from_secs_f64(0.2851));
     }
 
+    #[test]
+    fn test_playback_time_elapsed_since() {
+        let pt = PlaybackTime::default();
+        assert_eq!(pt.elapsed_since(Timestamp::ZERO), None);
+
+        let pt = PlaybackTime::reset_to(Some(Duration::from_secs(3).into()), Duration::ZERO);
+        assert_eq!(
+            pt.elapsed_since(Duration::from_secs(1).into()),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            pt.elapsed_since(Duration::from_secs(3).into()),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(pt.elapsed_since(Duration::from_secs(4).into()), None);
+
+        // ラップアラウンド
+        let pt = PlaybackTime::reset_to(Some(Timestamp::new(7473, 0)), Duration::ZERO);
+        assert_eq!(
+            pt.elapsed_since(Timestamp::new(8589906560, 0)),
+            Some(Duration::from_secs_f64(0.3945))
+        );
+    }
+
+    #[test]
+    fn test_tot_timestamp() {
+        // 1982-09-06T12:34:56
+        let datetime = DateTime::read(&[0xB0, 0xA2, 0x12, 0x34, 0x56]);
+        let base = Duration::from_secs(datetime.ntp_timestamp());
+        let tot = Tot {
+            datetime,
+            base_pcr: Timestamp::from_duration(Duration::from_secs(100)),
+        };
+
+        assert_eq!(tot.timestamp(tot.base_pcr), base);
+        assert_eq!(
+            tot.timestamp(Timestamp::from_duration(Duration::from_millis(101_500))),
+            base + Duration::from_millis(1_500)
+        );
+        // TOTを受信した位置より前
+        assert_eq!(
+            tot.timestamp(Timestamp::from_duration(Duration::from_millis(98_500))),
+            base - Duration::from_millis(1_500)
+        );
+
+        // ラップアラウンドを跨ぐ
+        let tot = Tot {
+            datetime,
+            base_pcr: Timestamp::MAX,
+        };
+        assert_eq!(
+            tot.timestamp(Timestamp::from_duration(Duration::from_secs(1))),
+            base + Duration::from_secs(1) + Timestamp::from_full(1).to_duration()
+        );
+    }
+
+    #[test]
+    fn test_positioned_read_remaining() {
+        let mut read = PositionedRead::new(io::Cursor::new(vec![0; 188 * 3]));
+        assert_eq!(read.total_len(), Some(188 * 3));
+        assert_eq!(read.remaining(), Some(188 * 3));
+
+        read.read_exact(&mut [0; 188]).unwrap();
+        assert_eq!(read.remaining(), Some(188 * 2));
+
+        read.seek(SeekFrom::End(0)).unwrap();
+        assert_eq!(read.remaining(), Some(0));
+
+        // 終端を超えてシークしても負にはならない
+        read.seek(SeekFrom::End(188)).unwrap();
+        assert_eq!(read.remaining(), Some(0));
+
+        let read = PositionedRead::new(io::Cursor::new(&[0; 188][..]));
+        assert_eq!(read.total_len(), Some(188));
+    }
+
+    #[test]
+    fn test_forward_seek_pos() {
+        assert_eq!(
+            forward_seek_pos(188 * 10, 188 * 5, Some(188 * 100), 188 * 20),
+            SeekFrom::Current(188 * 5)
+        );
+        assert_eq!(
+            forward_seek_pos(188 * 10, 188 * 5, None, 188 * 20),
+            SeekFrom::Current(188 * 5)
+        );
+
+        // 終端を超える場合は最後のPCRを探す位置に留める
+        assert_eq!(
+            forward_seek_pos(188 * 90, 188 * 10, Some(188 * 100), 188 * 20),
+            SeekFrom::Start(188 * 80)
+        );
+        assert_eq!(
+            forward_seek_pos(188 * 90, 188 * 50, Some(188 * 100), 188 * 20),
+            SeekFrom::Start(188 * 80)
+        );
+        // ファイルがtail_probe_sizeより小さい場合は先頭
+        assert_eq!(
+            forward_seek_pos(188 * 5, 188 * 10, Some(188 * 10), 188 * 20),
+            SeekFrom::Start(0)
+        );
+    }
+
     #[test]
     fn test_stream_length() {
         assert_eq!(
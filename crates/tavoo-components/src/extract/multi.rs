@@ -0,0 +1,298 @@
+//! 複数の[`Sink`]に同じ情報を配信する。
+
+use std::io;
+use std::time::Duration;
+
+use isdb::filters::sorter::{Caption, Service, ServiceMap};
+use isdb::packet::TransportScramblingControl;
+use isdb::time::Timestamp;
+use isdb::Pid;
+
+use super::{ExtractorError, Sink, StreamChanged};
+
+/// `io::Error`は複製できないため、種類とメッセージを引き継いだ新しいエラーを生成する。
+fn clone_io_error(error: &io::Error) -> io::Error {
+    io::Error::new(error.kind(), error.to_string())
+}
+
+/// 2つの[`Sink`]に同じ情報を配信する[`Sink`]。
+///
+/// `MultiSink<MultiSink<A, B>, C>`のように入れ子にすることで3つ以上の`Sink`にも配信できる。
+#[derive(Debug, Clone)]
+pub struct MultiSink<A: Sink, B: Sink> {
+    a: A,
+    b: B,
+}
+
+impl<A: Sink, B: Sink> MultiSink<A, B> {
+    /// `MultiSink`を生成する。
+    #[inline]
+    pub fn new(a: A, b: B) -> MultiSink<A, B> {
+        MultiSink { a, b }
+    }
+
+    /// 1つ目の`Sink`への参照を返す。
+    #[inline]
+    pub fn first(&self) -> &A {
+        &self.a
+    }
+
+    /// 2つ目の`Sink`への参照を返す。
+    #[inline]
+    pub fn second(&self) -> &B {
+        &self.b
+    }
+
+    /// 内包する`Sink`を分解して返す。
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+/// 2つの[`Sink`]に同じ情報を配信する[`MultiSink`]を生成する。
+#[inline]
+pub fn multi_sink<A: Sink, B: Sink>(a: A, b: B) -> MultiSink<A, B> {
+    MultiSink::new(a, b)
+}
+
+impl<A: Sink, B: Sink> Sink for MultiSink<A, B> {
+    fn on_services_updated(&mut self, services: &ServiceMap) {
+        self.a.on_services_updated(services);
+        self.b.on_services_updated(services);
+    }
+
+    fn on_streams_updated(&mut self, service: &Service) {
+        self.a.on_streams_updated(service);
+        self.b.on_streams_updated(service);
+    }
+
+    fn on_event_updated(&mut self, service: &Service, is_present: bool) {
+        self.a.on_event_updated(service, is_present);
+        self.b.on_event_updated(service, is_present);
+    }
+
+    fn on_service_changed(&mut self, service: &Service) {
+        self.a.on_service_changed(service);
+        self.b.on_service_changed(service);
+    }
+
+    fn on_stream_changed(&mut self, immediate: bool, changed: StreamChanged) {
+        self.a.on_stream_changed(immediate, changed);
+        self.b.on_stream_changed(immediate, changed);
+    }
+
+    fn on_video_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        self.a.on_video_packet(pos, payload);
+        self.b.on_video_packet(pos, payload);
+    }
+
+    fn on_audio_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        self.a.on_audio_packet(pos, payload);
+        self.b.on_audio_packet(pos, payload);
+    }
+
+    fn on_caption(&mut self, pos: Option<Duration>, caption: &Caption) {
+        self.a.on_caption(pos, caption);
+        self.b.on_caption(pos, caption);
+    }
+
+    fn on_superimpose(&mut self, pos: Option<Duration>, caption: &Caption) {
+        self.a.on_superimpose(pos, caption);
+        self.b.on_superimpose(pos, caption);
+    }
+
+    fn on_scrambling_changed(&mut self, pid: Pid, new_state: TransportScramblingControl) {
+        self.a.on_scrambling_changed(pid, new_state);
+        self.b.on_scrambling_changed(pid, new_state);
+    }
+
+    fn on_scrambled_packet(&mut self, pid: Pid) {
+        self.a.on_scrambled_packet(pid);
+        self.b.on_scrambled_packet(pid);
+    }
+
+    fn on_pcr_discontinuity(&mut self, old: Timestamp, new: Timestamp) {
+        self.a.on_pcr_discontinuity(old, new);
+        self.b.on_pcr_discontinuity(old, new);
+    }
+
+    fn on_bitrate_updated(&mut self, video_kbps: u32, audio_kbps: u32) {
+        self.a.on_bitrate_updated(video_kbps, audio_kbps);
+        self.b.on_bitrate_updated(video_kbps, audio_kbps);
+    }
+
+    fn on_volume_requested(&mut self, volume: f32) {
+        self.a.on_volume_requested(volume);
+        self.b.on_volume_requested(volume);
+    }
+
+    fn on_timestamp_updated(&mut self, timestamp: Duration) {
+        self.a.on_timestamp_updated(timestamp);
+        self.b.on_timestamp_updated(timestamp);
+    }
+
+    fn on_end_of_stream(&mut self) {
+        self.a.on_end_of_stream();
+        self.b.on_end_of_stream();
+    }
+
+    fn on_stream_error(&mut self, error: io::Error) {
+        self.a.on_stream_error(clone_io_error(&error));
+        self.b.on_stream_error(error);
+    }
+
+    fn on_seek_error(&mut self, error: ExtractorError) {
+        self.a.on_seek_error(error);
+        self.b.on_seek_error(error);
+    }
+
+    /// いずれかの`Sink`がESを必要としていれば`true`を返す。
+    fn needs_es(&self) -> bool {
+        self.a.needs_es() || self.b.needs_es()
+    }
+}
+
+/// 任意個の[`Sink`]に同じ情報を配信する[`Sink`]。
+pub struct MultiSinkVec(Vec<Box<dyn Sink + Send>>);
+
+impl MultiSinkVec {
+    /// 内包する`Sink`の一覧を返す。
+    #[inline]
+    pub fn into_inner(self) -> Vec<Box<dyn Sink + Send>> {
+        self.0
+    }
+}
+
+impl std::fmt::Debug for MultiSinkVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MultiSinkVec")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// 任意個の[`Sink`]に同じ情報を配信する[`MultiSinkVec`]を生成する。
+#[inline]
+pub fn multi_sink_vec(sinks: Vec<Box<dyn Sink + Send>>) -> MultiSinkVec {
+    MultiSinkVec(sinks)
+}
+
+impl Sink for MultiSinkVec {
+    fn on_services_updated(&mut self, services: &ServiceMap) {
+        for sink in &mut self.0 {
+            sink.on_services_updated(services);
+        }
+    }
+
+    fn on_streams_updated(&mut self, service: &Service) {
+        for sink in &mut self.0 {
+            sink.on_streams_updated(service);
+        }
+    }
+
+    fn on_event_updated(&mut self, service: &Service, is_present: bool) {
+        for sink in &mut self.0 {
+            sink.on_event_updated(service, is_present);
+        }
+    }
+
+    fn on_service_changed(&mut self, service: &Service) {
+        for sink in &mut self.0 {
+            sink.on_service_changed(service);
+        }
+    }
+
+    fn on_stream_changed(&mut self, immediate: bool, changed: StreamChanged) {
+        for sink in &mut self.0 {
+            sink.on_stream_changed(immediate, changed);
+        }
+    }
+
+    fn on_video_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        for sink in &mut self.0 {
+            sink.on_video_packet(pos, payload);
+        }
+    }
+
+    fn on_audio_packet(&mut self, pos: Option<Duration>, payload: &[u8]) {
+        for sink in &mut self.0 {
+            sink.on_audio_packet(pos, payload);
+        }
+    }
+
+    fn on_caption(&mut self, pos: Option<Duration>, caption: &Caption) {
+        for sink in &mut self.0 {
+            sink.on_caption(pos, caption);
+        }
+    }
+
+    fn on_superimpose(&mut self, pos: Option<Duration>, caption: &Caption) {
+        for sink in &mut self.0 {
+            sink.on_superimpose(pos, caption);
+        }
+    }
+
+    fn on_scrambling_changed(&mut self, pid: Pid, new_state: TransportScramblingControl) {
+        for sink in &mut self.0 {
+            sink.on_scrambling_changed(pid, new_state);
+        }
+    }
+
+    fn on_scrambled_packet(&mut self, pid: Pid) {
+        for sink in &mut self.0 {
+            sink.on_scrambled_packet(pid);
+        }
+    }
+
+    fn on_pcr_discontinuity(&mut self, old: Timestamp, new: Timestamp) {
+        for sink in &mut self.0 {
+            sink.on_pcr_discontinuity(old, new);
+        }
+    }
+
+    fn on_bitrate_updated(&mut self, video_kbps: u32, audio_kbps: u32) {
+        for sink in &mut self.0 {
+            sink.on_bitrate_updated(video_kbps, audio_kbps);
+        }
+    }
+
+    fn on_volume_requested(&mut self, volume: f32) {
+        for sink in &mut self.0 {
+            sink.on_volume_requested(volume);
+        }
+    }
+
+    fn on_timestamp_updated(&mut self, timestamp: Duration) {
+        for sink in &mut self.0 {
+            sink.on_timestamp_updated(timestamp);
+        }
+    }
+
+    fn on_end_of_stream(&mut self) {
+        for sink in &mut self.0 {
+            sink.on_end_of_stream();
+        }
+    }
+
+    fn on_stream_error(&mut self, error: io::Error) {
+        let Some((last, rest)) = self.0.split_last_mut() else {
+            return;
+        };
+        for sink in rest {
+            sink.on_stream_error(clone_io_error(&error));
+        }
+        last.on_stream_error(error);
+    }
+
+    fn on_seek_error(&mut self, error: ExtractorError) {
+        for sink in &mut self.0 {
+            sink.on_seek_error(error);
+        }
+    }
+
+    /// いずれかの`Sink`がESを必要としていれば`true`を返す。
+    fn needs_es(&self) -> bool {
+        self.0.iter().any(|sink| sink.needs_es())
+    }
+}
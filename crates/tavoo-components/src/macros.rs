@@ -1,4 +1,5 @@
 /// 値がエラーの際にラベル付きブロックを抜ける。
+#[cfg_attr(not(windows), allow(unused_macros))]
 macro_rules! tri {
     ($label:lifetime, $v:expr) => {
         match $v {
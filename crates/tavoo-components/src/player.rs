@@ -79,7 +79,10 @@ pub trait EventHandler: Send + 'static {
     fn on_service_changed(&self, service: &isdb::filters::sorter::Service);
 
     /// 選択中サービスのストリームについて何かが変更された際に呼ばれる。
-    fn on_stream_changed(&self, changed: crate::extract::StreamChanged);
+    ///
+    /// `immediate`はストリームを即座に切り替える必要がある場合（サービス切り替えやシーク時など）に
+    /// `true`となる。
+    fn on_stream_changed(&self, immediate: bool, changed: crate::extract::StreamChanged);
 
     /// 選択中サービスで字幕パケットを受信した際に呼ばれる。
     fn on_caption(&self, pos: Option<Duration>, caption: &isdb::filters::sorter::Caption);
@@ -114,6 +117,46 @@ pub enum DualMonoMode {
     Mix,
 }
 
+/// 映像をウィンドウ内の映像領域に表示する際の縦横比の扱い。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AspectRatioMode {
+    /// 映像の縦横比を保ち、余白を黒帯で埋める。
+    #[default]
+    Preserve,
+    /// 縦横比を無視して映像領域全体に引き伸ばす。
+    Stretch,
+    /// 中央付近の縦横比を保ちつつ、端を非線形に引き伸ばして映像領域全体を埋める。
+    NonLinearStretch,
+}
+
+/// 映像のフレームを画像として取得したもの。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoImage {
+    /// 画像の幅。
+    pub width: u32,
+    /// 画像の高さ。
+    pub height: u32,
+    /// 上の行から順に並べた、1画素4バイトのRGBAデータ。
+    pub data: Vec<u8>,
+}
+
+/// ある時点における[`Player`]の状態。
+///
+/// 各値は同時に取得されたものであり、相互に矛盾しない。
+#[derive(Debug, Clone)]
+pub struct PlayerSnapshot {
+    /// 現在のストリームにおける全サービスの情報。
+    pub services: isdb::filters::sorter::ServiceMap,
+    /// 選択中のサービス・ストリーム。
+    pub selected: Option<crate::extract::SelectedStream>,
+    /// 動画の長さ。
+    pub duration: Option<Duration>,
+    /// 既定サービスのPCRを元にした再生位置。
+    pub position: Option<Duration>,
+    /// TOTとPCRによって計算される、1900年1月1日からの経過時間。
+    pub timestamp: Option<Duration>,
+}
+
 /// TSを再生するためのプレイヤー。
 pub struct Player<H> {
     inner: imp::Player<H>,
@@ -139,6 +182,20 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.open(path)
     }
 
+    /// メモリ上のTSを開き、再生を開始する。
+    ///
+    /// ファイルを介さずにTSを再生できるため、テスト等で合成したTSを再生する場合に使う。
+    #[inline]
+    pub fn open_bytes(&mut self, data: &'static [u8]) -> Result<()> {
+        self.inner.open_bytes(data)
+    }
+
+    /// [`Player::open_bytes`]と同様だが、所有権を持つバッファからTSを開く。
+    #[inline]
+    pub fn open_vec(&mut self, data: Vec<u8>) -> Result<()> {
+        self.inner.open_vec(data)
+    }
+
     /// TSの処理中にイベントループに送られた[`PlayerEvent`]を処理する。
     #[inline]
     pub fn handle_event(&mut self, event: PlayerEvent) -> Result<()> {
@@ -163,6 +220,20 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.stop()
     }
 
+    /// 再生中であれば一時停止し、そうでなければ再生する。
+    #[inline]
+    pub fn play_or_pause(&mut self) -> Result<()> {
+        self.inner.play_or_pause()
+    }
+
+    /// 再生中かどうかを返す。
+    ///
+    /// 再生が要求されているがまだ開始されていない場合も`true`を返す。
+    #[inline]
+    pub fn is_playing(&self) -> Result<bool> {
+        self.inner.is_playing()
+    }
+
     /// 映像を再描画する。
     ///
     /// 一時停止中などで映像が描画されない場合があるため、
@@ -204,6 +275,14 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.set_position(pos)
     }
 
+    /// 選択中サービスにおける現在のイベント（番組）の開始位置にシークする。
+    ///
+    /// TOTやEITを受信していない場合はエラーを返す。
+    #[inline]
+    pub fn seek_to_event_start(&mut self) -> Result<()> {
+        self.inner.seek_to_event_start()
+    }
+
     /// 音量を取得する。
     #[inline]
     pub fn volume(&self) -> Result<f32> {
@@ -246,12 +325,63 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.set_rate(value)
     }
 
+    /// 音声の遅延をミリ秒単位で取得する。
+    #[inline]
+    pub fn audio_delay_ms(&self) -> Result<i32> {
+        self.inner.audio_delay_ms()
+    }
+
+    /// 音声の遅延をミリ秒単位で設定する。
+    ///
+    /// 正の値では音声を遅らせ、負の値では音声を早める。値は±2000ミリ秒の範囲に丸められる。
+    /// 遅延は以降に処理される音声に適用されるため、変更直後は音声が途切れたり重なったりする場合がある。
+    #[inline]
+    pub fn set_audio_delay_ms(&mut self, ms: i32) -> Result<()> {
+        self.inner.set_audio_delay_ms(ms)
+    }
+
+    /// 映像の縦横比の扱いを取得する。
+    #[inline]
+    pub fn aspect_ratio_mode(&self) -> Result<AspectRatioMode> {
+        self.inner.aspect_ratio_mode()
+    }
+
+    /// 映像の縦横比の扱いを設定する。
+    ///
+    /// 設定はファイルを開き直しても維持される。
+    #[inline]
+    pub fn set_aspect_ratio_mode(&mut self, mode: AspectRatioMode) -> Result<()> {
+        self.inner.set_aspect_ratio_mode(mode)
+    }
+
+    /// 字幕・文字スーパーの表示遅延をミリ秒単位で取得する。
+    #[inline]
+    pub fn subtitle_delay_ms(&self) -> Result<i64> {
+        self.inner.subtitle_delay_ms()
+    }
+
+    /// 字幕・文字スーパーの表示遅延をミリ秒単位で設定する。
+    ///
+    /// 正の値では字幕を遅らせ、負の値では字幕を早める。
+    /// 遅延は[`EventHandler::on_caption`]等に渡される再生位置に適用され、
+    /// 早めた結果が0を下回る場合は0となる。設定はファイルを開き直しても維持される。
+    #[inline]
+    pub fn set_subtitle_delay_ms(&mut self, ms: i64) -> Result<()> {
+        self.inner.set_subtitle_delay_ms(ms)
+    }
+
     /// 映像の解像度を返す。
     #[inline]
     pub fn video_size(&self) -> Result<(u32, u32)> {
         self.inner.video_size()
     }
 
+    /// 現在表示されている映像のフレームを画像として取得する。
+    #[inline]
+    pub fn capture_image(&self) -> Result<VideoImage> {
+        self.inner.capture_image()
+    }
+
     /// 音声のチャンネル数を返す。
     #[inline]
     pub fn audio_channels(&self) -> Result<u8> {
@@ -310,6 +440,15 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.services()
     }
 
+    /// サービス一覧や選択中のストリーム、再生位置等をまとめて取得する。
+    ///
+    /// 個別のメソッドで取得した場合と異なり、各値は相互に矛盾しない。
+    /// TSを開いていない状態では`None`を返す。
+    #[inline]
+    pub fn snapshot(&self) -> Option<PlayerSnapshot> {
+        self.inner.snapshot()
+    }
+
     /// 指定されたサービスを選択する。
     ///
     /// `service_id`に`None`を指定した場合、既定のサービスを選択する。
@@ -324,6 +463,14 @@ impl<H: EventHandler + Clone> Player<H> {
         self.inner.select_video_stream(component_tag)
     }
 
+    /// 指定されたPIDの映像ストリームを選択する。
+    ///
+    /// マルチビューTV等、同一サービス内の複数の映像ストリームを切り替えるのに使う。
+    #[inline]
+    pub fn select_video_stream_by_pid(&mut self, pid: isdb::Pid) -> Result<()> {
+        self.inner.select_video_stream_by_pid(pid)
+    }
+
     /// 指定されたコンポーネントタグの音声ストリームを選択する。
     #[inline]
     pub fn select_audio_stream(&mut self, component_tag: u8) -> Result<()> {
@@ -335,3 +482,41 @@ const _: () = {
     const fn assert_send<T: Send>() {}
     assert_send::<PlayerEvent>();
 };
+
+/// 再生位置`pos`を`delay_ms`ミリ秒だけずらす。
+///
+/// 負の値で位置が0を下回る場合は0に、オーバーフローする場合は元の位置のままとする。
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn delay_pos(pos: Duration, delay_ms: i64) -> Duration {
+    let delay = Duration::from_millis(delay_ms.unsigned_abs());
+    if delay_ms >= 0 {
+        pos.checked_add(delay).unwrap_or(pos)
+    } else {
+        pos.saturating_sub(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_pos() {
+        let pos = Duration::from_secs(10);
+        assert_eq!(delay_pos(pos, 0), pos);
+        assert_eq!(delay_pos(pos, 500), Duration::from_millis(10_500));
+        assert_eq!(delay_pos(pos, -500), Duration::from_millis(9_500));
+
+        // 0を下回る場合は0
+        assert_eq!(delay_pos(pos, -10_000), Duration::ZERO);
+        assert_eq!(delay_pos(pos, -20_000), Duration::ZERO);
+        assert_eq!(delay_pos(pos, i64::MIN), Duration::ZERO);
+
+        // オーバーフローする場合は元の位置のまま
+        assert_eq!(delay_pos(Duration::MAX, 1), Duration::MAX);
+        assert_eq!(
+            delay_pos(pos, i64::MAX),
+            pos + Duration::from_millis(i64::MAX as u64)
+        );
+    }
+}
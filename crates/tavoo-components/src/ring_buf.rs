@@ -1,6 +1,7 @@
 //! 固定長のリングバッファ。
 
 use std::fmt;
+use std::io;
 use std::iter;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
@@ -47,9 +48,13 @@ impl<T, const CAP: usize> RingBuf<T, CAP> {
     /// 既に`CAP`分の要素を保持している場合、最も古い要素が消される。
     pub fn push(&mut self, value: T) {
         if self.len < CAP {
-            // Safety: self.lenはバッファの範囲内であり、かつwriteにより未初期化値はDropされない
+            let mut last = self.first + self.len;
+            if last >= CAP {
+                last -= CAP;
+            }
+            // Safety: lastはバッファの範囲内であり、かつwriteにより未初期化値はDropされない
             unsafe {
-                self.ptr.add(self.len).write(value);
+                self.ptr.add(last).write(value);
             }
             self.len += 1;
         } else {
@@ -67,16 +72,38 @@ impl<T, const CAP: usize> RingBuf<T, CAP> {
         }
     }
 
-    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+    /// 最も古い要素を取り出す。
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
         debug_assert!(self.first < CAP);
-        // Safety: self.firstはバッファの範囲内
+        // Safety: self.firstはバッファの範囲内であり、かつ書き込み済み。
+        // 読み取った要素は範囲外となるため二重にDropされることはない
+        let value = unsafe { self.ptr.add(self.first).read() };
+        self.len -= 1;
+        self.first += 1;
+        if self.first == CAP {
+            self.first = 0;
+        }
+
+        Some(value)
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        debug_assert!(self.first < CAP || self.len == 0);
+        let end = self.first + self.len;
+        let (front_len, back_len) = if end <= CAP {
+            (self.len, 0)
+        } else {
+            (CAP - self.first, end - CAP)
+        };
+        // Safety: どちらのスライスもバッファの範囲内であり、かつ書き込み済み
         unsafe {
             (
-                &mut *ptr::slice_from_raw_parts_mut(
-                    self.ptr.add(self.first),
-                    self.len - self.first,
-                ),
-                &mut *ptr::slice_from_raw_parts_mut(self.ptr, self.first),
+                &mut *ptr::slice_from_raw_parts_mut(self.ptr.add(self.first), front_len),
+                &mut *ptr::slice_from_raw_parts_mut(self.ptr, back_len),
             )
         }
     }
@@ -117,6 +144,48 @@ impl<T, const CAP: usize> RingBuf<T, CAP> {
     }
 }
 
+impl<const CAP: usize> RingBuf<u8, CAP> {
+    /// 読み取り可能なバイト数を返す。
+    #[inline]
+    pub fn bytes_available(&self) -> usize {
+        self.len
+    }
+}
+
+/// 古いバイトから順に読み取り、読み取ったバイトはリングバッファから取り除かれる。
+impl<const CAP: usize> io::Read for RingBuf<u8, CAP> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (front, back) = self.as_mut_slices();
+        let front_len = front.len().min(buf.len());
+        buf[..front_len].copy_from_slice(&front[..front_len]);
+        let back_len = back.len().min(buf.len() - front_len);
+        buf[front_len..front_len + back_len].copy_from_slice(&back[..back_len]);
+
+        let n = front_len + back_len;
+        self.len -= n;
+        self.first += n;
+        if self.first >= CAP {
+            self.first -= CAP;
+        }
+        Ok(n)
+    }
+}
+
+/// 書き込まれたバイトを順に押し込み、容量を超えた場合は古いバイトから消される。
+impl<const CAP: usize> io::Write for RingBuf<u8, CAP> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &b in buf {
+            self.push(b);
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 unsafe impl<T, const CAP: usize> Send for RingBuf<T, CAP> {}
 
 impl<T, const CAP: usize> Drop for RingBuf<T, CAP> {
@@ -209,6 +278,25 @@ impl<'a, T, const CAP: usize> Iterator for Iter<'a, T, CAP> {
     }
 }
 
+impl<'a, T, const CAP: usize> DoubleEndedIterator for Iter<'a, T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            debug_assert!(self.first < CAP);
+            let mut last = self.first + self.len - 1;
+            if last >= CAP {
+                last -= CAP;
+            }
+            // Safety: lastはバッファの範囲内であり、かつ書き込み済み
+            let v = unsafe { &*self.ptr.add(last) };
+            self.len -= 1;
+
+            Some(v)
+        }
+    }
+}
+
 impl<'a, T, const CAP: usize> ExactSizeIterator for Iter<'a, T, CAP> {
     #[inline]
     fn len(&self) -> usize {
@@ -288,6 +376,117 @@ mod tests {
         assert_eq!(buf.as_mut_slices(), (arr(&mut []), arr(&mut [])));
     }
 
+    #[test]
+    fn test_ring_buf_iter_rev() {
+        let mut buf = RingBuf::<u32, 4>::new();
+        assert_eq!(buf.iter().next_back(), None);
+        assert_eq!(buf.iter().rev().count(), 0);
+
+        buf.push(0);
+        buf.push(1);
+        buf.push(2);
+        assert_eq!(buf.iter().rev().collect::<Vec<_>>(), [&2, &1, &0]);
+
+        // 満杯
+        buf.push(3);
+        assert_eq!(buf.iter().rev().collect::<Vec<_>>(), [&3, &2, &1, &0]);
+
+        // 上書きにより折り返している
+        buf.push(4);
+        buf.push(5);
+        assert_eq!(buf.iter().rev().collect::<Vec<_>>(), [&5, &4, &3, &2]);
+        assert_eq!(
+            buf.iter().rev().enumerate().collect::<Vec<_>>(),
+            [(0, &5), (1, &4), (2, &3), (3, &2)]
+        );
+
+        // 両端から
+        let mut iter = buf.iter();
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_ring_buf_pop_front() {
+        let mut buf = RingBuf::<u32, 3>::new();
+        assert_eq!(buf.pop_front(), None);
+
+        buf.push(0);
+        buf.push(1);
+        assert_eq!(buf.pop_front(), Some(0));
+        buf.push(2);
+        buf.push(3);
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+        assert_eq!(buf.as_mut_slices(), (arr(&mut [1, 2]), arr(&mut [3])));
+
+        // 上書き
+        buf.push(4);
+        assert_eq!(buf.iter().collect::<Vec<_>>(), [&2, &3, &4]);
+        assert_eq!(buf.pop_front(), Some(2));
+        assert_eq!(buf.pop_front(), Some(3));
+        assert_eq!(buf.pop_front(), Some(4));
+        assert_eq!(buf.pop_front(), None);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_ring_buf_io() {
+        use std::io::{Read, Write};
+
+        let mut buf = RingBuf::<u8, 8>::new();
+        let mut out = [0; 16];
+        assert_eq!(buf.bytes_available(), 0);
+        assert_eq!(buf.read(&mut out).unwrap(), 0);
+
+        buf.write_all(b"abcde").unwrap();
+        assert_eq!(buf.bytes_available(), 5);
+        assert_eq!(buf.read(&mut out[..3]).unwrap(), 3);
+        assert_eq!(&out[..3], b"abc");
+
+        // 折り返し
+        buf.write_all(b"fghij").unwrap();
+        assert_eq!(buf.bytes_available(), 7);
+        assert_eq!(buf.read(&mut out).unwrap(), 7);
+        assert_eq!(&out[..7], b"defghij");
+        assert_eq!(buf.bytes_available(), 0);
+
+        // 容量を超えた書き込みでは古いバイトが消える
+        buf.write_all(b"0123456789").unwrap();
+        assert_eq!(buf.bytes_available(), 8);
+        assert_eq!(buf.read(&mut out).unwrap(), 8);
+        assert_eq!(&out[..8], b"23456789");
+
+        // 様々な書き込み・読み取りの大きさで`VecDeque`と一致することを確認
+        let mut expected = std::collections::VecDeque::new();
+        let mut value = 0u8;
+        for i in 0..200usize {
+            let write_len = i * 7 % 11;
+            let read_len = i * 5 % 9;
+
+            for _ in 0..write_len {
+                buf.write_all(&[value]).unwrap();
+                expected.push_back(value);
+                if expected.len() > 8 {
+                    expected.pop_front();
+                }
+                value = value.wrapping_add(1);
+            }
+            assert_eq!(buf.bytes_available(), expected.len());
+
+            let n = buf.read(&mut out[..read_len]).unwrap();
+            assert_eq!(n, read_len.min(expected.len()));
+            let expected_read: Vec<u8> = expected.drain(..n).collect();
+            assert_eq!(&out[..n], &*expected_read);
+        }
+    }
+
     #[test]
     fn test_ring_buf_drop() {
         use parking_lot::Mutex;
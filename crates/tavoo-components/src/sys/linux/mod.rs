@@ -0,0 +1,15 @@
+//! Linux向けの実装。
+//!
+//! 現状は各モジュールの型と関数の形だけを用意しており、操作は全てエラーとなる。
+//! WebViewのwebkit2gtkによる実装は、ビルドにGTKとWebKitGTKの開発用ライブラリを必要とするため未着手である。
+
+pub mod player;
+pub mod webview;
+
+/// 未対応の操作で返すエラーメッセージ。
+const UNSUPPORTED_MSG: &str = "Linuxでは未対応の操作です";
+
+#[inline]
+fn unsupported<T>() -> anyhow::Result<T> {
+    Err(anyhow::Error::msg(UNSUPPORTED_MSG))
+}
@@ -0,0 +1,250 @@
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use isdb::psi::table::ServiceId;
+
+use crate::player::{AspectRatioMode, DualMonoMode, EventHandler, PlayerSnapshot, VideoImage};
+
+use super::unsupported;
+
+#[derive(Debug, Clone)]
+pub struct PlayerEvent(());
+
+pub struct Player<H> {
+    #[allow(dead_code)]
+    event_handler: H,
+}
+
+impl<H: EventHandler + Clone> Player<H> {
+    #[inline]
+    pub fn new(_window: &winit::window::Window, event_handler: H) -> Result<Player<H>> {
+        Ok(Player { event_handler })
+    }
+
+    #[inline]
+    pub fn is_opened(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn open<P: AsRef<Path>>(&mut self, _path: P) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn open_bytes(&mut self, _data: &'static [u8]) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn open_vec(&mut self, _data: Vec<u8>) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn selected_service(&self) -> Option<isdb::filters::sorter::Service> {
+        None
+    }
+
+    #[inline]
+    pub fn active_video_tag(&self) -> Option<u8> {
+        None
+    }
+
+    #[inline]
+    pub fn active_audio_tag(&self) -> Option<u8> {
+        None
+    }
+
+    #[inline]
+    pub fn services(&self) -> Option<isdb::filters::sorter::ServiceMap> {
+        None
+    }
+
+    #[inline]
+    pub fn snapshot(&self) -> Option<PlayerSnapshot> {
+        None
+    }
+
+    #[inline]
+    pub fn select_service(&mut self, _service_id: Option<ServiceId>) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn select_video_stream(&mut self, _component_tag: u8) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn select_video_stream_by_pid(&mut self, _pid: isdb::Pid) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn select_audio_stream(&mut self, _component_tag: u8) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn handle_event(&mut self, _event: PlayerEvent) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn play(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn pause(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn stop(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn play_or_pause(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> Result<bool> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn repaint(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_bounds(&mut self, _left: u32, _top: u32, _right: u32, _bottom: u32) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    pub fn position(&self) -> Result<Duration> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_position(&mut self, _pos: Duration) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn seek_to_event_start(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn volume(&self) -> Result<f32> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_volume(&mut self, _value: f32) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn muted(&self) -> Result<bool> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_muted(&mut self, _muted: bool) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn rate_range(&self) -> Result<RangeInclusive<f32>> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn rate(&self) -> Result<f32> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_rate(&mut self, _value: f32) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn audio_delay_ms(&self) -> Result<i32> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_audio_delay_ms(&mut self, _ms: i32) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn aspect_ratio_mode(&self) -> Result<AspectRatioMode> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_aspect_ratio_mode(&mut self, _mode: AspectRatioMode) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn subtitle_delay_ms(&self) -> Result<i64> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_subtitle_delay_ms(&mut self, _ms: i64) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn video_size(&self) -> Result<(u32, u32)> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn capture_image(&self) -> Result<VideoImage> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn audio_channels(&self) -> Result<u8> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn dual_mono_mode(&self) -> Result<Option<DualMonoMode>> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_dual_mono_mode(&self, _mode: DualMonoMode) -> Result<()> {
+        unsupported()
+    }
+}
@@ -0,0 +1,231 @@
+use std::io;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::webview::{CookieInfo, Handler};
+
+use super::unsupported;
+
+pub struct RequestBody(());
+
+impl io::Read for RequestBody {
+    #[inline]
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+}
+
+pub struct ResponseBody(#[allow(dead_code)] Option<Box<dyn io::Read>>);
+
+impl ResponseBody {
+    #[inline]
+    pub fn new(read: Box<dyn io::Read>) -> ResponseBody {
+        ResponseBody(Some(read))
+    }
+
+    #[inline]
+    pub fn empty() -> ResponseBody {
+        ResponseBody(None)
+    }
+}
+
+#[derive(Default)]
+pub struct Builder(());
+
+impl Builder {
+    #[inline]
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    #[inline]
+    pub fn arguments(&mut self, _args: &str) {}
+
+    #[inline]
+    pub fn add_scheme<T>(&mut self, _name: &str, _handler: T)
+    where
+        T: Handler,
+    {
+    }
+
+    #[inline]
+    pub fn file_drop_handler<F>(&mut self, _handler: F)
+    where
+        F: FnMut(&Path) + 'static,
+    {
+    }
+
+    #[inline]
+    pub fn navigation_starting_handler<F>(&mut self, _handler: F)
+    where
+        F: FnMut(&str) -> bool + 'static,
+    {
+    }
+
+    #[inline]
+    pub fn navigation_completed_handler<F>(&mut self, _handler: F)
+    where
+        F: FnMut() + 'static,
+    {
+    }
+
+    #[inline]
+    pub fn document_title_changed_handler<F>(&mut self, _handler: F)
+    where
+        F: FnMut(&str) + 'static,
+    {
+    }
+
+    #[inline]
+    pub fn web_message_received_handler<F>(&mut self, _handler: F)
+    where
+        F: FnMut(&str) + 'static,
+    {
+    }
+
+    pub fn build(
+        self,
+        _window: &winit::window::Window,
+        create_completed: Box<dyn FnOnce(Result<()>)>,
+    ) -> WebView {
+        create_completed(unsupported());
+        WebView(())
+    }
+}
+
+pub struct WebView(());
+
+impl WebView {
+    #[inline]
+    pub fn open_dev_tools(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn focus(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn notify_parent_window_moved(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn resize(&mut self, _width: u32, _height: u32) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_background_color(&mut self, _r: u8, _g: u8, _b: u8, _a: u8) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn set_user_agent(&mut self, _ua: &str) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn user_agent(&self) -> Result<String> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn navigate(&mut self, _url: &str) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn reload(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn go_back(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn go_forward(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn can_go_back(&self) -> Result<bool> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn can_go_forward(&self) -> Result<bool> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn post_web_message(&mut self, _json: &str) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn execute_script(
+        &mut self,
+        _script: &str,
+        _callback: Option<Box<dyn FnOnce(String)>>,
+    ) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn inject_stylesheet(&mut self, _css: &str) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn print(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn print_to_pdf(
+        &mut self,
+        _path: &Path,
+        _callback: Box<dyn FnOnce(Result<()>)>,
+    ) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn cookies_clear(&mut self) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn cookies_get(
+        &self,
+        _uri: &str,
+        _callback: Box<dyn FnOnce(Result<Vec<CookieInfo>>)>,
+    ) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn add_context_menu_item(
+        &mut self,
+        _label: &str,
+        _id: u32,
+        _handler: Box<dyn Fn(u32)>,
+    ) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn remove_context_menu_item(&mut self, _id: u32) -> Result<()> {
+        unsupported()
+    }
+
+    #[inline]
+    pub fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
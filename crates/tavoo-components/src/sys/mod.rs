@@ -2,6 +2,9 @@ cfg_if::cfg_if! {
     if #[cfg(windows)] {
         mod windows;
         pub use self::windows::*;
+    } else if #[cfg(target_os = "linux")] {
+        mod linux;
+        pub use self::linux::*;
     } else {
         compile_error!("This platform is not supported");
     }
@@ -16,7 +16,7 @@ use windows::Win32::Foundation as F;
 use windows::Win32::Media::MediaFoundation as MF;
 use winit::platform::windows::WindowExtWindows;
 
-use crate::player::{DualMonoMode, EventHandler};
+use crate::player::{AspectRatioMode, DualMonoMode, EventHandler, PlayerSnapshot, VideoImage};
 
 #[derive(Debug, Clone)]
 pub struct PlayerEvent(MF::IMFMediaEvent);
@@ -38,6 +38,9 @@ struct PlayerState {
     pub volume: f32,
     pub muted: bool,
     pub rate: f32,
+    pub audio_delay_ms: i32,
+    pub aspect_ratio_mode: AspectRatioMode,
+    pub subtitle_delay_ms: i64,
 }
 
 pub struct Player<H> {
@@ -62,6 +65,9 @@ impl<H: EventHandler + Clone> Player<H> {
                 volume: 1.0,
                 muted: false,
                 rate: 1.0,
+                audio_delay_ms: 0,
+                aspect_ratio_mode: AspectRatioMode::default(),
+                subtitle_delay_ms: 0,
             })),
             event_handler,
             session: None,
@@ -77,11 +83,29 @@ impl<H: EventHandler + Clone> Player<H> {
         let _ = self.close();
 
         let file = std::fs::File::open(path)?;
+        self.open_read(file)
+    }
 
+    #[inline]
+    pub fn open_bytes(&mut self, data: &'static [u8]) -> Result<()> {
+        let _ = self.close();
+        self.open_read(std::io::Cursor::new(data))
+    }
+
+    #[inline]
+    pub fn open_vec(&mut self, data: Vec<u8>) -> Result<()> {
+        let _ = self.close();
+        self.open_read(std::io::Cursor::new(data))
+    }
+
+    fn open_read<R>(&mut self, read: R) -> Result<()>
+    where
+        R: std::io::Read + std::io::Seek + Send + 'static,
+    {
         self.session = Some(session::Session::new(
             self.player_state.clone(),
             self.event_handler.clone(),
-            file,
+            read,
         )?);
         Ok(())
     }
@@ -122,8 +146,20 @@ impl<H: EventHandler + Clone> Player<H> {
 
     pub fn services(&self) -> Option<isdb::filters::sorter::ServiceMap> {
         let extract_handler = self.session.as_ref()?.extract_handler();
-        let services = extract_handler.services();
-        Some(services.clone())
+        let snapshot = extract_handler.services_snapshot();
+        Some((*snapshot.services).clone())
+    }
+
+    pub fn snapshot(&self) -> Option<PlayerSnapshot> {
+        let extract_handler = self.session.as_ref()?.extract_handler();
+        let snapshot = extract_handler.state_snapshot();
+        Some(PlayerSnapshot {
+            services: (*snapshot.services).clone(),
+            selected: snapshot.selected_stream,
+            duration: snapshot.duration,
+            position: snapshot.position,
+            timestamp: snapshot.timestamp,
+        })
     }
 
     pub fn select_service(&mut self, service_id: Option<ServiceId>) -> Result<()> {
@@ -138,6 +174,12 @@ impl<H: EventHandler + Clone> Player<H> {
         Ok(())
     }
 
+    pub fn select_video_stream_by_pid(&mut self, pid: isdb::Pid) -> Result<()> {
+        let extract_handler = self.session_must()?.extract_handler();
+        extract_handler.select_video_stream_by_pid(pid)?;
+        Ok(())
+    }
+
     pub fn select_audio_stream(&mut self, component_tag: u8) -> Result<()> {
         let extract_handler = self.session_must()?.extract_handler();
         extract_handler.select_audio_stream(component_tag)?;
@@ -166,6 +208,15 @@ impl<H: EventHandler + Clone> Player<H> {
         Ok(())
     }
 
+    pub fn play_or_pause(&mut self) -> Result<()> {
+        self.session_must()?.play_or_pause()?;
+        Ok(())
+    }
+
+    pub fn is_playing(&self) -> Result<bool> {
+        Ok(self.session_must()?.is_playing())
+    }
+
     pub fn repaint(&mut self) -> Result<()> {
         if let Some(session) = &self.session {
             session.repaint()?;
@@ -200,6 +251,14 @@ impl<H: EventHandler + Clone> Player<H> {
         Ok(())
     }
 
+    pub fn seek_to_event_start(&mut self) -> Result<()> {
+        let pos = self
+            .session_must()?
+            .extract_handler()
+            .event_start_position()?;
+        self.set_position(pos)
+    }
+
     pub fn volume(&self) -> Result<f32> {
         let volume = self.player_state.lock().volume;
         Ok(volume)
@@ -247,11 +306,59 @@ impl<H: EventHandler + Clone> Player<H> {
         Ok(())
     }
 
+    pub fn audio_delay_ms(&self) -> Result<i32> {
+        let delay = self.player_state.lock().audio_delay_ms;
+        Ok(delay)
+    }
+
+    pub fn set_audio_delay_ms(&mut self, ms: i32) -> Result<()> {
+        /// 音声の遅延として設定できる最大のミリ秒数。
+        const MAX_AUDIO_DELAY_MS: i32 = 2000;
+
+        let ms = ms.clamp(-MAX_AUDIO_DELAY_MS, MAX_AUDIO_DELAY_MS);
+        if let Some(session) = &self.session {
+            session.set_audio_delay_ms(ms);
+        } else {
+            self.player_state.lock().audio_delay_ms = ms;
+        }
+        Ok(())
+    }
+
+    pub fn aspect_ratio_mode(&self) -> Result<AspectRatioMode> {
+        let mode = self.player_state.lock().aspect_ratio_mode;
+        Ok(mode)
+    }
+
+    pub fn set_aspect_ratio_mode(&mut self, mode: AspectRatioMode) -> Result<()> {
+        if let Some(session) = &self.session {
+            session.set_aspect_ratio_mode(mode)?;
+        } else {
+            self.player_state.lock().aspect_ratio_mode = mode;
+        }
+        Ok(())
+    }
+
+    pub fn subtitle_delay_ms(&self) -> Result<i64> {
+        let delay = self.player_state.lock().subtitle_delay_ms;
+        Ok(delay)
+    }
+
+    pub fn set_subtitle_delay_ms(&mut self, ms: i64) -> Result<()> {
+        // 字幕の通知時に参照されるため、状態を書き換えるだけで次の字幕から適用される
+        self.player_state.lock().subtitle_delay_ms = ms;
+        Ok(())
+    }
+
     pub fn video_size(&self) -> Result<(u32, u32)> {
         let size = self.session_must()?.video_size()?;
         Ok(size)
     }
 
+    pub fn capture_image(&self) -> Result<VideoImage> {
+        let image = self.session_must()?.capture_image()?;
+        Ok(image)
+    }
+
     pub fn audio_channels(&self) -> Result<u8> {
         let num_channels = self.session_must()?.audio_channels()?;
         Ok(num_channels)
@@ -11,13 +11,16 @@ use parking_lot::lock_api::{RawMutex, RawMutexTimed};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use windows::core::{self as C, implement, AsImpl, ComInterface, Interface, Result as WinResult};
 use windows::Win32::Foundation as F;
+use windows::Win32::Graphics::Gdi;
 use windows::Win32::Media::KernelStreaming::GUID_NULL;
 use windows::Win32::Media::MediaFoundation as MF;
 
 use crate::codec;
 use crate::extract::{self, ExtractHandler, Sink};
-use crate::player::{DualMonoMode, EventHandler, PlayerEvent};
-use crate::sys::com::PropVariant;
+use crate::player::{
+    delay_pos, AspectRatioMode, DualMonoMode, EventHandler, PlayerEvent, VideoImage,
+};
+use crate::sys::com::{CoBox, PropVariant};
 use crate::sys::wrap;
 
 use super::source::{AudioCodecInfo, TransportStream, VideoCodecInfo};
@@ -237,6 +240,16 @@ impl Session {
         self.inner().pause()
     }
 
+    #[inline]
+    pub fn play_or_pause(&self) -> WinResult<()> {
+        self.inner().play_or_pause()
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.inner().is_playing()
+    }
+
     #[inline]
     pub fn stop(&self) -> WinResult<()> {
         self.inner().stop()
@@ -282,11 +295,26 @@ impl Session {
         self.inner().set_rate(value)
     }
 
+    #[inline]
+    pub fn set_audio_delay_ms(&self, ms: i32) {
+        self.inner().set_audio_delay_ms(ms)
+    }
+
+    #[inline]
+    pub fn set_aspect_ratio_mode(&self, mode: AspectRatioMode) -> WinResult<()> {
+        self.inner().set_aspect_ratio_mode(mode)
+    }
+
     #[inline]
     pub fn video_size(&self) -> WinResult<(u32, u32)> {
         self.inner().video_size()
     }
 
+    #[inline]
+    pub fn capture_image(&self) -> WinResult<VideoImage> {
+        self.inner().capture_image()
+    }
+
     #[inline]
     pub fn audio_channels(&self) -> WinResult<u8> {
         self.inner().audio_channels()
@@ -325,8 +353,8 @@ impl Sink for Session {
     fn on_stream_changed(&mut self, immediate: bool, changed: extract::StreamChanged) {
         // ストリームに変化があったということはサービスは選択されている
         let mut inner = self.inner();
-        match Inner::prepare_streams_changing(&mut inner, immediate, changed.clone()) {
-            Ok(()) => inner.event_handler.on_stream_changed(changed),
+        match Inner::prepare_streams_changing(&mut inner, immediate, changed) {
+            Ok(()) => inner.event_handler.on_stream_changed(immediate, changed),
             Err(e) => inner.event_handler.on_stream_error(e),
         }
     }
@@ -404,17 +432,32 @@ impl Sink for Session {
     }
 
     fn on_caption(&mut self, pos: Option<Duration>, caption: &isdb::filters::sorter::Caption) {
-        self.inner().event_handler.on_caption(pos, caption);
+        let inner = self.inner();
+        let delay_ms = inner.player_state.lock().subtitle_delay_ms;
+        let pos = pos.map(|pos| delay_pos(pos, delay_ms));
+        inner.event_handler.on_caption(pos, caption);
     }
 
     fn on_superimpose(&mut self, pos: Option<Duration>, caption: &isdb::filters::sorter::Caption) {
-        self.inner().event_handler.on_superimpose(pos, caption);
+        let inner = self.inner();
+        let delay_ms = inner.player_state.lock().subtitle_delay_ms;
+        let pos = pos.map(|pos| delay_pos(pos, delay_ms));
+        inner.event_handler.on_superimpose(pos, caption);
     }
 
     fn on_timestamp_updated(&mut self, timestamp: Duration) {
         self.inner().event_handler.on_timestamp_updated(timestamp);
     }
 
+    fn on_volume_requested(&mut self, volume: f32) {
+        let mut inner = self.inner();
+        if let Err(e) = inner.set_volume(volume) {
+            // 音声を再生できない状態でも次の再生に向けて音量を保持する
+            log::debug!("音量を適用できない：{}", e);
+            inner.player_state.lock().volume = volume;
+        }
+    }
+
     fn on_end_of_stream(&mut self) {
         self.inner().on_end_of_stream();
     }
@@ -642,6 +685,7 @@ impl Inner {
             this.extract_handler.clone(),
             &video_codec_info,
             &audio_codec_info,
+            this.player_state.lock().audio_delay_ms,
         )?;
 
         source.deliver_video_packets(iter_packets(video_packets));
@@ -1199,6 +1243,10 @@ impl Inner {
                 if let Err(e) = self.set_bounds_internal(left, top, right, bottom) {
                     log::warn!("映像領域を設定できない：{}", e);
                 }
+                if let Err(e) = self.set_aspect_ratio_mode_internal(player_state.aspect_ratio_mode)
+                {
+                    log::warn!("縦横比の扱いを設定できない：{}", e);
+                }
                 if let Err(e) = self.set_volume_internal(player_state.volume) {
                     log::warn!("音量を設定できない：{}", e);
                 }
@@ -1415,6 +1463,23 @@ impl Inner {
         Ok(())
     }
 
+    pub fn play_or_pause(&mut self) -> WinResult<()> {
+        if self.is_playing() {
+            self.pause()
+        } else {
+            self.play()
+        }
+    }
+
+    /// 再生中、または再生が要求されている場合に`true`を返す。
+    pub fn is_playing(&self) -> bool {
+        match self.op_request.command {
+            Some(Command::Start) => true,
+            Some(Command::Pause | Command::Stop) => false,
+            None => self.state == State::Started,
+        }
+    }
+
     fn do_stop(&mut self) -> WinResult<()> {
         log::trace!("Session::do_stop");
 
@@ -1483,6 +1548,30 @@ impl Inner {
         r
     }
 
+    fn set_aspect_ratio_mode_internal(&self, mode: AspectRatioMode) -> WinResult<()> {
+        let Some(video_display) = &self.video_display else {
+            return Ok(());
+        };
+
+        let value = match mode {
+            AspectRatioMode::Preserve => MF::MFVideoARMode_PreservePicture,
+            AspectRatioMode::Stretch => MF::MFVideoARMode_None,
+            AspectRatioMode::NonLinearStretch => MF::MFVideoARMode_NonLinearStretch,
+        };
+        unsafe { video_display.SetAspectRatioMode(value.0 as u32)? };
+
+        Ok(())
+    }
+
+    pub fn set_aspect_ratio_mode(&mut self, mode: AspectRatioMode) -> WinResult<()> {
+        let r = self.set_aspect_ratio_mode_internal(mode);
+        if r.is_ok() {
+            self.player_state.lock().aspect_ratio_mode = mode;
+        }
+
+        r
+    }
+
     pub fn position(&self) -> WinResult<Duration> {
         let Some(pres) = &self.presentation else {
             log::trace!("presentationがないのに位置要求");
@@ -1613,6 +1702,13 @@ impl Inner {
         Ok(())
     }
 
+    pub fn set_audio_delay_ms(&mut self, ms: i32) {
+        if let Some(pres) = &self.presentation {
+            pres.source.set_audio_delay_ms(ms);
+        }
+        self.player_state.lock().audio_delay_ms = ms;
+    }
+
     pub fn video_size(&self) -> WinResult<(u32, u32)> {
         let pres = self.presentation.as_ref().ok_or(MF::MF_E_INVALIDREQUEST)?;
         let size = if let VideoCodecInfo::Mpeg2(seq) = &pres.video_codec_info {
@@ -1627,6 +1723,54 @@ impl Inner {
         Ok(size)
     }
 
+    pub fn capture_image(&self) -> WinResult<VideoImage> {
+        let vd = self.video_display.as_ref().ok_or(MF::MF_E_INVALIDREQUEST)?;
+
+        let mut bih = Gdi::BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<Gdi::BITMAPINFOHEADER>() as u32,
+            ..Default::default()
+        };
+        let mut dib = ptr::null_mut();
+        let mut dib_len = 0;
+        let mut timestamp = 0;
+        unsafe { vd.GetCurrentImage(&mut bih, &mut dib, &mut dib_len, &mut timestamp)? };
+        if dib.is_null() {
+            return Err(MF::MF_E_INVALIDREQUEST.into());
+        }
+        // Safety: GetCurrentImageによってCoTaskMemAllocで確保された領域
+        let dib = unsafe { CoBox::from_raw(ptr::slice_from_raw_parts_mut(dib, dib_len as usize)) };
+
+        // 32ビットのBGRA以外は想定しない
+        if bih.biBitCount != 32 || bih.biWidth <= 0 || bih.biHeight == 0 {
+            return Err(MF::MF_E_INVALIDMEDIATYPE.into());
+        }
+        let width = bih.biWidth as u32;
+        let height = bih.biHeight.unsigned_abs();
+        let stride = width as usize * 4;
+        if dib.len() < stride * height as usize {
+            return Err(MF::MF_E_INVALIDMEDIATYPE.into());
+        }
+
+        let mut data = Vec::with_capacity(stride * height as usize);
+        for y in 0..height as usize {
+            // biHeightが正の場合はボトムアップ
+            let row = if bih.biHeight > 0 {
+                height as usize - 1 - y
+            } else {
+                y
+            };
+            for bgra in dib[row * stride..][..stride].chunks_exact(4) {
+                data.extend_from_slice(&[bgra[2], bgra[1], bgra[0], 0xFF]);
+            }
+        }
+
+        Ok(VideoImage {
+            width,
+            height,
+            data,
+        })
+    }
+
     pub fn audio_channels(&self) -> WinResult<u8> {
         let pres = self.presentation.as_ref().ok_or(MF::MF_E_INVALIDREQUEST)?;
         let AudioCodecInfo::Aac(frame) = &pres.audio_codec_info;
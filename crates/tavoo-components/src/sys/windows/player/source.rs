@@ -8,6 +8,7 @@ use windows::Win32::Media::MediaFoundation as MF;
 
 use crate::codec;
 use crate::extract::ExtractHandler;
+use crate::player::delay_pos;
 use crate::sys::com::PropVariant;
 use crate::sys::wrap;
 
@@ -181,6 +182,7 @@ impl TransportStream {
         extract_handler: ExtractHandler,
         video_codec_info: &VideoCodecInfo,
         audio_codec_info: &AudioCodecInfo,
+        audio_delay_ms: i32,
     ) -> WinResult<TransportStream> {
         let video_sd = create_sd(SID_VIDEO, create_video_mt(video_codec_info)?)?;
         let audio_sd = create_sd(SID_AUDIO, create_audio_mt(audio_codec_info)?)?;
@@ -213,6 +215,7 @@ impl TransportStream {
             audio_stream: dummy_stream,
 
             rate: 1.,
+            audio_delay_ms,
             pending_eos: 0,
         });
         let this = TransportStream(
@@ -255,6 +258,12 @@ impl TransportStream {
         self.outer().inner.lock()
     }
 
+    /// 以降に送る音声サンプルの時刻を`ms`ミリ秒だけ遅らせる。負の値では早める。
+    #[inline]
+    pub fn set_audio_delay_ms(&self, ms: i32) {
+        self.inner().audio_delay_ms = ms;
+    }
+
     pub fn clear_video_packets(&self) {
         Inner::clear_video_packets(&mut self.inner());
     }
@@ -367,6 +376,8 @@ struct Inner {
     audio_stream: MF::IMFMediaStream,
 
     rate: f32,
+    /// 音声サンプルの時刻をずらすミリ秒数。
+    audio_delay_ms: i32,
     pending_eos: usize,
 }
 
@@ -628,7 +639,8 @@ impl Inner {
         pos: Option<Duration>,
         payload: &[u8],
     ) -> WinResult<()> {
-        let sample = create_sample(payload, pos)?;
+        let delay_ms = this.audio_delay_ms.into();
+        let sample = create_sample(payload, pos.map(|pos| delay_pos(pos, delay_ms)))?;
         Inner::audio_stream_unlocked(this, |es| {
             es.push_sample(sample);
             es.dispatch_samples()?;
@@ -654,9 +666,10 @@ impl Inner {
         this: &mut MutexGuard<Self>,
         iter: impl IntoIterator<Item = (Option<Duration>, &'a [u8])>,
     ) -> WinResult<()> {
+        let delay_ms = this.audio_delay_ms.into();
         Inner::audio_stream_unlocked(this, |es| {
             for (pos, payload) in iter {
-                let sample = create_sample(payload, pos)?;
+                let sample = create_sample(payload, pos.map(|pos| delay_pos(pos, delay_ms)))?;
                 es.push_sample(sample);
             }
             es.dispatch_samples()?;
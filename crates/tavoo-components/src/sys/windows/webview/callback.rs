@@ -33,6 +33,54 @@ macro_rules! event_callback {
 }
 
 macro_rules! completed_callback {
+    (
+        $(#[$attr:meta])*
+        $handler:ident,
+        $intf:ty,
+        $impl:ty,
+        $arg_name:ident: $arg_type:ty => $type:ty = $convert:expr,
+    ) => {
+        #[doc = concat!("クロージャから[`", stringify!($intf), "`]を生成する。")]
+        $(#[doc = ""] #[$attr])*
+        pub fn $handler<F>(f: F) -> $intf
+        where
+            F: ::core::ops::FnOnce(::windows::core::Result<$type>) -> ::windows::core::Result<()>
+                + 'static,
+        {
+            #[::windows::core::implement($intf)]
+            struct Handler(
+                ::parking_lot::Mutex<
+                    ::core::option::Option<
+                        ::std::boxed::Box<
+                            dyn ::core::ops::FnOnce(
+                                ::windows::core::Result<$type>,
+                            )
+                                -> ::windows::core::Result<()>,
+                        >,
+                    >,
+                >,
+            );
+            impl $impl for Handler {
+                fn Invoke(
+                    &self,
+                    errorcode: ::windows::core::HRESULT,
+                    $arg_name: $arg_type,
+                ) -> ::windows::core::Result<()> {
+                    match self.0.lock().take() {
+                        Some(f) => f(errorcode.ok().and_then(
+                            |()| -> ::windows::core::Result<$type> { $convert },
+                        )),
+                        None => {
+                            log::trace!(concat!(stringify!($intf), "が二度呼ばれた"));
+                            Err(::windows::Win32::Foundation::E_UNEXPECTED.into())
+                        }
+                    }
+                }
+            }
+
+            Handler(::parking_lot::Mutex::new(Some(::std::boxed::Box::new(f)))).into()
+        }
+    };
     ($handler:ident, $intf:ty, $impl:ty, $type:ty,) => {
         #[doc = concat!("クロージャから[`", stringify!($intf), "`]を生成する。")]
         pub fn $handler<F>(f: F) -> $intf
@@ -116,6 +164,22 @@ event_callback!(
     args: ::core::option::Option<&ICoreWebView2WebMessageReceivedEventArgs>,
 );
 
+event_callback!(
+    context_menu_requested_event_handler,
+    ICoreWebView2ContextMenuRequestedEventHandler,
+    ICoreWebView2ContextMenuRequestedEventHandler_Impl,
+    sender: ::core::option::Option<&ICoreWebView2>,
+    args: ::core::option::Option<&ICoreWebView2ContextMenuRequestedEventArgs>,
+);
+
+event_callback!(
+    custom_item_selected_event_handler,
+    ICoreWebView2CustomItemSelectedEventHandler,
+    ICoreWebView2CustomItemSelectedEventHandler_Impl,
+    sender: ::core::option::Option<&ICoreWebView2ContextMenuItem>,
+    args: ::core::option::Option<&IUnknown>,
+);
+
 completed_callback!(
     environment_completed_handler,
     ICoreWebView2CreateCoreWebView2EnvironmentCompletedHandler,
@@ -129,3 +193,44 @@ completed_callback!(
     ICoreWebView2CreateCoreWebView2ControllerCompletedHandler_Impl,
     ICoreWebView2Controller,
 );
+
+completed_callback!(
+    get_cookies_completed_handler,
+    ICoreWebView2GetCookiesCompletedHandler,
+    ICoreWebView2GetCookiesCompletedHandler_Impl,
+    ICoreWebView2CookieList,
+);
+
+completed_callback!(
+    print_completed_handler,
+    ICoreWebView2PrintCompletedHandler,
+    ICoreWebView2PrintCompletedHandler_Impl,
+    printstatus: COREWEBVIEW2_PRINT_STATUS => COREWEBVIEW2_PRINT_STATUS = Ok(printstatus),
+);
+
+completed_callback!(
+    /// クロージャにはPDFの出力に成功したかどうかが渡される。
+    print_to_pdf_completed_handler,
+    ICoreWebView2PrintToPdfCompletedHandler,
+    ICoreWebView2PrintToPdfCompletedHandler_Impl,
+    issuccessful: ::windows::Win32::Foundation::BOOL => bool = Ok(issuccessful.as_bool()),
+);
+
+completed_callback!(
+    /// クロージャにはスクリプトの実行結果がJSON形式の文字列で渡される。
+    execute_script_completed_handler,
+    ICoreWebView2ExecuteScriptCompletedHandler,
+    ICoreWebView2ExecuteScriptCompletedHandler_Impl,
+    resultobjectasjson: &::windows::core::PCWSTR => String =
+        unsafe { resultobjectasjson.to_string() }
+            .map_err(|_| ::windows::Win32::Foundation::E_INVALIDARG.into()),
+);
+
+completed_callback!(
+    /// クロージャには追加されたスクリプトのIDが渡される。
+    add_script_completed_handler,
+    ICoreWebView2AddScriptToExecuteOnDocumentCreatedCompletedHandler,
+    ICoreWebView2AddScriptToExecuteOnDocumentCreatedCompletedHandler_Impl,
+    id: &::windows::core::PCWSTR => String = unsafe { id.to_string() }
+        .map_err(|_| ::windows::Win32::Foundation::E_INVALIDARG.into()),
+);
@@ -23,7 +23,7 @@ use winit::platform::windows::WindowExtWindows;
 use crate::sys::com;
 use crate::sys::wide_string::WideString;
 use crate::sys::wrap;
-use crate::webview::{Handler, Request};
+use crate::webview::{CookieInfo, Handler, Request};
 
 use super::wide_string::WideStr;
 
@@ -100,6 +100,24 @@ struct Handlers {
     web_message_received_handler: Option<Box<dyn FnMut(&str)>>,
 }
 
+/// アプリ独自のコンテキストメニュー項目。
+struct ContextMenuItem {
+    id: u32,
+    label: WideString,
+    handler: Arc<dyn Fn(u32)>,
+}
+
+impl std::fmt::Debug for ContextMenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ContextMenuItem")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+type ContextMenuItems = Arc<Mutex<Vec<ContextMenuItem>>>;
+
 #[derive(Default)]
 pub struct Builder {
     env_opts: options::CoreWebView2EnvironmentOptions,
@@ -186,6 +204,7 @@ impl Builder {
         let options: WV2::ICoreWebView2EnvironmentOptions = env_opts.into();
 
         let state = Arc::new(Mutex::new(State::Pending(PendingOps::default())));
+        let context_menu_items = ContextMenuItems::default();
         let r = unsafe {
             WV2::CreateCoreWebView2EnvironmentWithOptions(
                 None,
@@ -197,6 +216,7 @@ impl Builder {
                     create_completed.clone(),
                     scheme_handlers,
                     handlers,
+                    context_menu_items.clone(),
                 ),
             )
         };
@@ -228,7 +248,10 @@ impl Builder {
             Ok(()) => {}
         }
 
-        return WebView { state };
+        return WebView {
+            state,
+            context_menu_items,
+        };
 
         fn env_completed_handler(
             state: Arc<Mutex<State>>,
@@ -236,6 +259,7 @@ impl Builder {
             create_completed: CreateCompleted,
             scheme_handlers: FxHashMap<String, Box<dyn Handler>>,
             handlers: Handlers,
+            context_menu_items: ContextMenuItems,
         ) -> WV2::ICoreWebView2CreateCoreWebView2EnvironmentCompletedHandler {
             callback::environment_completed_handler(move |env| {
                 let r = 'r: {
@@ -249,6 +273,7 @@ impl Builder {
                                 create_completed.clone(),
                                 scheme_handlers,
                                 handlers,
+                                context_menu_items,
                                 env.clone(),
                             ),
                         ));
@@ -275,6 +300,7 @@ impl Builder {
             create_completed: CreateCompleted,
             scheme_handlers: FxHashMap<String, Box<dyn Handler>>,
             handlers: Handlers,
+            context_menu_items: ContextMenuItems,
             env: WV2::ICoreWebView2Environment,
         ) -> WV2::ICoreWebView2CreateCoreWebView2ControllerCompletedHandler {
             callback::controller_completed_handler(move |controller| {
@@ -343,6 +369,10 @@ impl Builder {
                             &WebView::req_handler(&env, scheme_handlers),
                             &mut token,
                         ));
+                        tri!('r, webview.add_ContextMenuRequested(
+                            &WebView::context_menu_handler(&env, context_menu_items),
+                            &mut token,
+                        ));
                     }
 
                     if let Some(handler) = handlers.navigation_starting_handler {
@@ -368,7 +398,7 @@ impl Builder {
                         }
                     }
 
-                    tri!('r, state.lock().shift(webview, controller));
+                    tri!('r, state.lock().shift(hwnd_webview, webview, controller));
 
                     Ok(())
                 };
@@ -384,18 +414,57 @@ impl Builder {
     }
 }
 
+/// 印刷の完了を受け取るコールバック。
+struct PrintCallback(Box<dyn FnOnce(Result<()>)>);
+
+impl std::fmt::Debug for PrintCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("PrintCallback")
+    }
+}
+
+/// スクリプトの実行結果を受け取るコールバック。
+struct ScriptCallback(Box<dyn FnOnce(String)>);
+
+impl std::fmt::Debug for ScriptCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("ScriptCallback")
+    }
+}
+
+/// Cookieの取得結果を受け取るコールバック。
+struct CookiesCallback(Box<dyn FnOnce(Result<Vec<CookieInfo>>)>);
+
+impl std::fmt::Debug for CookiesCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("CookiesCallback")
+    }
+}
+
 #[derive(Debug, Default)]
 struct PendingOps {
     open_dev_tools: bool,
     focus: bool,
     notify_parent_window_moved: bool,
     resize: Option<(u32, u32)>,
+    background_color: Option<WV2::COREWEBVIEW2_COLOR>,
+    user_agent: Option<String>,
     navigate: Option<WideString>,
+    reload: bool,
+    go_back: bool,
+    go_forward: bool,
     web_messages: Vec<WideString>,
+    scripts: Vec<(WideString, Option<ScriptCallback>)>,
+    stylesheets: Vec<WideString>,
+    print: bool,
+    print_to_pdf: Vec<(WideString, PrintCallback)>,
+    cookies_clear: bool,
+    cookies_get: Vec<(WideString, CookiesCallback)>,
 }
 
 #[derive(Debug)]
 struct Inner {
+    hwnd_webview: F::HWND,
     webview: ICoreWebView2,
     controller: ICoreWebView2Controller,
 }
@@ -410,6 +479,7 @@ enum State {
 #[derive(Debug, Clone)]
 pub struct WebView {
     state: Arc<Mutex<State>>,
+    context_menu_items: ContextMenuItems,
 }
 
 impl State {
@@ -418,8 +488,14 @@ impl State {
     /// # パニック
     ///
     /// 状態が`State::Pending`でない場合、このメソッドはパニックする。
-    fn shift(&mut self, webview: ICoreWebView2, controller: ICoreWebView2Controller) -> Result<()> {
+    fn shift(
+        &mut self,
+        hwnd_webview: F::HWND,
+        webview: ICoreWebView2,
+        controller: ICoreWebView2Controller,
+    ) -> Result<()> {
         let inner = Inner {
+            hwnd_webview,
             webview,
             controller,
         };
@@ -445,9 +521,46 @@ impl State {
         if let Some((width, height)) = ops.resize {
             inner.resize(width, height)?;
         }
+        if let Some(color) = ops.background_color {
+            inner.set_background_color(color)?;
+        }
+        if let Some(ua) = ops.user_agent.as_deref() {
+            // 古いランタイムでも他の操作は続行する
+            if let Err(e) = inner.set_user_agent(ua) {
+                log::warn!("User-Agentを設定できない：{:#}", e);
+            }
+        }
+        // 遷移後のドキュメントにも適用されるよう遷移より先に登録する
+        for script in ops.stylesheets {
+            inner.inject_stylesheet(&*script)?;
+        }
         if let Some(url) = ops.navigate.as_deref() {
             inner.navigate(url)?;
         }
+        if ops.reload {
+            inner.reload()?;
+        }
+        if ops.go_back {
+            inner.go_back()?;
+        }
+        if ops.go_forward {
+            inner.go_forward()?;
+        }
+        for (script, callback) in ops.scripts {
+            inner.execute_script(&*script, callback)?;
+        }
+        if ops.print {
+            inner.print()?;
+        }
+        for (path, callback) in ops.print_to_pdf {
+            inner.print_to_pdf(&*path, callback)?;
+        }
+        if ops.cookies_clear {
+            inner.cookies_clear()?;
+        }
+        for (uri, callback) in ops.cookies_get {
+            inner.cookies_get(&*uri, callback)?;
+        }
 
         Ok(())
     }
@@ -484,16 +597,159 @@ impl Inner {
         }
     }
 
+    fn set_background_color(&self, color: WV2::COREWEBVIEW2_COLOR) -> WinResult<()> {
+        unsafe { self.controller.SetDefaultBackgroundColor(color)? };
+        // 不透明な背景色では透過させない
+        set_transparent(self.hwnd_webview, color.A != 255);
+        Ok(())
+    }
+
+    fn settings2(&self) -> Result<WV2::ICoreWebView2Settings2> {
+        // ICoreWebView2Settings2は1.0.864以降でしか使えない
+        let settings = unsafe { self.webview.Settings()? };
+        let settings = settings
+            .cast::<WV2::ICoreWebView2Settings2>()
+            .context("WebView2のバージョンが古い")?;
+        Ok(settings)
+    }
+
+    fn set_user_agent(&self, ua: &str) -> Result<()> {
+        let ua: WideString = ua.into();
+        unsafe { self.settings2()?.SetUserAgent(ua.as_pcwstr())? };
+        Ok(())
+    }
+
+    fn user_agent(&self) -> Result<String> {
+        let settings = self.settings2()?;
+        let ua = wrap::wrap(|a| unsafe { settings.UserAgent(a) })?.to_string()?;
+        Ok(ua)
+    }
+
     #[inline]
     fn navigate(&self, url: &WideStr) -> WinResult<()> {
         unsafe { self.webview.Navigate(url.as_pcwstr()) }
     }
 
+    #[inline]
+    fn reload(&self) -> WinResult<()> {
+        unsafe { self.webview.Reload() }
+    }
+
+    #[inline]
+    fn go_back(&self) -> WinResult<()> {
+        unsafe { self.webview.GoBack() }
+    }
+
+    #[inline]
+    fn go_forward(&self) -> WinResult<()> {
+        unsafe { self.webview.GoForward() }
+    }
+
+    #[inline]
+    fn can_go_back(&self) -> WinResult<bool> {
+        wrap::wrap(|a| unsafe { self.webview.CanGoBack(a) })
+    }
+
+    #[inline]
+    fn can_go_forward(&self) -> WinResult<bool> {
+        wrap::wrap(|a| unsafe { self.webview.CanGoForward(a) })
+    }
+
     #[inline]
     fn post_web_message(&self, message: &WideStr) -> WinResult<()> {
         unsafe { self.webview.PostWebMessageAsJson(message.as_pcwstr()) }
     }
 
+    fn execute_script(&self, script: &WideStr, callback: Option<ScriptCallback>) -> WinResult<()> {
+        let handler = callback::execute_script_completed_handler(move |result| {
+            let json = result?;
+            if let Some(callback) = callback {
+                (callback.0)(json);
+            }
+            Ok(())
+        });
+        unsafe { self.webview.ExecuteScript(script.as_pcwstr(), &handler) }
+    }
+
+    fn inject_stylesheet(&self, script: &WideStr) -> WinResult<()> {
+        let handler = callback::add_script_completed_handler(|result| {
+            if let Err(e) = result {
+                log::error!("スタイルシートを登録できない：{}", e);
+            }
+            Ok(())
+        });
+        unsafe {
+            self.webview
+                .AddScriptToExecuteOnDocumentCreated(script.as_pcwstr(), &handler)?;
+        }
+
+        // 表示中のドキュメントにも即座に適用する
+        self.execute_script(script, None)
+    }
+
+    fn print(&self) -> Result<()> {
+        // Printは1.0.1518以降でしか使えない
+        let webview = self
+            .webview
+            .cast::<WV2::ICoreWebView2_16>()
+            .context("WebView2のバージョンが古い")?;
+
+        let handler = callback::print_completed_handler(|result| {
+            match result {
+                Ok(WV2::COREWEBVIEW2_PRINT_STATUS_SUCCEEDED) => {}
+                Ok(status) => log::error!("印刷に失敗：{:?}", status),
+                Err(e) => log::error!("印刷に失敗：{}", e),
+            }
+            Ok(())
+        });
+        unsafe { webview.Print(None::<&WV2::ICoreWebView2PrintSettings>, &handler)? };
+        Ok(())
+    }
+
+    fn print_to_pdf(&self, path: &WideStr, callback: PrintCallback) -> WinResult<()> {
+        let handler = callback::print_to_pdf_completed_handler(move |result| {
+            let result = match result {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(anyhow::Error::msg("PDFを出力できない")),
+                Err(e) => Err(anyhow::Error::from(e)),
+            };
+            (callback.0)(result);
+            Ok(())
+        });
+        unsafe {
+            self.webview.PrintToPdf(
+                path.as_pcwstr(),
+                None::<&WV2::ICoreWebView2PrintSettings>,
+                &handler,
+            )
+        }
+    }
+
+    #[inline]
+    fn cookies_clear(&self) -> WinResult<()> {
+        unsafe { self.webview.CookieManager()?.DeleteAllCookies() }
+    }
+
+    fn cookies_get(&self, uri: &WideStr, callback: CookiesCallback) -> WinResult<()> {
+        let handler = callback::get_cookies_completed_handler(move |result| {
+            let result = result
+                .and_then(|list| {
+                    let count = wrap::wrap(|a| unsafe { list.Count(a) })?;
+                    (0..count)
+                        .map(|i| cookie_info(&unsafe { list.GetValueAtIndex(i)? }))
+                        .collect::<WinResult<Vec<_>>>()
+                })
+                .map_err(anyhow::Error::from);
+            (callback.0)(result);
+            Ok(())
+        });
+        unsafe {
+            self.webview
+                .CookieManager()?
+                .GetCookies(uri.as_pcwstr(), &handler)
+        }
+    }
+
     #[inline]
     fn close(&self) -> WinResult<()> {
         unsafe { self.controller.Close() }
@@ -514,10 +770,8 @@ impl WebView {
         callback::navigation_completed_event_handler(move |_sender, _args| {
             if !loaded {
                 // 背景を透過させる（2/2）
-                unsafe {
-                    let ex_style = WM::GetWindowLongPtrW(hwnd_webview, WM::GWL_EXSTYLE)
-                        | WM::WS_EX_TRANSPARENT.0 as isize;
-                    WM::SetWindowLongPtrW(hwnd_webview, WM::GWL_EXSTYLE, ex_style);
+                if unsafe { controller.DefaultBackgroundColor()? }.A != 255 {
+                    set_transparent(hwnd_webview, true);
                 }
 
                 // D&Dを捕捉（2/2）
@@ -565,10 +819,6 @@ impl WebView {
                 log::trace!("独自スキームへのアクセス：{}", uri);
 
                 let method = wrap::wrap(|s| unsafe { req.Method(s) })?.to_string()?;
-                if !method.eq_ignore_ascii_case("GET") {
-                    log::warn!("独自スキームへのGET以外のアクセス：{}", method);
-                    break 'res None;
-                }
 
                 let Some(handler) = uri
                     .scheme_str()
@@ -632,6 +882,59 @@ impl WebView {
         })
     }
 
+    fn context_menu_handler(
+        env: &ICoreWebView2Environment,
+        items: ContextMenuItems,
+    ) -> WV2::ICoreWebView2ContextMenuRequestedEventHandler {
+        let env = env.clone();
+
+        callback::context_menu_requested_event_handler(move |_, args| {
+            let args = args.ok_or(F::E_POINTER)?;
+
+            let items = items.lock();
+            if items.is_empty() {
+                return Ok(());
+            }
+
+            // ページ自体に対するコンテキストメニューにのみ追加する
+            let target = unsafe { args.ContextMenuTarget()? };
+            let mut kind = WV2::COREWEBVIEW2_CONTEXT_MENU_TARGET_KIND::default();
+            unsafe { target.Kind(&mut kind)? };
+            if kind != WV2::COREWEBVIEW2_CONTEXT_MENU_TARGET_KIND_PAGE {
+                return Ok(());
+            }
+
+            let menu_items = unsafe { args.MenuItems()? };
+            let mut index = wrap::wrap(|a| unsafe { menu_items.Count(a) })?;
+            for item in &*items {
+                let menu_item = unsafe {
+                    env.CreateContextMenuItem(
+                        item.label.as_pcwstr(),
+                        None::<&Com::IStream>,
+                        WV2::COREWEBVIEW2_CONTEXT_MENU_ITEM_KIND_COMMAND,
+                    )?
+                };
+
+                let id = item.id;
+                let handler = item.handler.clone();
+                let mut token = windows::Win32::System::WinRT::EventRegistrationToken::default();
+                unsafe {
+                    menu_item.add_CustomItemSelected(
+                        &callback::custom_item_selected_event_handler(move |_, _| {
+                            handler(id);
+                            Ok(())
+                        }),
+                        &mut token,
+                    )?;
+                    menu_items.InsertValueAtIndex(index, &menu_item)?;
+                }
+                index += 1;
+            }
+
+            Ok(())
+        })
+    }
+
     const FAILED_MSG: &str = "WebViewは使用不可能";
 
     pub fn open_dev_tools(&mut self) -> Result<()> {
@@ -670,6 +973,43 @@ impl WebView {
         Ok(())
     }
 
+    pub fn set_background_color(&mut self, r: u8, g: u8, b: u8, a: u8) -> Result<()> {
+        let color = WV2::COREWEBVIEW2_COLOR {
+            R: r,
+            G: g,
+            B: b,
+            A: a,
+        };
+
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.background_color = Some(color),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.set_background_color(color)?,
+        }
+        Ok(())
+    }
+
+    pub fn set_user_agent(&mut self, ua: &str) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.user_agent = Some(ua.to_string()),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.set_user_agent(ua)?,
+        }
+        Ok(())
+    }
+
+    pub fn user_agent(&self) -> Result<String> {
+        match &*self.state.lock() {
+            State::Pending(PendingOps {
+                user_agent: Some(ua),
+                ..
+            }) => Ok(ua.clone()),
+            State::Pending(_) => Err(anyhow::Error::msg("WebViewの生成中")),
+            State::Failed => Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.user_agent(),
+        }
+    }
+
     pub fn navigate(&mut self, url: &str) -> Result<()> {
         let url = url.into();
 
@@ -681,6 +1021,50 @@ impl WebView {
         Ok(())
     }
 
+    pub fn reload(&mut self) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.reload = true,
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.reload()?,
+        }
+        Ok(())
+    }
+
+    pub fn go_back(&mut self) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.go_back = true,
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.go_back()?,
+        }
+        Ok(())
+    }
+
+    pub fn go_forward(&mut self) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.go_forward = true,
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.go_forward()?,
+        }
+        Ok(())
+    }
+
+    pub fn can_go_back(&self) -> Result<bool> {
+        match &*self.state.lock() {
+            // 生成中のWebViewには履歴が無い
+            State::Pending(_) => Ok(false),
+            State::Failed => Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => Ok(inner.can_go_back()?),
+        }
+    }
+
+    pub fn can_go_forward(&self) -> Result<bool> {
+        match &*self.state.lock() {
+            State::Pending(_) => Ok(false),
+            State::Failed => Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => Ok(inner.can_go_forward()?),
+        }
+    }
+
     pub fn post_web_message(&mut self, json: &str) -> Result<()> {
         let json = json.into();
 
@@ -692,6 +1076,116 @@ impl WebView {
         Ok(())
     }
 
+    pub fn execute_script(
+        &mut self,
+        script: &str,
+        callback: Option<Box<dyn FnOnce(String)>>,
+    ) -> Result<()> {
+        let script = script.into();
+        let callback = callback.map(ScriptCallback);
+
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.scripts.push((script, callback)),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.execute_script(&*script, callback)?,
+        }
+        Ok(())
+    }
+
+    pub fn inject_stylesheet(&mut self, css: &str) -> Result<()> {
+        let script = stylesheet_script(css).as_str().into();
+
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.stylesheets.push(script),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.inject_stylesheet(&*script)?,
+        }
+        Ok(())
+    }
+
+    pub fn print(&mut self) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.print = true,
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.print()?,
+        }
+        Ok(())
+    }
+
+    pub fn print_to_pdf(
+        &mut self,
+        path: &Path,
+        callback: Box<dyn FnOnce(Result<()>)>,
+    ) -> Result<()> {
+        let path = WideString::from_os_str(path.as_os_str());
+        let callback = PrintCallback(callback);
+
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.print_to_pdf.push((path, callback)),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.print_to_pdf(&*path, callback)?,
+        }
+        Ok(())
+    }
+
+    pub fn cookies_clear(&mut self) -> Result<()> {
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.cookies_clear = true,
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.cookies_clear()?,
+        }
+        Ok(())
+    }
+
+    pub fn cookies_get(
+        &self,
+        uri: &str,
+        callback: Box<dyn FnOnce(Result<Vec<CookieInfo>>)>,
+    ) -> Result<()> {
+        let uri = uri.into();
+        let callback = CookiesCallback(callback);
+
+        match &mut *self.state.lock() {
+            State::Pending(ops) => ops.cookies_get.push((uri, callback)),
+            State::Failed => return Err(anyhow::Error::msg(Self::FAILED_MSG)),
+            State::Ready(inner) => inner.cookies_get(&*uri, callback)?,
+        }
+        Ok(())
+    }
+
+    pub fn add_context_menu_item(
+        &mut self,
+        label: &str,
+        id: u32,
+        handler: Box<dyn Fn(u32)>,
+    ) -> Result<()> {
+        if let State::Failed = &*self.state.lock() {
+            return Err(anyhow::Error::msg(Self::FAILED_MSG));
+        }
+
+        let item = ContextMenuItem {
+            id,
+            label: label.into(),
+            handler: handler.into(),
+        };
+
+        let mut items = self.context_menu_items.lock();
+        match items.iter_mut().find(|item| item.id == id) {
+            Some(old) => *old = item,
+            None => items.push(item),
+        }
+        Ok(())
+    }
+
+    pub fn remove_context_menu_item(&mut self, id: u32) -> Result<()> {
+        if let State::Failed = &*self.state.lock() {
+            return Err(anyhow::Error::msg(Self::FAILED_MSG));
+        }
+
+        self.context_menu_items.lock().retain(|item| item.id != id);
+        Ok(())
+    }
+
     pub fn close(&mut self) -> Result<()> {
         match &mut *self.state.lock() {
             // 生成中のWebViewにも生成失敗後にもやれることはない
@@ -702,6 +1196,61 @@ impl WebView {
     }
 }
 
+/// `css`を`document.adoptedStyleSheets`に追加するスクリプトを生成する。
+fn stylesheet_script(css: &str) -> String {
+    let mut script =
+        String::from("(() => { const sheet = new CSSStyleSheet(); sheet.replaceSync(\"");
+    for c in css.chars() {
+        match c {
+            '"' => script.push_str("\\\""),
+            '\\' => script.push_str("\\\\"),
+            '\n' => script.push_str("\\n"),
+            '\r' => script.push_str("\\r"),
+            // 制御文字や行区切り文字は文字列リテラル中に置けないためエスケープする
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                let _ = write!(script, "\\u{:04X}", c as u32);
+            }
+            c => script.push(c),
+        }
+    }
+    script.push_str(
+        "\"); document.adoptedStyleSheets = [...document.adoptedStyleSheets, sheet]; })();",
+    );
+    script
+}
+
+/// `ICoreWebView2Cookie`から[`CookieInfo`]を生成する。
+fn cookie_info(cookie: &WV2::ICoreWebView2Cookie) -> WinResult<CookieInfo> {
+    let is_session = wrap::wrap(|a| unsafe { cookie.IsSession(a) })?;
+    let expires = if is_session {
+        None
+    } else {
+        Some(wrap::wrap(|a| unsafe { cookie.Expires(a) })?)
+    };
+
+    Ok(CookieInfo {
+        name: wrap::wrap(|a| unsafe { cookie.Name(a) })?.to_string()?,
+        value: wrap::wrap(|a| unsafe { cookie.Value(a) })?.to_string()?,
+        domain: wrap::wrap(|a| unsafe { cookie.Domain(a) })?.to_string()?,
+        path: wrap::wrap(|a| unsafe { cookie.Path(a) })?.to_string()?,
+        expires,
+        is_secure: wrap::wrap(|a| unsafe { cookie.IsSecure(a) })?,
+    })
+}
+
+/// WebViewのウィンドウに`WS_EX_TRANSPARENT`を設定、または解除する。
+fn set_transparent(hwnd_webview: F::HWND, transparent: bool) {
+    unsafe {
+        let ex_style = WM::GetWindowLongPtrW(hwnd_webview, WM::GWL_EXSTYLE);
+        let ex_style = if transparent {
+            ex_style | WM::WS_EX_TRANSPARENT.0 as isize
+        } else {
+            ex_style & !(WM::WS_EX_TRANSPARENT.0 as isize)
+        };
+        WM::SetWindowLongPtrW(hwnd_webview, WM::GWL_EXSTYLE, ex_style);
+    }
+}
+
 fn build_request(
     req: &WV2::ICoreWebView2WebResourceRequest,
     uri: http::Uri,
@@ -724,10 +1273,15 @@ fn build_request(
         }
     }
 
-    let req = builder.body(()).map_err(|e| {
-        log::debug!("不正なリクエスト：{}", e);
-        F::E_FAIL
-    })?;
+    // 内容のないリクエストでは取得に失敗する
+    let body = RequestBody(unsafe { req.Content() }.ok());
+
+    let req = builder
+        .body(crate::webview::RequestBody(body))
+        .map_err(|e| {
+            log::debug!("不正なリクエスト：{}", e);
+            F::E_FAIL
+        })?;
     Ok(req)
 }
 
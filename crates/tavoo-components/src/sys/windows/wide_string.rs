@@ -1,6 +1,8 @@
+use std::ffi::OsStr;
 use std::fmt;
 use std::fmt::Write;
 use std::ops;
+use std::os::windows::ffi::OsStrExt;
 
 use windows::core::PCWSTR;
 
@@ -56,6 +58,11 @@ impl WideString {
     pub fn from_str(s: &str) -> WideString {
         WideString(s.encode_utf16().chain([0]).collect())
     }
+
+    #[inline]
+    pub fn from_os_str(s: &OsStr) -> WideString {
+        WideString(s.encode_wide().chain([0]).collect())
+    }
 }
 
 impl From<&str> for WideString {
@@ -41,6 +41,34 @@ impl WrappedValue for SIZE {
     }
 }
 
+impl WrappedValue for u32 {
+    type Native = u32;
+
+    #[inline]
+    fn placeholder() -> Self {
+        0
+    }
+
+    #[inline]
+    fn into_native(self) -> Self::Native {
+        self
+    }
+}
+
+impl WrappedValue for f64 {
+    type Native = f64;
+
+    #[inline]
+    fn placeholder() -> Self {
+        0.
+    }
+
+    #[inline]
+    fn into_native(self) -> Self::Native {
+        self
+    }
+}
+
 impl WrappedValue for PWSTR {
     type Native = super::com::CoString;
 
@@ -8,8 +8,18 @@ pub use http;
 
 use crate::sys::webview as imp;
 
+/// 独自スキームを処理するための[`Handler`]に渡されるリクエストの内容。
+pub struct RequestBody(pub(crate) imp::RequestBody);
+
+impl io::Read for RequestBody {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
 /// 独自スキームを処理するための[`Handler`]から返されるレスポンスの内容。
-pub struct ResponseBody(pub(crate) imp::ResponseBody);
+pub struct ResponseBody(#[cfg_attr(not(windows), allow(dead_code))] pub(crate) imp::ResponseBody);
 
 impl ResponseBody {
     /// [`Read`][`io::Read`]を実装するオブジェクトからレスポンス内容を生成する。
@@ -26,7 +36,9 @@ impl ResponseBody {
 }
 
 /// 独自スキームを処理するための[`Handler`]に渡されるリクエスト。
-pub type Request<T = ()> = http::Request<T>;
+///
+/// POST等で送信された内容は[`Request::body_mut`][http::Request::body_mut]から読み取ることができる。
+pub type Request<T = RequestBody> = http::Request<T>;
 
 /// 独自スキームを処理するための[`Handler`]から返されるレスポンス。
 pub type Response<T = ResponseBody> = http::Response<T>;
@@ -47,6 +59,23 @@ where
     }
 }
 
+/// WebViewに保存されているCookieの情報。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CookieInfo {
+    /// Cookieの名前。
+    pub name: String,
+    /// Cookieの値。
+    pub value: String,
+    /// Cookieが有効なドメイン。
+    pub domain: String,
+    /// Cookieが有効なパス。
+    pub path: String,
+    /// Cookieの有効期限（UNIX時間による秒数）。セッションCookieの場合は`None`。
+    pub expires: Option<f64>,
+    /// Cookieが安全な接続でのみ送信されるかどうか。
+    pub is_secure: bool,
+}
+
 /// WebViewに設定を与える。
 pub struct Builder {
     inner: imp::Builder,
@@ -69,8 +98,8 @@ impl Builder {
 
     /// 独自スキームとそこにアクセスがあった際のハンドラーを追加する。
     ///
-    /// このスキームにはGETリクエストのみを送信することができる。
-    /// それ以外のリクエストを送信した場合、400等のエラーが返される。
+    /// GETに限らず全てのメソッドのリクエストがハンドラーに渡されるため、
+    /// 対応しないメソッドについてはハンドラー側でエラーを返す必要がある。
     pub fn add_scheme<T>(mut self, name: &str, handler: T) -> Builder
     where
         T: Handler,
@@ -202,18 +231,161 @@ impl WebView {
         self.inner.resize(width, height)
     }
 
+    /// WebViewの背景色を設定する。
+    ///
+    /// 既定では背景は透過される。`a`が255の場合、背景は透過されなくなる。
+    #[inline]
+    pub fn set_background_color(&mut self, r: u8, g: u8, b: u8, a: u8) -> Result<()> {
+        self.inner.set_background_color(r, g, b, a)
+    }
+
+    /// WebViewから送信されるリクエストのUser-Agentを設定する。
+    ///
+    /// WebView2ランタイム1.0.864以降が必要。それより古い場合はエラーを返すが、
+    /// WebViewの生成中に呼ばれた場合は警告をログに記録してUser-Agentを既定のままとする。
+    #[inline]
+    pub fn set_user_agent(&mut self, ua: &str) -> Result<()> {
+        self.inner.set_user_agent(ua)
+    }
+
+    /// WebViewから送信されるリクエストのUser-Agentを返す。
+    ///
+    /// WebView2ランタイム1.0.864以降が必要。
+    /// WebViewの生成中は[`WebView::set_user_agent`]で設定した値を返し、未設定の場合はエラーを返す。
+    #[inline]
+    pub fn user_agent(&self) -> Result<String> {
+        self.inner.user_agent()
+    }
+
     /// WebViewを`url`に遷移させる。
     #[inline]
     pub fn navigate(&mut self, url: &str) -> Result<()> {
         self.inner.navigate(url)
     }
 
+    /// 表示中のコンテンツを再読み込みする。
+    #[inline]
+    pub fn reload(&mut self) -> Result<()> {
+        self.inner.reload()
+    }
+
+    /// 履歴を一つ戻る。
+    #[inline]
+    pub fn go_back(&mut self) -> Result<()> {
+        self.inner.go_back()
+    }
+
+    /// 履歴を一つ進む。
+    #[inline]
+    pub fn go_forward(&mut self) -> Result<()> {
+        self.inner.go_forward()
+    }
+
+    /// 履歴を戻れるかどうかを返す。
+    ///
+    /// WebViewの生成中は常に`false`を返す。
+    #[inline]
+    pub fn can_go_back(&self) -> Result<bool> {
+        self.inner.can_go_back()
+    }
+
+    /// 履歴を進めるかどうかを返す。
+    ///
+    /// WebViewの生成中は常に`false`を返す。
+    #[inline]
+    pub fn can_go_forward(&self) -> Result<bool> {
+        self.inner.can_go_forward()
+    }
+
     /// JSON形式のメッセージをWebViewに送る。
     #[inline]
     pub fn post_web_message(&mut self, json: &str) -> Result<()> {
         self.inner.post_web_message(json)
     }
 
+    /// WebView上でJavaScriptを実行する。
+    ///
+    /// WebViewの生成中に呼ばれた場合、スクリプトは生成完了後に呼ばれた順で実行される。
+    #[inline]
+    pub fn execute_script(&mut self, script: &str) -> Result<()> {
+        self.inner.execute_script(script, None)
+    }
+
+    /// WebView上でJavaScriptを実行し、その結果をJSON形式の文字列として`callback`に渡す。
+    ///
+    /// スクリプトの実行に失敗した場合、`callback`は呼ばれない。
+    #[inline]
+    pub fn execute_script_with_result<F>(&mut self, script: &str, callback: F) -> Result<()>
+    where
+        F: FnOnce(String) + 'static,
+    {
+        self.inner.execute_script(script, Some(Box::new(callback)))
+    }
+
+    /// CSSをスタイルシートとしてWebViewに追加する。
+    ///
+    /// 追加したスタイルシートは表示中のコンテンツに即座に適用されるほか、遷移後のコンテンツにも適用される。
+    #[inline]
+    pub fn inject_stylesheet(&mut self, css: &str) -> Result<()> {
+        self.inner.inject_stylesheet(css)
+    }
+
+    /// 表示中のコンテンツを既定のプリンターで印刷する。
+    ///
+    /// WebView2ランタイムが古く印刷に対応していない場合はエラーを返す。
+    /// 印刷自体の失敗はログに記録される。
+    #[inline]
+    pub fn print(&mut self) -> Result<()> {
+        self.inner.print()
+    }
+
+    /// 表示中のコンテンツをPDFとして`path`に出力する。
+    ///
+    /// 出力は非同期に行われ、完了したら`completed`が呼ばれる。引数の`Result`によって出力時のエラーを捉えることができる。
+    #[inline]
+    pub fn print_to_pdf<F>(&mut self, path: &Path, completed: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>) + 'static,
+    {
+        self.inner.print_to_pdf(path, Box::new(completed))
+    }
+
+    /// WebViewに保存されている全てのCookieを削除する。
+    #[inline]
+    pub fn cookies_clear(&mut self) -> Result<()> {
+        self.inner.cookies_clear()
+    }
+
+    /// `uri`に送信されるCookieを取得し、その一覧を`completed`に渡す。
+    ///
+    /// Cookieの取得は非同期に行われる。引数の`Result`によって取得時のエラーを捉えることができる。
+    #[inline]
+    pub fn cookies_get<F>(&self, uri: &str, completed: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<CookieInfo>>) + 'static,
+    {
+        self.inner.cookies_get(uri, Box::new(completed))
+    }
+
+    /// ページに対するコンテキストメニューにアプリ独自の項目を追加する。
+    ///
+    /// 項目が選択されると`handler`に`id`が渡される。
+    /// 既に同じ`id`の項目が追加されている場合、その項目を置き換える。
+    #[inline]
+    pub fn add_context_menu_item<F>(&mut self, label: &str, id: u32, handler: F) -> Result<()>
+    where
+        F: Fn(u32) + 'static,
+    {
+        self.inner
+            .add_context_menu_item(label, id, Box::new(handler))
+    }
+
+    /// [`WebView::add_context_menu_item`]で追加した項目を削除する。
+    #[inline]
+    pub fn remove_context_menu_item(&mut self, id: u32) -> Result<()> {
+        self.inner.remove_context_menu_item(id)
+    }
+
     /// WebViewを閉じる。
     #[inline]
     pub fn close(&mut self) -> Result<()> {
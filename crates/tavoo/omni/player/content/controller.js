@@ -1,10 +1,12 @@
 // @ts-check
 
 /**
+ * @typedef {import("./message.d.ts").AspectRatioMode} AspectRatioMode
  * @typedef {import("./message.d.ts").Caption} Caption
  * @typedef {import("./message.d.ts").Command} Command
  * @typedef {import("./message.d.ts").DualMonoMode} DualMonoMode
  * @typedef {import("./message.d.ts").Notification} Notification
+ * @typedef {import("./message.d.ts").ScreenshotFormat} ScreenshotFormat
  * @typedef {import("./message.d.ts").Service} Service
  */
 
@@ -63,6 +65,64 @@ export class EventEvent extends PlayerEvent {
   }
 }
 
+/**
+ * 現在のイベントが別サービスのイベントにリレーされる際に発生するイベント。
+ */
+export class EventRelayEvent extends PlayerEvent {
+  /**
+   * リレー元のサービス識別。
+   *
+   * @type {number}
+   */
+  fromService;
+
+  /**
+   * リレー先のサービス識別。
+   *
+   * @type {number}
+   */
+  toService;
+
+  /**
+   * リレー先のイベント識別。
+   *
+   * @type {number}
+   */
+  eventId;
+
+  /**
+   * @param {string} type
+   * @param {EventInit & { fromService: number; toService: number; eventId: number }} options
+   */
+  constructor(type, options) {
+    super(type, options);
+    this.fromService = options.fromService;
+    this.toService = options.toService;
+    this.eventId = options.eventId;
+  }
+}
+
+/**
+ * ファイルがドロップされた際に発生するイベント。
+ */
+export class DroppedFileEvent extends PlayerEvent {
+  /**
+   * ドロップされたファイルへのパス。
+   *
+   * @type {string}
+   */
+  path;
+
+  /**
+   * @param {string} type
+   * @param {EventInit & { path: string }} options
+   */
+  constructor(type, options) {
+    super(type, options);
+    this.path = options.path;
+  }
+}
+
 /**
  * 字幕・文字スーパーを受信した際に発生するイベント。
  */
@@ -92,6 +152,27 @@ export class CaptionEvent extends PlayerEvent {
   }
 }
 
+/**
+ * スクリーンショットが撮影された際に発生するイベント。
+ */
+export class ScreenshotEvent extends PlayerEvent {
+  /**
+   * 画像を表すデータURL。
+   *
+   * @type {string}
+   */
+  dataUrl;
+
+  /**
+   * @param {string} type
+   * @param {EventInit & { dataUrl: string }} options
+   */
+  constructor(type, options) {
+    super(type, options);
+    this.dataUrl = options.dataUrl;
+  }
+}
+
 /**
  * 全サービスを保持するクラス。
  *
@@ -224,6 +305,11 @@ export const gController = new class Controller extends EventTarget {
         this.dispatchEvent(new PlayerEvent("source"));
         break;
 
+      case "dropped-file":
+        // ファイルがドロップされた
+        this.dispatchEvent(new DroppedFileEvent("dropped-file", { path: noti.path }));
+        break;
+
       case "volume":
         this.#volume = noti.volume;
         this.#muted = noti.muted;
@@ -326,6 +412,15 @@ export const gController = new class Controller extends EventTarget {
         break;
       }
 
+      case "event-relay":
+        // 現在のイベントが別サービスにリレーされる
+        this.dispatchEvent(new EventRelayEvent("event-relay", {
+          fromService: noti.fromService,
+          toService: noti.toService,
+          eventId: noti.eventId,
+        }));
+        break;
+
       case "service-changed":
         // サービスが選択し直された
         this.#currentServiceId = noti.newServiceId;
@@ -369,6 +464,17 @@ export const gController = new class Controller extends EventTarget {
         this.dispatchEvent(new PlayerEvent("timestamp"));
         break;
 
+      case "subtitle-delay-changed":
+        // 字幕の表示遅延が変更された
+        this.#subtitleDelay = noti.delayMs;
+        this.dispatchEvent(new PlayerEvent("subtitle-delay"));
+        break;
+
+      case "screenshot":
+        // スクリーンショットが撮影された
+        this.dispatchEvent(new ScreenshotEvent("screenshot", { dataUrl: noti.dataUrl }));
+        break;
+
       case "error":
         // エラーが発生した
         alert(noti.message);
@@ -749,6 +855,20 @@ export const gController = new class Controller extends EventTarget {
     });
   }
 
+  /**
+   * @type {number}
+   */
+  #subtitleDelay = 0;
+
+  /**
+   * 字幕・文字スーパーの表示遅延（ミリ秒単位）。
+   *
+   * @type {number}
+   */
+  get subtitleDelay() {
+    return this.#subtitleDelay;
+  }
+
   /**
    * 再生を開始する。
    */
@@ -777,6 +897,18 @@ export const gController = new class Controller extends EventTarget {
     this.#postCommand({ command: "close" });
   }
 
+  /**
+   * ファイルを開く。
+   *
+   * @param {string} path
+   */
+  openFile(path) {
+    this.#postCommand({
+      command: "open-file",
+      path,
+    });
+  }
+
   /**
    * サービスを選択する。
    *
@@ -812,4 +944,61 @@ export const gController = new class Controller extends EventTarget {
       componentTag,
     });
   }
+
+  /**
+   * 字幕・文字スーパーの表示遅延を設定する。
+   *
+   * 正の値では字幕を遅らせ、負の値では字幕を早める。
+   *
+   * @param {number} delayMs ミリ秒単位の遅延
+   */
+  setSubtitleDelay(delayMs) {
+    if (!Number.isSafeInteger(delayMs)) {
+      throw new Error("不正な字幕の遅延");
+    }
+
+    this.#postCommand({
+      command: "set-subtitle-delay",
+      delayMs,
+    });
+  }
+
+  /**
+   * 映像の縦横比の扱いを設定する。
+   *
+   * @param {AspectRatioMode} mode
+   */
+  setAspectRatioMode(mode) {
+    if (!["preserve", "stretch", "non-linear-stretch"].includes(mode)) {
+      throw new Error("不正な縦横比の扱い");
+    }
+
+    this.#postCommand({
+      command: "set-aspect-ratio-mode",
+      mode,
+    });
+  }
+
+  /**
+   * 選択中サービスにおける現在の番組の開始位置にシークする。
+   */
+  seekToEventStart() {
+    this.#postCommand({
+      command: "seek-to-event-start",
+    });
+  }
+
+  /**
+   * 現在の映像のスクリーンショットを撮影する。
+   *
+   * 撮影された画像は`screenshot`イベントで通知される。
+   *
+   * @param {ScreenshotFormat} format
+   */
+  screenshot(format) {
+    this.#postCommand({
+      command: "screenshot",
+      format,
+    });
+  }
 };
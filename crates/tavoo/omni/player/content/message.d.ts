@@ -2,6 +2,16 @@
 
 export type PlaybackState = "open-pending" | "playing" | "paused" | "stopped" | "closed";
 export type DualMonoMode = "left" | "right" | "stereo" | "mix";
+export type AspectRatioMode = "preserve" | "stretch" | "non-linear-stretch";
+
+/** スクリーンショットの画像形式。 */
+export type ScreenshotFormat = {
+  type: "jpeg";
+  /** `1`～`100`の画質。大きいほど高画質。 */
+  quality: number;
+} | {
+  type: "png";
+};
 
 /** ホストからの通知。 */
 export type Notification = {
@@ -9,6 +19,11 @@ export type Notification = {
   notification: "source";
   /** 開かれたファイルへのパスだが、ファイルが閉じられた場合は`null`。 */
   path: string | null;
+} | {
+  /** ファイルがドロップされた。このファイルは続けて開かれ、結果は`source`通知または`error`通知で送られる。 */
+  notification: "dropped-file";
+  /** ドロップされたファイルへのパス。 */
+  path: string;
 } | {
   /** 音量。 */
   notification: "volume";
@@ -76,6 +91,12 @@ export type Notification = {
   serviceId: number;
   isPresent: boolean;
   event: TvEvent;
+} | {
+  /** 現在のイベントがリレーされる。 */
+  notification: "event-relay";
+  fromService: number;
+  toService: number;
+  eventId: number;
 } | {
   /** サービスが選択し直された。 */
   notification: "service-changed";
@@ -105,6 +126,16 @@ export type Notification = {
   /** TSの日付時刻。 */
   notification: "timestamp";
   timestamp: Timestamp;
+} | {
+  /** 字幕の表示遅延が変更された。 */
+  notification: "subtitle-delay-changed";
+  /** ミリ秒単位の遅延。 */
+  delayMs: number;
+} | {
+  /** スクリーンショットが撮影された。 */
+  notification: "screenshot";
+  /** 画像を表すデータURL。 */
+  dataUrl: string;
 } | {
   /** エラーが発生した。 */
   notification: "error";
@@ -138,10 +169,17 @@ export type Command = {
 } | {
   /** 再生終了。 */
   command: "close";
+} | {
+  /** ファイルを開く。 */
+  command: "open-file";
+  path: string;
 } | {
   /** 再生位置の変更。 */
   command: "set-position";
   position: number;
+} | {
+  /** 選択中サービスにおける現在の番組の開始位置へのシーク。 */
+  command: "seek-to-event-start";
 } | {
   /** 音量の変更。 */
   command: "set-volume";
@@ -158,6 +196,10 @@ export type Command = {
   /** デュアルモノラルの再生方法の変更。 */
   command: "set-dual-mono-mode";
   mode: DualMonoMode;
+} | {
+  /** 映像の縦横比の扱いの変更。 */
+  command: "set-aspect-ratio-mode";
+  mode: AspectRatioMode;
 } | {
   /** サービスの選択。 */
   command: "select-service";
@@ -171,6 +213,15 @@ export type Command = {
   /** 音声ストリームの選択。 */
   command: "select-audio-stream";
   componentTag: number;
+} | {
+  /** 字幕の表示遅延の変更。 */
+  command: "set-subtitle-delay";
+  /** ミリ秒単位の遅延。正の値では字幕を遅らせ、負の値では字幕を早める。 */
+  delayMs: number;
+} | {
+  /** 表示中の映像のスクリーンショットを撮影する。撮影した画像は`screenshot`通知で送られる。 */
+  command: "screenshot";
+  format: ScreenshotFormat;
 };
 
 // bin.rs
@@ -318,6 +369,8 @@ export type Service = {
   audioStreams: Stream[];
   providerName: string;
   serviceName: string;
+  logoId: number | null;
+  logoDownloadDataId: number | null;
   presentEvent: TvEvent | null;
   followingEvent: TvEvent | null;
 };
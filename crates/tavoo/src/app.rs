@@ -10,7 +10,7 @@ use winit::window::WindowBuilder;
 
 use crate::message::caption::Caption;
 use crate::message::time::Timestamp;
-use crate::message::{Command, Notification, PlaybackState};
+use crate::message::{Command, Notification, PlaybackState, ScreenshotFormat};
 
 /// 値がエラーの際にラベル付きブロックを抜ける。
 macro_rules! tri {
@@ -51,6 +51,40 @@ impl EventLoopProxy {
     }
 }
 
+/// 映像の画像を`format`で符号化し、データURLとして返す。
+fn encode_screenshot(
+    image: &player::VideoImage,
+    format: ScreenshotFormat,
+) -> anyhow::Result<String> {
+    use base64::engine::{general_purpose::STANDARD, Engine};
+    use image::ImageEncoder;
+
+    let mut buf = Vec::new();
+    let mime = match format {
+        ScreenshotFormat::Jpeg { quality } => {
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, quality.clamp(1, 100))
+                .write_image(
+                    &image.data,
+                    image.width,
+                    image.height,
+                    image::ColorType::Rgba8,
+                )?;
+            "image/jpeg"
+        }
+        ScreenshotFormat::Png => {
+            image::codecs::png::PngEncoder::new(&mut buf).write_image(
+                &image.data,
+                image.width,
+                image.height,
+                image::ColorType::Rgba8,
+            )?;
+            "image/png"
+        }
+    };
+
+    Ok(format!("data:{};base64,{}", mime, STANDARD.encode(&buf)))
+}
+
 #[derive(Debug, Clone)]
 struct PlayerEventHandler {
     proxy: EventLoopProxy,
@@ -237,6 +271,11 @@ impl tavoo_components::player::EventHandler for PlayerEventHandler {
         }
         .expect("is_presentで示されるイベントは必須")
         .into();
+        let relay = if is_present {
+            service.relay_event()
+        } else {
+            None
+        };
 
         self.proxy.dispatch_task(move |app| {
             app.send_notification(Notification::Event {
@@ -244,6 +283,13 @@ impl tavoo_components::player::EventHandler for PlayerEventHandler {
                 is_present,
                 event,
             });
+            if let Some((to_service, event_id)) = relay {
+                app.send_notification(Notification::EventRelay {
+                    from_service: service_id,
+                    to_service: to_service.get(),
+                    event_id: event_id.get(),
+                });
+            }
         });
     }
 
@@ -260,7 +306,7 @@ impl tavoo_components::player::EventHandler for PlayerEventHandler {
         });
     }
 
-    fn on_stream_changed(&self, _: tavoo_components::extract::StreamChanged) {
+    fn on_stream_changed(&self, _: bool, _: tavoo_components::extract::StreamChanged) {
         self.proxy.dispatch_task(|app| {
             if let (Some(video_component_tag), Some(audio_component_tag)) =
                 (app.player.active_video_tag(), app.player.active_audio_tag())
@@ -406,6 +452,13 @@ impl App {
         }
     }
 
+    fn on_file_dropped(&mut self, path: &Path) {
+        self.send_notification(Notification::DroppedFile {
+            path: path.to_string_lossy().into_owned(),
+        });
+        self.open(path);
+    }
+
     fn closed(&mut self) {
         self.closing = false;
 
@@ -573,6 +626,9 @@ impl App {
                         self.closed();
                     }
                 }
+                Command::OpenFile { path } => {
+                    self.open(Path::new(&*path));
+                }
                 Command::SetPosition { position } => {
                     tri!('r, self
                         .player
@@ -580,6 +636,13 @@ impl App {
                         .map_err(|e| format!("再生位置を設定できません：{}", e)));
                     self.seeking = true;
                 }
+                Command::SeekToEventStart => {
+                    tri!('r, self
+                        .player
+                        .seek_to_event_start()
+                        .map_err(|e| format!("番組の先頭に移動できません：{}", e)));
+                    self.seeking = true;
+                }
                 Command::SetVolume { volume } => {
                     tri!('r, self
                         .player
@@ -604,6 +667,12 @@ impl App {
                         .set_dual_mono_mode(mode.into())
                         .map_err(|e| format!("デュアルモノラルの再生方法を設定できません：{}", e)));
                 }
+                Command::SetAspectRatioMode { mode } => {
+                    tri!('r, self
+                        .player
+                        .set_aspect_ratio_mode(mode.into())
+                        .map_err(|e| format!("縦横比の扱いを設定できません：{}", e)));
+                }
                 Command::SelectService { service_id } => {
                     let service_id = service_id.and_then(ServiceId::new);
                     tri!('r, self.player
@@ -622,6 +691,22 @@ impl App {
                         .select_audio_stream(component_tag)
                         .map_err(|e| format!("音声ストリームを選択できません：{}", e)));
                 }
+                Command::SetSubtitleDelay { delay_ms } => {
+                    tri!('r, self
+                        .player
+                        .set_subtitle_delay_ms(delay_ms)
+                        .map_err(|e| format!("字幕の遅延を設定できません：{}", e)));
+                    self.send_notification(Notification::SubtitleDelayChanged { delay_ms });
+                }
+                Command::Screenshot { format } => {
+                    let image = tri!('r, self
+                        .player
+                        .capture_image()
+                        .map_err(|e| format!("映像を取得できません：{}", e)));
+                    let data_url = tri!('r, encode_screenshot(&image, format)
+                        .map_err(|e| format!("スクリーンショットを保存できません：{}", e)));
+                    self.send_notification(Notification::Screenshot { data_url });
+                }
             }
 
             Ok(())
@@ -659,7 +744,7 @@ pub fn run() -> anyhow::Result<()> {
             let proxy = proxy.clone();
             move |path| {
                 let path = path.to_path_buf();
-                proxy.dispatch_task(move |app| app.open(&*path))
+                proxy.dispatch_task(move |app| app.on_file_dropped(&*path))
             }
         })
         .navigation_completed_handler({
@@ -716,7 +801,7 @@ pub fn run() -> anyhow::Result<()> {
                     }
                 }
 
-                WindowEvent::DroppedFile(path) => app.open(&*path),
+                WindowEvent::DroppedFile(path) => app.on_file_dropped(&*path),
 
                 WindowEvent::CloseRequested => {
                     control_flow.set_exit();
@@ -754,3 +839,42 @@ pub fn run() -> anyhow::Result<()> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_data_url(data_url: &str, mime: &str) -> image::DynamicImage {
+        use base64::engine::{general_purpose::STANDARD, Engine};
+
+        let prefix = format!("data:{};base64,", mime);
+        assert!(data_url.starts_with(&prefix), "{}", data_url);
+        let data = STANDARD.decode(&data_url[prefix.len()..]).unwrap();
+        image::load_from_memory(&data).unwrap()
+    }
+
+    #[test]
+    fn test_encode_screenshot() {
+        let image = player::VideoImage {
+            width: 2,
+            height: 2,
+            data: vec![
+                0xFF, 0x00, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0xFF, // 1行目
+                0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // 2行目
+            ],
+        };
+
+        let png = encode_screenshot(&image, ScreenshotFormat::Png).unwrap();
+        let decoded = decode_data_url(&png, "image/png");
+        assert_eq!(decoded.into_rgba8().into_raw(), image.data);
+
+        let jpeg = encode_screenshot(&image, ScreenshotFormat::Jpeg { quality: 90 }).unwrap();
+        let decoded = decode_data_url(&jpeg, "image/jpeg");
+        assert_eq!((decoded.width(), decoded.height()), (2, 2));
+
+        // 範囲外の品質は丸められる
+        let jpeg = encode_screenshot(&image, ScreenshotFormat::Jpeg { quality: 0 }).unwrap();
+        let decoded = decode_data_url(&jpeg, "image/jpeg");
+        assert_eq!((decoded.width(), decoded.height()), (2, 2));
+    }
+}
@@ -57,6 +57,45 @@ impl From<DualMonoMode> for tavoo_components::player::DualMonoMode {
     }
 }
 
+/// 映像の縦横比の扱い。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AspectRatioMode {
+    /// 映像の縦横比を保ち、余白を黒帯で埋める。
+    Preserve,
+    /// 縦横比を無視して映像領域全体に引き伸ばす。
+    Stretch,
+    /// 中央付近の縦横比を保ちつつ、端を非線形に引き伸ばして映像領域全体を埋める。
+    NonLinearStretch,
+}
+
+impl From<AspectRatioMode> for tavoo_components::player::AspectRatioMode {
+    fn from(mode: AspectRatioMode) -> tavoo_components::player::AspectRatioMode {
+        match mode {
+            AspectRatioMode::Preserve => tavoo_components::player::AspectRatioMode::Preserve,
+            AspectRatioMode::Stretch => tavoo_components::player::AspectRatioMode::Stretch,
+            AspectRatioMode::NonLinearStretch => {
+                tavoo_components::player::AspectRatioMode::NonLinearStretch
+            }
+        }
+    }
+}
+
+/// スクリーンショットの画像形式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ScreenshotFormat {
+    /// JPEG。
+    #[serde(rename_all = "camelCase")]
+    Jpeg {
+        /// `1`～`100`の画質。大きいほど高画質。
+        quality: u8,
+    },
+    /// PNG。
+    #[serde(rename_all = "camelCase")]
+    Png,
+}
+
 /// WebViewへの通知。
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(tag = "notification", rename_all = "kebab-case")]
@@ -67,6 +106,14 @@ pub enum Notification {
         /// 開かれたファイルへのパスだが、ファイルが閉じられた場合は`None`（`null`）。
         path: Option<String>,
     },
+    /// ファイルがドロップされた。
+    ///
+    /// このファイルは続けて開かれ、結果は[`Notification::Source`]または[`Notification::Error`]で通知される。
+    #[serde(rename_all = "camelCase")]
+    DroppedFile {
+        /// ドロップされたファイルへのパス。
+        path: String,
+    },
     /// 音量。
     #[serde(rename_all = "camelCase")]
     Volume { volume: f64, muted: bool },
@@ -121,6 +168,16 @@ pub enum Notification {
         is_present: bool,
         event: service::Event,
     },
+    /// 現在のイベントが別サービスのイベントにリレーされる。
+    #[serde(rename_all = "camelCase")]
+    EventRelay {
+        /// リレー元のサービス識別。
+        from_service: u16,
+        /// リレー先のサービス識別。
+        to_service: u16,
+        /// リレー先のイベント識別。
+        event_id: u16,
+    },
     /// サービスが選択し直された。
     #[serde(rename_all = "camelCase")]
     ServiceChanged {
@@ -153,6 +210,18 @@ pub enum Notification {
     /// TSの日付時刻。
     #[serde(rename_all = "camelCase")]
     Timestamp { timestamp: time::Timestamp },
+    /// 字幕の表示遅延が変更された。
+    #[serde(rename_all = "camelCase")]
+    SubtitleDelayChanged {
+        /// ミリ秒単位の遅延。
+        delay_ms: i64,
+    },
+    /// スクリーンショットが撮影された。
+    #[serde(rename_all = "camelCase")]
+    Screenshot {
+        /// 画像を表すデータURL。
+        data_url: String,
+    },
     /// エラーが発生した。
     #[serde(rename_all = "camelCase")]
     Error { message: String },
@@ -187,9 +256,15 @@ pub enum Command {
     /// 再生終了。
     #[serde(rename_all = "camelCase")]
     Close,
+    /// ファイルを開く。
+    #[serde(rename_all = "camelCase")]
+    OpenFile { path: String },
     /// 再生位置の変更。
     #[serde(rename_all = "camelCase")]
     SetPosition { position: f64 },
+    /// 選択中サービスにおける現在の番組の開始位置へのシーク。
+    #[serde(rename_all = "camelCase")]
+    SeekToEventStart,
     /// 音量の変更。
     #[serde(rename_all = "camelCase")]
     SetVolume { volume: f64 },
@@ -202,6 +277,9 @@ pub enum Command {
     /// デュアルモノラルの再生方法の変更。
     #[serde(rename_all = "camelCase")]
     SetDualMonoMode { mode: DualMonoMode },
+    /// 映像の縦横比の扱いの変更。
+    #[serde(rename_all = "camelCase")]
+    SetAspectRatioMode { mode: AspectRatioMode },
     /// サービスの選択。
     #[serde(rename_all = "camelCase")]
     SelectService {
@@ -214,4 +292,17 @@ pub enum Command {
     /// 音声ストリームの選択。
     #[serde(rename_all = "camelCase")]
     SelectAudioStream { component_tag: u8 },
+    /// 字幕の表示遅延の変更。
+    #[serde(rename_all = "camelCase")]
+    SetSubtitleDelay {
+        /// ミリ秒単位の遅延。
+        ///
+        /// 正の値では字幕を遅らせ、負の値では字幕を早める。
+        delay_ms: i64,
+    },
+    /// 表示中の映像のスクリーンショットを撮影する。
+    ///
+    /// 撮影した画像は[`Notification::Screenshot`]で通知される。
+    #[serde(rename_all = "camelCase")]
+    Screenshot { format: ScreenshotFormat },
 }
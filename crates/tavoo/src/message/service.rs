@@ -167,6 +167,8 @@ pub struct Service {
     audio_streams: Vec<Stream>,
     provider_name: String,
     service_name: String,
+    logo_id: Option<u16>,
+    logo_download_data_id: Option<u16>,
     present_event: Option<Event>,
     following_event: Option<Event>,
 }
@@ -181,6 +183,8 @@ impl From<&isdb::filters::sorter::Service> for Service {
             audio_streams: service.audio_streams().iter().map(Into::into).collect(),
             provider_name: service.provider_name().to_string(Default::default()),
             service_name: service.service_name().to_string(Default::default()),
+            logo_id: service.logo_id(),
+            logo_download_data_id: service.logo_download_data_id(),
             present_event: service.present_event().map(Into::into),
             following_event: service.following_event().map(Into::into),
         }
@@ -7,6 +7,14 @@ pub struct TavooHandler;
 
 impl Handler for TavooHandler {
     fn handle(&mut self, req: Request) -> Response {
+        if req.method() != http::Method::GET {
+            log::warn!("GET以外のアクセス：{}", req.method());
+            return Response::builder()
+                .status(http::StatusCode::METHOD_NOT_ALLOWED)
+                .body(ResponseBody::empty())
+                .unwrap();
+        }
+
         let Some(host) = req.uri().host() else {
             log::error!("ホストがない：{}", req.uri());
             return Response::builder()